@@ -24,6 +24,7 @@ use npm1300_rs::{
     sysreg::{
         VbusInCurrentLimit,
     },
+    units::Milliamps,
     NPM1300,
 };
 
@@ -56,7 +57,7 @@ async fn main(_spawner: Spawner) {
     let _ = npm1300.use_ntc_measurements().await;
 
     defmt::info!("Configuring Charging...");
-    let _ = npm1300.set_charger_current(300).await;
+    let _ = npm1300.set_charger_current(Milliamps(300)).await;
     let _ = npm1300.set_termination_current_level(ChargerTerminationCurrentLevelSelect::SEL10).await;
     let _ = npm1300.set_normal_temperature_termination_voltage(ChargerTerminationVoltage::V4_20).await;
     let _ = npm1300.set_warm_temperature_termination_voltage(ChargerTerminationVoltage::V4_10).await;
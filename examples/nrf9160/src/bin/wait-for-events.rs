@@ -0,0 +1,73 @@
+#![no_std]
+#![no_main]
+
+//! Example demonstrating how to sleep until the NPM1300 PMIC reports an event
+
+use embassy_executor::Spawner;
+use embassy_nrf::{
+    bind_interrupts,
+    gpio::{Input, Pull},
+    peripherals,
+    twim::{self, Twim},
+};
+
+use {defmt_rtt as _, panic_probe as _};
+
+use npm1300_rs::{
+    events::{ChargerEvents, Events, VbusEvents},
+    gpios::{GpioConfigBuilder, GpioMode, GpioPin},
+    NPM1300,
+};
+
+bind_interrupts!(struct Irqs {
+    SERIAL0 => twim::InterruptHandler<peripherals::SERIAL0>;
+});
+
+#[embassy_executor::main]
+async fn main(_spawner: Spawner) {
+    let p = embassy_nrf::init(Default::default());
+
+    let sdapin = p.P0_28;
+    let sclpin = p.P0_29;
+    let mut config = twim::Config::default();
+
+    // Modify the configuration fields
+    config.sda_pullup = true;
+    config.scl_pullup = true;
+
+    defmt::info!("Configuring TWIM...");
+    let twi = Twim::new(p.SERIAL0, Irqs, sdapin, sclpin, config);
+
+    // Host pin connected to the PMIC GPIO0
+    let mut irq = Input::new(p.P0_30, Pull::Down);
+
+    let mut npm1300 = NPM1300::new(twi, embassy_time::Delay);
+
+    defmt::info!("Configuring GPIO0 as interrupt output...");
+    let gpio_config = GpioConfigBuilder::new().mode(GpioMode::GpoIrq).build();
+    npm1300.configure_gpio(GpioPin::Gpio0, gpio_config).await.unwrap();
+
+    defmt::info!("Enabling USB and charging events...");
+    let events = Events {
+        vbus: VbusEvents::DETECTED | VbusEvents::REMOVED,
+        charger: ChargerEvents::COMPLETED | ChargerEvents::ERROR,
+        ..Events::empty()
+    };
+    npm1300.enable_events(events).await.unwrap();
+
+    loop {
+        let events = npm1300.wait_for_events(&mut irq).await.unwrap();
+        if events.vbus.contains(VbusEvents::DETECTED) {
+            defmt::info!("USB plugged in");
+        }
+        if events.vbus.contains(VbusEvents::REMOVED) {
+            defmt::info!("USB unplugged");
+        }
+        if events.charger.contains(ChargerEvents::COMPLETED) {
+            defmt::info!("Charging completed");
+        }
+        if events.charger.contains(ChargerEvents::ERROR) {
+            defmt::warn!("Charger error");
+        }
+    }
+}
@@ -3,39 +3,154 @@ mod types;
 // Re-export everything in types.rs
 pub use types::*;
 
+#[cfg(feature = "blocking")]
+use crate::hal::prelude::*;
+use crate::hal::RegisterInterface;
+use crate::units::{CentiCelsius, Milliamps, Millivolts};
 use crate::{
     charger::DischargeCurrentLimit, common::Task, Ibatmeasenable, NtcThermistorType, Ntcautotim,
     Tempautotim, Vbatautoenable, Vbatburstenable,
 };
-use libm::logf;
 
-/// Convert an ADC voltage measurement to a voltage in volts
+/// Divide and round to the nearest integer
+fn div_round(numerator: u64, denominator: u64) -> u64 {
+    (numerator + denominator / 2) / denominator
+}
+
+/// Convert an ADC voltage measurement to a voltage in millivolts
 ///
 /// # Arguments
 ///
 /// * `v_adc` - The ADC voltage measurement in u16
-/// * `v_full_scale` - The full scale voltage for the measurement in volts
-fn convert_vadc_to_voltage(v_adc: u16, v_full_scale: f32) -> f32 {
-    // Convert result to f32
-    // 1023.0 is the maximum value for the 10 bit ADC.
-    (v_adc as f32 / 1023.0) * v_full_scale
+/// * `v_full_scale` - The full scale voltage for the measurement in millivolts
+fn convert_vadc_to_millivolts(v_adc: u16, v_full_scale: u16) -> Millivolts {
+    // 1023 is the maximum value for the 10 bit ADC.
+    Millivolts(div_round(v_adc as u64 * v_full_scale as u64, 1023) as u16)
+}
+
+/// Natural logarithm of `numerator / denominator` in Q16 fixed point
+///
+/// Both values must be between 1 and 1024.
+fn ln_q16(numerator: u32, denominator: u32) -> i64 {
+    const ONE: i64 = 1 << 30;
+    // ln(2) in Q30
+    const LN_2: i64 = 744_261_118;
+
+    // Normalize the ratio to m * 2^k with m in [1, 2), m in Q30
+    let mut k = numerator.ilog2() as i64 - denominator.ilog2() as i64;
+    let mut m = if k >= 0 {
+        ((numerator as i64) << 30) / ((denominator as i64) << k)
+    } else {
+        ((numerator as i64) << (30 - k)) / denominator as i64
+    };
+    if m < ONE {
+        m <<= 1;
+        k -= 1;
+    }
+
+    // ln(m) = 2 * atanh(z) with z = (m - 1) / (m + 1) <= 1/3, the series converges quickly
+    let z = ((m - ONE) << 30) / (m + ONE);
+    let z2 = (z * z) >> 30;
+    let mut term = z;
+    let mut sum = 0;
+    for n in (1..=13).step_by(2) {
+        sum += term / n;
+        term = (term * z2) >> 30;
+    }
+
+    (2 * sum + k * LN_2) >> 14
+}
+
+/// Convert an ADC NTC measurement to a temperature
+///
+/// # Arguments
+///
+/// * `ntc_adc` - The ADC NTC measurement in u16
+/// * `ntc_beta` - The beta value of the NTC thermistor
+fn convert_ntc_to_temperature(ntc_adc: u16, ntc_beta: u16) -> CentiCelsius {
+    // Keep the resistance ratio finite at both ends of the ADC range
+    let ntc_adc = ntc_adc.clamp(1, 1023) as u32;
+    let ln_ratio = ln_q16(1024 - ntc_adc, ntc_adc);
+
+    // 1 / T = 1 / 298.15 K - ln(1024 / ntc_adc - 1) / beta, with T in centikelvin
+    let beta = ntc_beta as i64;
+    let denominator = (beta * 100 * 65536 - 29815 * ln_ratio).max(1);
+    let centikelvin = 29815 * beta * 100 * 65536 / denominator;
+
+    CentiCelsius((centikelvin - 27315).clamp(i16::MIN as i64, i16::MAX as i64) as i16)
+}
+
+/// Convert an ADC die temperature measurement to a temperature
+///
+/// # Arguments
+///
+/// * `temp_adc` - The ADC die temperature measurement in u16
+fn convert_die_temperature(temp_adc: u16) -> CentiCelsius {
+    // T = 394.67 - 0.7926 * temp_adc, in degrees Celsius. Codes at both ends of the range are far
+    // outside of the operating range and do not fit in a CentiCelsius.
+    let centi_celsius = 39467 - div_round(7926 * temp_adc as u64, 100) as i32;
+    CentiCelsius(centi_celsius.clamp(i16::MIN as i32, i16::MAX as i32) as i16)
+}
+
+/// Combine a result MSB register with its 2 LSBs from a shared LSB register
+///
+/// # Arguments
+///
+/// * `msb` - The result MSB register
+/// * `lsbs` - The shared LSB register
+/// * `index` - The position of the result in the shared LSB register
+fn combine_result(msb: u8, lsbs: u8, index: u8) -> u16 {
+    ((msb as u16) << 2) | ((lsbs >> (2 * index)) & 0x03) as u16
 }
 
-impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
-    crate::NPM1300<I2c, Delay>
-{
+/// Address of ADCIBATMEASSTATUS, the first register of an [`AdcSnapshot`]
+const ADC_SNAPSHOT_ADDRESS: u16 = 0x0510;
+
+/// Number of registers in an [`AdcSnapshot`], from ADCIBATMEASSTATUS to ADCGP1RESULTLSBS
+const ADC_SNAPSHOT_LEN: usize = 11;
+
+/// Decode the registers of an [`AdcSnapshot`]
+///
+/// # Arguments
+///
+/// * `regs` - The registers from ADCIBATMEASSTATUS to ADCGP1RESULTLSBS
+/// * `ntc_beta` - The beta value of the NTC thermistor, if configured
+fn decode_adc_snapshot(regs: &[u8; ADC_SNAPSHOT_LEN], ntc_beta: Option<u16>) -> AdcSnapshot {
+    let [status, vbat, ntc, temp, vsys, gp0, burst0, burst1, burst2, burst3, gp1] = *regs;
+
+    // The status code is 4 bits wide, see `get_ibat_status`
+    let ibat_status = IbatStatuscodes::try_from(status & 0x0F).ok();
+
+    AdcSnapshot {
+        ibat_status,
+        // 5000 mV is VFSVBAT and VFSVSYS, the full scale voltage for measuring VBAT and VSYS.
+        vbat: convert_vadc_to_millivolts(combine_result(vbat, gp0, 0), 5000),
+        ntc_temperature: ntc_beta
+            .map(|beta| convert_ntc_to_temperature(combine_result(ntc, gp0, 1), beta)),
+        die_temperature: convert_die_temperature(combine_result(temp, gp0, 2)),
+        vsys: convert_vadc_to_millivolts(combine_result(vsys, gp0, 3), 5000),
+        burst: [
+            combine_result(burst0, gp1, 0),
+            combine_result(burst1, gp1, 1),
+            combine_result(burst2, gp1, 2),
+            combine_result(burst3, gp1, 3),
+        ],
+    }
+}
+
+#[maybe_async::maybe_async]
+impl<I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> crate::NPM1300<I2c, Delay> {
     /// Measure VBAT
     ///
     /// This function triggers a VBAT measurement and returns the result.
     ///
     /// # Returns
     ///
-    /// * `Ok(f32)` - The measured VBAT voltage
+    /// * `Ok(Millivolts)` - The measured VBAT voltage
     /// * `Err(NPM1300Error)` - An error occurred while reading the VBAT measurement result
-    pub async fn measure_vbat(
+    pub async fn measure_vbat_millivolts(
         &mut self,
-        // ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
-    ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+    ) -> Result<Millivolts, crate::NPM1300Error<I2c::Error>> {
         // Disable VBAT burst measurement
         self.configure_vbat_burst_measurement(false).await?;
 
@@ -52,7 +167,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         defmt::debug!("Waiting for measurement to complete...");
         self.delay.delay_us(250).await;
 
-        let result = self.get_vbat_measurement_result().await?;
+        let result = self.get_vbat_measurement_result_millivolts().await?;
 
         Ok(result)
     }
@@ -64,12 +179,11 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Returns
     ///
-    /// * `Ok((f32, f32, f32, f32))` - The measured VBAT voltages (VBAT0, VBAT1, VBAT2, VBAT3)
+    /// * `Ok([Millivolts; 4])` - The measured VBAT voltages (VBAT0, VBAT1, VBAT2, VBAT3)
     /// * `Err(NPM1300Error)` - An error occurred while reading the VBAT measurement result
-    pub async fn measure_vbat_burst(
+    pub async fn measure_vbat_burst_millivolts(
         &mut self,
-        // ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
-    ) -> Result<(f32, f32, f32, f32), crate::NPM1300Error<I2c::Error>> {
+    ) -> Result<[Millivolts; 4], crate::NPM1300Error<I2c::Error>> {
         // Enable VBAT burst measurement
         self.configure_vbat_burst_measurement(true).await?;
 
@@ -87,12 +201,12 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         // Conversions are run back-to-back and complete in tCONV.
         self.delay.delay_us(250).await;
 
-        let vbat0 = self.get_vbat_burst_measurement_result(0).await?;
-        let vbat1 = self.get_vbat_burst_measurement_result(1).await?;
-        let vbat2 = self.get_vbat_burst_measurement_result(2).await?;
-        let vbat3 = self.get_vbat_burst_measurement_result(3).await?;
+        let vbat0 = self.get_vbat_burst_measurement_result_millivolts(0).await?;
+        let vbat1 = self.get_vbat_burst_measurement_result_millivolts(1).await?;
+        let vbat2 = self.get_vbat_burst_measurement_result_millivolts(2).await?;
+        let vbat3 = self.get_vbat_burst_measurement_result_millivolts(3).await?;
 
-        Ok((vbat0, vbat1, vbat2, vbat3))
+        Ok([vbat0, vbat1, vbat2, vbat3])
     }
 
     /// Get the latest VBAT measurement result without triggering a new measurement
@@ -104,11 +218,11 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Returns
     ///
-    /// * `Ok(f32)` - The most recent VBAT measurement in volts
+    /// * `Ok(Millivolts)` - The most recent VBAT measurement
     /// * `Err(NPM1300Error)` - An error occurred while reading the ADC registers
-    pub async fn get_vbat_measurement_result(
+    pub async fn get_vbat_measurement_result_millivolts(
         &mut self,
-    ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+    ) -> Result<Millivolts, crate::NPM1300Error<I2c::Error>> {
         let msb = self
             .device
             .adc()
@@ -128,9 +242,9 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         // Convert result to u16
         let result = ((msb as u16) << 2) | (lsb & 0x03) as u16;
 
-        // Convert result to f32
-        // 5.0 is VFSVBAT, the full scale voltage for measuring VBAT.
-        let result = convert_vadc_to_voltage(result, 5.0);
+        // Convert result to millivolts
+        // 5000 mV is VFSVBAT, the full scale voltage for measuring VBAT.
+        let result = convert_vadc_to_millivolts(result, 5000);
 
         Ok(result)
     }
@@ -148,35 +262,39 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Returns
     ///
-    /// * `Ok(f32)` - The VBAT measurement result in volts
+    /// * `Ok(Millivolts)` - The VBAT measurement result
+    /// * `Err(NPM1300Error::InvalidIndex)` - The VBAT index is higher than 3
     /// * `Err(NPM1300Error)` - An error occurred while reading the ADC registers
-    pub async fn get_vbat_burst_measurement_result(
+    pub async fn get_vbat_burst_measurement_result_millivolts(
         &mut self,
         vbat_index: u8,
-    ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
-        let vbat_index = vbat_index as usize;
+    ) -> Result<Millivolts, crate::NPM1300Error<I2c::Error>> {
+        if vbat_index > 3 {
+            return Err(crate::NPM1300Error::InvalidIndex(vbat_index as usize));
+        }
+
         let msb = self
             .device
             .adc()
-            .adcvbatburstresultmsb(vbat_index)
+            .adcvbatburstresultmsb(vbat_index as usize)
             .read_async()
             .await?
             .vbatresultmsb();
 
-        let lsb = self
-            .device
-            .adc()
-            .adcgp_1_resultlsbs()
-            .read_async()
-            .await?
-            .vbat_3_resultlsb();
+        let lsbs = self.device.adc().adcgp_1_resultlsbs().read_async().await?;
+        let lsb = match vbat_index {
+            0 => lsbs.vbat_0_resultlsb(),
+            1 => lsbs.vbat_1_resultlsb(),
+            2 => lsbs.vbat_2_resultlsb(),
+            _ => lsbs.vbat_3_resultlsb(),
+        };
 
         // Convert result to u16
         let result = ((msb as u16) << 2) | (lsb & 0x03) as u16;
 
-        // Convert result to f32
-        // 5.0 is VFSVBAT, the full scale voltage for measuring VBAT.
-        let result = convert_vadc_to_voltage(result, 5.0);
+        // Convert result to millivolts
+        // 5000 mV is VFSVBAT, the full scale voltage for measuring VBAT.
+        let result = convert_vadc_to_millivolts(result, 5000);
 
         Ok(result)
     }
@@ -185,10 +303,12 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Returns
     ///
-    /// * `Ok(f32)` - The measured NTC resistance in degrees Celsius
+    /// * `Ok(CentiCelsius)` - The measured NTC temperature
     /// * `Err(NPM1300Error)` - An error occurred while reading the NTC measurement result
     //TODO: test this function
-    pub async fn measure_ntc(&mut self) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+    pub async fn measure_ntc_centi_celsius(
+        &mut self,
+    ) -> Result<CentiCelsius, crate::NPM1300Error<I2c::Error>> {
         #[cfg(feature = "defmt-03")]
         defmt::debug!("Triggering NTC measurement...");
         self.device
@@ -202,7 +322,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         defmt::debug!("Waiting for measurement to complete...");
         self.delay.delay_us(250).await;
 
-        let result = self.get_ntc_measurement_result().await?;
+        let result = self.get_ntc_measurement_result_centi_celsius().await?;
         Ok(result)
     }
 
@@ -215,11 +335,11 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Returns
     ///
-    /// * `Ok(f32)` - The most recent NTC measurement in degrees Celsius
+    /// * `Ok(CentiCelsius)` - The most recent NTC temperature measurement
     /// * `Err(NPM1300Error)` - An error occurred while reading the ADC registers
-    pub async fn get_ntc_measurement_result(
+    pub async fn get_ntc_measurement_result_centi_celsius(
         &mut self,
-    ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+    ) -> Result<CentiCelsius, crate::NPM1300Error<I2c::Error>> {
         let msb = self
             .device
             .adc()
@@ -236,12 +356,9 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             .ntcresultlsb();
         // Convert result to u16
         let result = ((msb as u16) << 2) | (lsb & 0x03) as u16;
-        // Convert result to f32
-        // The temperature is returned in degrees Celsius
-        //
+        // Convert result to a temperature
         let result = if let Some(ntc_beta) = self.ntc_beta {
-            1.0 / ((1.0 / 298.15) - (1.0 / ntc_beta) * logf((1024.0 / result as f32) - 1.0))
-                - 273.15
+            convert_ntc_to_temperature(result, ntc_beta)
         } else {
             return Err(crate::NPM1300Error::InvalidNtcBeta);
         };
@@ -252,12 +369,11 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Returns
     ///
-    /// * `Ok(f32)` - The measured die temperature in degrees Celsius
+    /// * `Ok(CentiCelsius)` - The measured die temperature
     /// * `Err(NPM1300Error)` - An error occurred while reading the die temperature measurement result
-    pub async fn measure_die_temperature(
+    pub async fn measure_die_temperature_centi_celsius(
         &mut self,
-        // ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
-    ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+    ) -> Result<CentiCelsius, crate::NPM1300Error<I2c::Error>> {
         #[cfg(feature = "defmt-03")]
         defmt::debug!("Triggering die temperature measurement...");
         self.device
@@ -271,7 +387,9 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         defmt::debug!("Waiting for measurement to complete...");
         self.delay.delay_us(250).await;
 
-        let result = self.get_die_temperature_measurement_result().await?;
+        let result = self
+            .get_die_temperature_measurement_result_centi_celsius()
+            .await?;
         Ok(result)
     }
 
@@ -284,11 +402,11 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Returns
     ///
-    /// * `Ok(f32)` - The most recent die temperature measurement in degrees Celsius
+    /// * `Ok(CentiCelsius)` - The most recent die temperature measurement
     /// * `Err(NPM1300Error)` - An error occurred while reading the ADC registers
-    pub async fn get_die_temperature_measurement_result(
+    pub async fn get_die_temperature_measurement_result_centi_celsius(
         &mut self,
-    ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+    ) -> Result<CentiCelsius, crate::NPM1300Error<I2c::Error>> {
         let msb = self
             .device
             .adc()
@@ -308,9 +426,8 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         // Convert result to u16
         let result = ((msb as u16) << 2) | (lsb & 0x03) as u16;
 
-        // Convert result to f32
-        // The temperature is returned in degrees Celsius
-        let result = 394.67 - 0.7926 * result as f32;
+        // Convert result to a temperature
+        let result = convert_die_temperature(result);
 
         Ok(result)
     }
@@ -319,12 +436,11 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Returns
     ///
-    /// * `Ok(f32)` - The measured VSYS voltage
+    /// * `Ok(Millivolts)` - The measured VSYS voltage
     /// * `Err(NPM1300Error)` - An error occurred while reading the VSYS measurement result
-    pub async fn measure_vsys(
+    pub async fn measure_vsys_millivolts(
         &mut self,
-        // ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
-    ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+    ) -> Result<Millivolts, crate::NPM1300Error<I2c::Error>> {
         #[cfg(feature = "defmt-03")]
         defmt::debug!("Triggering VSYS measurement...");
         self.device
@@ -358,9 +474,9 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         // Convert result to u16
         let result = ((msb as u16) << 2) | (lsb & 0x03) as u16;
 
-        // Convert result to f32
-        // 5.0 is VFSVSYS, the full scale voltage for measuring VSYS.
-        let result = convert_vadc_to_voltage(result, 5.0);
+        // Convert result to millivolts
+        // 5000 mV is VFSVSYS, the full scale voltage for measuring VSYS.
+        let result = convert_vadc_to_millivolts(result, 5000);
 
         Ok(result)
     }
@@ -369,15 +485,17 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Returns
     ///
-    /// * `Ok(f32)` - The measured VBUS voltage
+    /// * `Ok(Millivolts)` - The measured VBUS voltage
     /// * `Err(NPM1300Error)` - An error occurred while reading the VBUS measurement result
-    pub async fn measure_vbus(&mut self) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+    pub async fn measure_vbus_millivolts(
+        &mut self,
+    ) -> Result<Millivolts, crate::NPM1300Error<I2c::Error>> {
         #[cfg(feature = "defmt-03")]
         defmt::debug!("Triggering VBUS measurement...");
         self.device
             .adc()
             .taskvbus_7_measure()
-            .dispatch_async(|command| command.set_taskvsysmeasure(Task::Trigger))
+            .dispatch_async(|command| command.set_taskvbus_7_measure(Task::Trigger))
             .await?;
 
         // Wait for measurement to complete
@@ -405,9 +523,9 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         // Convert result to u16
         let result = ((msb as u16) << 2) | (lsb & 0x03) as u16;
 
-        // Convert result to f32
-        // 5.0 is VFSVBUS, the full scale voltage for measuring VBUS.
-        let result = convert_vadc_to_voltage(result, 7.5);
+        // Convert result to millivolts
+        // 7500 mV is VFSVBUS, the full scale voltage for measuring VBUS.
+        let result = convert_vadc_to_millivolts(result, 7500);
 
         Ok(result)
     }
@@ -424,14 +542,14 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Returns
     ///
-    /// * `Ok(f32)` - The measured delayed VBAT voltage
+    /// * `Ok(Millivolts)` - The measured delayed VBAT voltage
     /// * `Err(NPM1300Error)` - An error occurred while reading the delayed VBAT measurement result
-    pub async fn measure_delayed_vbat(
+    pub async fn measure_delayed_vbat_millivolts(
         &mut self,
         delay: u16,
-    ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+    ) -> Result<Millivolts, crate::NPM1300Error<I2c::Error>> {
         // Test if delay is valid
-        if !(4..=514).contains(&delay) || delay % 2 != 0 {
+        if !(4..=514).contains(&delay) || !delay.is_multiple_of(2) {
             return Err(crate::NPM1300Error::InvalidVbatMeasurementDelayValue);
         }
         // Calculate register delay
@@ -469,7 +587,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         defmt::debug!("Waiting for delayed measurement to complete...");
         self.delay.delay_us(250).await;
 
-        let result = self.get_vbat_measurement_result().await?;
+        let result = self.get_vbat_measurement_result_millivolts().await?;
 
         Ok(result)
     }
@@ -572,15 +690,17 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             .vbatburstenable())
     }
 
-    /// Configure the NTC thermistor resistance value
+    /// Configure the NTC thermistor resistance value, with an integer beta value
     ///
     /// # Arguments
     ///
     /// * `ntc_value` - The NTC thermistor resistance value to configure
-    pub async fn configure_ntc_resistance(
+    /// * `ntc_beta` - The beta value of the NTC thermistor in kelvin, required unless
+    ///   `ntc_resistance` is [`NtcThermistorType::None`]
+    pub async fn configure_ntc_resistance_kelvin(
         &mut self,
         ntc_resistance: NtcThermistorType,
-        ntc_beta: Option<f32>,
+        ntc_beta: Option<u16>,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
         // Write NTC value to register
         self.device
@@ -723,6 +843,25 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             .await
     }
 
+    /// Get the IBAT measurement configuration
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - True if IBAT measurement is enabled
+    /// * `Err(NPM1300Error)` - An error occurred while reading the IBAT measurement configuration
+    pub async fn get_ibat_measurement_configuration(
+        &mut self,
+    ) -> Result<bool, crate::NPM1300Error<I2c::Error>> {
+        Ok(self
+            .device
+            .adc()
+            .adcibatmeasen()
+            .read_async()
+            .await?
+            .ibatmeasenable()
+            == Ibatmeasenable::Ibaton)
+    }
+
     ///Get ibat status, this register is 4 bit wide,
     /// but i dont want to do breaking changes in the the
     /// device.yaml in case someone is using this functionality,
@@ -740,28 +879,14 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     pub async fn get_ibat_status(
         &mut self,
     ) -> Result<IbatStatuscodes, crate::NPM1300Error<I2c::Error>> {
-        let msb = self
-            .device
-            .adc()
-            .adcibatmeasstatus()
-            .read_async()
-            .await?
-            .bchargermode();
-        let lsb = self
-            .device
-            .adc()
-            .adcibatmeasstatus()
-            .read_async()
-            .await?
-            .bchargericharge();
-
-        // 3. Combine them into a 4-bit value:
-        let raw_status = ((msb as u8) << 2) | (lsb as u8);
+        let status = self.device.adc().adcibatmeasstatus().read_async().await?;
 
-        // 4. Convert the 4-bit code into our enum
-        let status_enum = IbatStatuscodes::from(raw_status);
+        // Combine them into a 4-bit value:
+        let raw_status = (status.bchargermode() << 2) | status.bchargericharge();
 
-        return Ok(status_enum);
+        // Convert the 4-bit code into our enum
+        IbatStatuscodes::try_from(raw_status)
+            .map_err(|_| crate::NPM1300Error::UnexpectedRegisterValue)
     }
 
     /// Measure IBAT current.
@@ -796,7 +921,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
 
         // Convert result to u16
         let result = ((msb as u16) << 2) | (lsb & 0x03) as u16;
-        return Ok(result);
+        Ok(result)
     }
     /// Measure IBAT current and convert it to a scaled value
     /// This is more at home in the ADC function, but i couldnt
@@ -806,25 +931,200 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Returns
     ///
-    /// a scaled signed integer in ma of the current charge or discharge current
-    pub async fn measure_ibat(&mut self) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
-        let ibat_raw = self.measure_ibat_raw().await? as f32;
+    /// the current charge (positive) or discharge (negative) current
+    pub async fn measure_ibat_milliamps(
+        &mut self,
+    ) -> Result<Milliamps, crate::NPM1300Error<I2c::Error>> {
+        let (ibat_raw, full_scale) = self.measure_ibat_scaled().await?;
+        Ok(Milliamps(((ibat_raw * full_scale) / 1024) as i16))
+    }
+
+    /// Read the raw IBAT result together with the full scale current it is relative to
+    ///
+    /// # Returns
+    ///
+    /// The raw 10 bit result and the signed full scale current in mA, negative when
+    /// discharging and zero when the charger is not in a charge or discharge state
+    async fn measure_ibat_scaled(&mut self) -> Result<(i32, i32), crate::NPM1300Error<I2c::Error>> {
+        let ibat_raw = self.measure_ibat_raw().await? as i32;
         let ibat_status = self.get_ibat_status().await?;
-        let charger_max_current = self.get_charger_config_current().await? as f32;
+        let charger_max_current = self.get_charger_config_current().await? as i32;
         let discharge_current_limit = self.get_discharge_current_limit().await?;
-        let ibat_scaled = match ibat_status {
-            IbatStatuscodes::IbatStatDischarge => {
-                let max_discharge_current = match discharge_current_limit {
-                    DischargeCurrentLimit::Low => 200,
-                    DischargeCurrentLimit::High => 1000,
-                } as f32;
-                (ibat_raw * -max_discharge_current) / 1024.0
-            }
+        let full_scale = match ibat_status {
+            IbatStatuscodes::IbatStatDischarge => match discharge_current_limit {
+                DischargeCurrentLimit::Low => -200,
+                DischargeCurrentLimit::High => -1000,
+            },
             IbatStatuscodes::IbatStatChargeTrickle
             | IbatStatuscodes::IbatStatChargeCool
-            | IbatStatuscodes::IbatStatChargeNormal => (ibat_raw * charger_max_current) / 1024.0,
-            _ => 0.0,
+            | IbatStatuscodes::IbatStatChargeNormal => charger_max_current,
+            _ => 0,
         };
-        return Ok(ibat_scaled);
+        Ok((ibat_raw, full_scale))
+    }
+
+    /// Read all ADC results at once
+    ///
+    /// Reads the result registers from ADCIBATMEASSTATUS to ADCGP1RESULTLSBS in a single
+    /// auto-incremented I2C transaction. This is faster than the individual getters, and the
+    /// MSBs and LSBs of a result can not be torn by an automatic measurement updating the
+    /// registers in between.
+    /// No measurement is triggered, delays must be handled by the caller.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(AdcSnapshot)` - The decoded ADC results, see [`AdcSnapshot`]
+    /// * `Err(NPM1300Error)` - An error occurred while reading the ADC registers
+    pub async fn get_adc_snapshot(
+        &mut self,
+    ) -> Result<AdcSnapshot, crate::NPM1300Error<I2c::Error>> {
+        let mut regs = [0; ADC_SNAPSHOT_LEN];
+        self.device
+            .interface
+            .read_register(ADC_SNAPSHOT_ADDRESS, 8 * ADC_SNAPSHOT_LEN as u32, &mut regs)
+            .await?;
+        Ok(decode_adc_snapshot(&regs, self.ntc_beta))
+    }
+}
+
+/// Floating point variants of the ADC measurements
+///
+/// These are layered on top of the integer measurements and are only available with the `float`
+/// feature, to avoid pulling soft-float code into targets without an FPU.
+#[cfg(feature = "float")]
+#[maybe_async::maybe_async]
+impl<I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> crate::NPM1300<I2c, Delay> {
+    /// Measure VBAT in volts, see [`Self::measure_vbat_millivolts`]
+    pub async fn measure_vbat(&mut self) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+        Ok(self.measure_vbat_millivolts().await?.as_volts())
+    }
+
+    /// Measure VBAT burst (VBAT0, VBAT1, VBAT2, VBAT3) in volts, see
+    /// [`Self::measure_vbat_burst_millivolts`]
+    pub async fn measure_vbat_burst(
+        &mut self,
+    ) -> Result<(f32, f32, f32, f32), crate::NPM1300Error<I2c::Error>> {
+        let [vbat0, vbat1, vbat2, vbat3] = self.measure_vbat_burst_millivolts().await?;
+        Ok((
+            vbat0.as_volts(),
+            vbat1.as_volts(),
+            vbat2.as_volts(),
+            vbat3.as_volts(),
+        ))
+    }
+
+    /// Get the latest VBAT measurement result in volts, see
+    /// [`Self::get_vbat_measurement_result_millivolts`]
+    pub async fn get_vbat_measurement_result(
+        &mut self,
+    ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+        Ok(self
+            .get_vbat_measurement_result_millivolts()
+            .await?
+            .as_volts())
+    }
+
+    /// Get the VBAT burst measurement result for a specific VBAT index in volts, see
+    /// [`Self::get_vbat_burst_measurement_result_millivolts`]
+    pub async fn get_vbat_burst_measurement_result(
+        &mut self,
+        vbat_index: u8,
+    ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+        Ok(self
+            .get_vbat_burst_measurement_result_millivolts(vbat_index)
+            .await?
+            .as_volts())
+    }
+
+    /// Configure the NTC thermistor resistance value, see
+    /// [`Self::configure_ntc_resistance_kelvin`]
+    ///
+    /// The beta value is rounded to the nearest kelvin.
+    ///
+    /// # Arguments
+    ///
+    /// * `ntc_value` - The NTC thermistor resistance value to configure
+    /// * `ntc_beta` - The beta value of the NTC thermistor in kelvin, required unless
+    ///   `ntc_resistance` is [`NtcThermistorType::None`]
+    pub async fn configure_ntc_resistance(
+        &mut self,
+        ntc_resistance: NtcThermistorType,
+        ntc_beta: Option<f32>,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        let ntc_beta = match ntc_beta.map(libm::roundf) {
+            Some(beta) if (1.0..=f32::from(u16::MAX)).contains(&beta) => Some(beta as u16),
+            Some(_) => return Err(crate::NPM1300Error::InvalidNtcBeta),
+            None => None,
+        };
+        self.configure_ntc_resistance_kelvin(ntc_resistance, ntc_beta)
+            .await
+    }
+
+    /// Measure NTC in degrees Celsius, see [`Self::measure_ntc_centi_celsius`]
+    pub async fn measure_ntc(&mut self) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+        Ok(self.measure_ntc_centi_celsius().await?.as_celsius())
+    }
+
+    /// Get the latest NTC measurement result in degrees Celsius, see
+    /// [`Self::get_ntc_measurement_result_centi_celsius`]
+    pub async fn get_ntc_measurement_result(
+        &mut self,
+    ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+        Ok(self
+            .get_ntc_measurement_result_centi_celsius()
+            .await?
+            .as_celsius())
+    }
+
+    /// Measure die temperature in degrees Celsius, see
+    /// [`Self::measure_die_temperature_centi_celsius`]
+    pub async fn measure_die_temperature(
+        &mut self,
+    ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+        Ok(self
+            .measure_die_temperature_centi_celsius()
+            .await?
+            .as_celsius())
+    }
+
+    /// Get the latest die temperature measurement result in degrees Celsius, see
+    /// [`Self::get_die_temperature_measurement_result_centi_celsius`]
+    pub async fn get_die_temperature_measurement_result(
+        &mut self,
+    ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+        Ok(self
+            .get_die_temperature_measurement_result_centi_celsius()
+            .await?
+            .as_celsius())
+    }
+
+    /// Measure VSYS in volts, see [`Self::measure_vsys_millivolts`]
+    pub async fn measure_vsys(&mut self) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+        Ok(self.measure_vsys_millivolts().await?.as_volts())
+    }
+
+    /// Measure VBUS in volts, see [`Self::measure_vbus_millivolts`]
+    pub async fn measure_vbus(&mut self) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+        Ok(self.measure_vbus_millivolts().await?.as_volts())
+    }
+
+    /// Measure delayed VBAT in volts, see [`Self::measure_delayed_vbat_millivolts`]
+    pub async fn measure_delayed_vbat(
+        &mut self,
+        delay: u16,
+    ) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+        Ok(self
+            .measure_delayed_vbat_millivolts(delay)
+            .await?
+            .as_volts())
+    }
+
+    /// Measure IBAT current in mA, see [`Self::measure_ibat_milliamps`]
+    ///
+    /// The current is computed from the raw result, so it keeps the fraction of a mA that the
+    /// integer measurement truncates.
+    pub async fn measure_ibat(&mut self) -> Result<f32, crate::NPM1300Error<I2c::Error>> {
+        let (ibat_raw, full_scale) = self.measure_ibat_scaled().await?;
+        Ok(ibat_raw as f32 * full_scale as f32 / 1024.0)
     }
 }
@@ -1,3 +1,5 @@
+use crate::units::{CentiCelsius, Millivolts};
+
 /// Battery charger ibat status codes
 #[derive(Copy, Clone, Debug)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
@@ -8,16 +10,18 @@ pub enum IbatStatuscodes {
     IbatStatChargeCool = 13,    // 0x0D
     IbatStatChargeNormal = 15,  // 0x0F
 }
-// Add conversion from u8
-impl From<u8> for IbatStatuscodes {
-    fn from(value: u8) -> Self {
+// Add conversion from u8, rejecting unknown status codes
+impl TryFrom<u8> for IbatStatuscodes {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            4 => Self::IbatStatDischarge,
-            8 => Self::IbatStatChargeError,
-            12 => Self::IbatStatChargeTrickle,
-            13 => Self::IbatStatChargeCool,
-            15 => Self::IbatStatChargeNormal,
-            _ => panic!("Invalid value"),
+            4 => Ok(Self::IbatStatDischarge),
+            8 => Ok(Self::IbatStatChargeError),
+            12 => Ok(Self::IbatStatChargeTrickle),
+            13 => Ok(Self::IbatStatChargeCool),
+            15 => Ok(Self::IbatStatChargeNormal),
+            _ => Err(()),
         }
     }
 }
@@ -28,3 +32,54 @@ impl From<IbatStatuscodes> for u8 {
         value as u8
     }
 }
+
+/// Snapshot of all ADC results, read in a single I2C transaction
+///
+/// See [`crate::NPM1300::get_adc_snapshot`].
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct AdcSnapshot {
+    /// Battery charger status during the last IBAT measurement, `None` if no IBAT measurement
+    /// was taken
+    pub ibat_status: Option<IbatStatuscodes>,
+    /// VBAT
+    pub vbat: Millivolts,
+    /// NTC temperature, `None` if no NTC beta is configured
+    pub ntc_temperature: Option<CentiCelsius>,
+    /// Die temperature
+    pub die_temperature: CentiCelsius,
+    /// VSYS
+    pub vsys: Millivolts,
+    /// Raw 10-bit results of the burst slots
+    ///
+    /// The slots hold the 4 results of a VBAT burst measurement, or the IBAT measurement in
+    /// slot 2 and the VBUS measurement in slot 3.
+    pub burst: [u16; 4],
+}
+
+impl AdcSnapshot {
+    /// VBAT burst measurement result
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the burst result (0-3)
+    ///
+    /// # Returns
+    ///
+    /// The VBAT burst result, or `None` if the index is out of range
+    pub fn vbat_burst(&self, index: usize) -> Option<Millivolts> {
+        self.burst
+            .get(index)
+            .map(|&raw| super::convert_vadc_to_millivolts(raw, 5000))
+    }
+
+    /// VBUS measurement result
+    pub fn vbus(&self) -> Millivolts {
+        super::convert_vadc_to_millivolts(self.burst[3], 7500)
+    }
+
+    /// Raw IBAT measurement result, see [`crate::NPM1300::measure_ibat_raw`]
+    pub fn ibat_raw(&self) -> u16 {
+        self.burst[2]
+    }
+}
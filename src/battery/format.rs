@@ -0,0 +1,261 @@
+use heapless::Vec;
+use libm::roundf;
+
+use super::{BatteryModel, BatteryModelError, ChargeLimits, OcvPoint, OcvTable, ResistancePoint};
+
+/// Magic bytes at the start of a binary battery model
+const MAGIC: [u8; 4] = *b"NPMB";
+
+/// Current version of the binary format
+const VERSION: u8 = 1;
+
+/// Size of the binary header in bytes
+const HEADER_LEN: usize = 15;
+
+/// Reads little-endian values from a byte slice
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], BatteryModelError> {
+        let (head, tail) = self
+            .bytes
+            .split_first_chunk::<N>()
+            .ok_or(BatteryModelError::Truncated)?;
+        self.bytes = tail;
+        Ok(*head)
+    }
+
+    fn u8(&mut self) -> Result<u8, BatteryModelError> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn i8(&mut self) -> Result<i8, BatteryModelError> {
+        Ok(i8::from_le_bytes(self.take()?))
+    }
+
+    fn u16(&mut self) -> Result<u16, BatteryModelError> {
+        Ok(u16::from_le_bytes(self.take()?))
+    }
+}
+
+/// Writes little-endian values to a byte slice
+struct Writer<'a> {
+    bytes: &'a mut [u8],
+    len: usize,
+}
+
+impl Writer<'_> {
+    fn put(&mut self, value: &[u8]) -> Result<(), BatteryModelError> {
+        let end = self.len + value.len();
+        self.bytes
+            .get_mut(self.len..end)
+            .ok_or(BatteryModelError::BufferTooSmall)?
+            .copy_from_slice(value);
+        self.len = end;
+        Ok(())
+    }
+
+    fn u8(&mut self, value: u8) -> Result<(), BatteryModelError> {
+        self.put(&[value])
+    }
+
+    fn i8(&mut self, value: f32) -> Result<(), BatteryModelError> {
+        self.put(&(roundf(value) as i8).to_le_bytes())
+    }
+
+    fn u16(&mut self, value: f32) -> Result<(), BatteryModelError> {
+        self.put(&(roundf(value) as u16).to_le_bytes())
+    }
+}
+
+impl BatteryModel {
+    /// Size of the binary representation of the model in bytes
+    pub fn encoded_len(&self) -> usize {
+        HEADER_LEN
+            + self
+                .ocv
+                .iter()
+                .map(|table| 2 + 4 * table.points.len())
+                .sum::<usize>()
+            + 3 * self.resistance.len()
+    }
+
+    /// Load a model from its binary representation
+    ///
+    /// The model is validated with [`Self::validate`] after loading.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The binary model, as written by [`Self::to_bytes`]
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BatteryModel)` - The loaded model
+    /// * `Err(BatteryModelError)` - The data is malformed or the model is invalid
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BatteryModelError> {
+        let mut reader = Reader { bytes };
+
+        if reader.take::<4>()? != MAGIC {
+            return Err(BatteryModelError::InvalidHeader);
+        }
+        let version = reader.u8()?;
+        if version != VERSION {
+            return Err(BatteryModelError::UnsupportedVersion(version));
+        }
+
+        let capacity = f32::from(reader.u16()?);
+        let charge_limits = ChargeLimits {
+            charge_current: reader.u16()?,
+            termination_voltage: reader.u16()?,
+            warm_termination_voltage: match reader.u16()? {
+                0 => None,
+                millivolts => Some(millivolts),
+            },
+        };
+        let table_count = reader.u8()?;
+        let resistance_count = reader.u8()?;
+
+        let mut ocv = Vec::new();
+        for _ in 0..table_count {
+            let temperature = f32::from(reader.i8()?);
+            let point_count = reader.u8()?;
+            let mut points = Vec::new();
+            for _ in 0..point_count {
+                let point = OcvPoint {
+                    soc: f32::from(reader.u16()?) / 10000.0,
+                    voltage: f32::from(reader.u16()?) / 1000.0,
+                };
+                points
+                    .push(point)
+                    .map_err(|_| BatteryModelError::TooManyPoints)?;
+            }
+            ocv.push(OcvTable {
+                temperature,
+                points,
+            })
+            .map_err(|_| BatteryModelError::TooManyPoints)?;
+        }
+
+        let mut resistance = Vec::new();
+        for _ in 0..resistance_count {
+            let point = ResistancePoint {
+                temperature: f32::from(reader.i8()?),
+                resistance: f32::from(reader.u16()?) / 1000.0,
+            };
+            resistance
+                .push(point)
+                .map_err(|_| BatteryModelError::TooManyPoints)?;
+        }
+
+        if !reader.bytes.is_empty() {
+            return Err(BatteryModelError::TrailingBytes);
+        }
+
+        let model = Self {
+            capacity,
+            ocv,
+            resistance,
+            charge_limits,
+        };
+        model.validate()?;
+        Ok(model)
+    }
+
+    /// Write the binary representation of the model
+    ///
+    /// The model is validated with [`Self::validate`] before writing.
+    ///
+    /// The binary format is little-endian and quantized to keep models small enough to store
+    /// in flash or send over a radio link. Values are rounded to the resolution of the format.
+    ///
+    /// | Field                        | Type | Unit  |
+    /// | ---------------------------- | ---- | ----- |
+    /// | Magic `NPMB`                 | 4 B  |       |
+    /// | Format version               | u8   |       |
+    /// | Capacity                     | u16  | mAh   |
+    /// | Charge current               | u16  | mA    |
+    /// | Termination voltage          | u16  | mV    |
+    /// | Warm termination voltage     | u16  | mV, 0 if not set |
+    /// | Number of OCV tables         | u8   |       |
+    /// | Number of resistance points  | u8   |       |
+    ///
+    /// Each OCV table follows as its temperature (i8, °C) and point count (u8), then its points
+    /// as state of charge (u16, 0.01 %) and voltage (u16, mV). The resistance points follow as
+    /// temperature (i8, °C) and resistance (u16, mΩ).
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - The buffer to write to, at least [`Self::encoded_len`] bytes long
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The number of bytes written
+    /// * `Err(BatteryModelError)` - The model is invalid or the buffer is too small
+    pub fn to_bytes(&self, buffer: &mut [u8]) -> Result<usize, BatteryModelError> {
+        self.validate()?;
+        let mut writer = Writer {
+            bytes: buffer,
+            len: 0,
+        };
+
+        writer.put(&MAGIC)?;
+        writer.u8(VERSION)?;
+        writer.u16(self.capacity)?;
+        writer.put(&self.charge_limits.charge_current.to_le_bytes())?;
+        writer.put(&self.charge_limits.termination_voltage.to_le_bytes())?;
+        writer.put(
+            &self
+                .charge_limits
+                .warm_termination_voltage
+                .unwrap_or(0)
+                .to_le_bytes(),
+        )?;
+        writer.u8(self.ocv.len() as u8)?;
+        writer.u8(self.resistance.len() as u8)?;
+
+        for table in &self.ocv {
+            writer.i8(table.temperature)?;
+            writer.u8(table.points.len() as u8)?;
+            for point in &table.points {
+                writer.u16(point.soc * 10000.0)?;
+                writer.u16(point.voltage * 1000.0)?;
+            }
+        }
+        for point in &self.resistance {
+            writer.i8(point.temperature)?;
+            writer.u16(point.resistance * 1000.0)?;
+        }
+
+        Ok(writer.len)
+    }
+
+    /// Load a model from JSON
+    ///
+    /// The JSON object mirrors the fields of [`BatteryModel`]. The model is validated with
+    /// [`Self::validate`] after loading.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BatteryModel)` - The loaded model
+    /// * `Err(BatteryModelError)` - The JSON is malformed or the model is invalid
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Self, BatteryModelError> {
+        let (model, _): (Self, _) =
+            serde_json_core::from_str(json).map_err(|_| BatteryModelError::InvalidJson)?;
+        model.validate()?;
+        Ok(model)
+    }
+
+    /// Write the model as JSON
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The number of bytes written
+    /// * `Err(BatteryModelError)` - The buffer is too small
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self, buffer: &mut [u8]) -> Result<usize, BatteryModelError> {
+        serde_json_core::to_slice(self, buffer).map_err(|_| BatteryModelError::BufferTooSmall)
+    }
+}
@@ -0,0 +1,200 @@
+use crate::charger::{ChargeCurrent, ChargerTerminationVoltage};
+
+mod format;
+mod models;
+mod types;
+
+// Re-export everything in types.rs
+pub use types::*;
+
+/// Linearly interpolate a curve, clamping at both ends
+///
+/// # Arguments
+///
+/// * `points` - The curve points as `(x, y)`, sorted by increasing `x`
+/// * `x` - The point to evaluate
+fn interpolate(points: impl Iterator<Item = (f32, f32)>, x: f32) -> f32 {
+    let mut previous: Option<(f32, f32)> = None;
+    for (px, py) in points {
+        match previous {
+            None if x <= px => return py,
+            Some((ax, ay)) if x <= px => return ay + (py - ay) * (x - ax) / (px - ax),
+            _ => previous = Some((px, py)),
+        }
+    }
+    previous.map_or(0.0, |(_, y)| y)
+}
+
+impl OcvTable {
+    /// Open-circuit voltage at a given state of charge
+    ///
+    /// # Arguments
+    ///
+    /// * `soc` - State of charge, from 0.0 to 1.0
+    pub fn ocv_at(&self, soc: f32) -> f32 {
+        interpolate(self.points.iter().map(|p| (p.soc, p.voltage)), soc)
+    }
+
+    /// State of charge at a given open-circuit voltage
+    ///
+    /// # Arguments
+    ///
+    /// * `voltage` - Open-circuit voltage in volts
+    pub fn soc_at(&self, voltage: f32) -> f32 {
+        interpolate(self.points.iter().map(|p| (p.voltage, p.soc)), voltage)
+    }
+
+    /// Check that the table has at least two points, with strictly increasing state of
+    /// charge between 0.0 and 1.0 and strictly increasing voltage
+    fn validate(&self) -> Result<(), BatteryModelError> {
+        if self.points.len() < 2 {
+            return Err(BatteryModelError::InvalidOcvTable);
+        }
+        let in_range = self
+            .points
+            .iter()
+            .all(|p| (0.0..=1.0).contains(&p.soc) && p.voltage > 0.0);
+        let increasing = self
+            .points
+            .windows(2)
+            .all(|w| w[0].soc < w[1].soc && w[0].voltage < w[1].voltage);
+        if !in_range || !increasing {
+            return Err(BatteryModelError::InvalidOcvTable);
+        }
+        Ok(())
+    }
+}
+
+impl BatteryModel {
+    /// The OCV tables around a temperature and the weight of the upper one
+    fn ocv_tables_at(&self, temperature: f32) -> (&OcvTable, &OcvTable, f32) {
+        let upper = self
+            .ocv
+            .iter()
+            .position(|table| table.temperature >= temperature)
+            .unwrap_or(self.ocv.len() - 1);
+        let lower = upper.saturating_sub(1);
+        let (a, b) = (&self.ocv[lower], &self.ocv[upper]);
+
+        let weight = if b.temperature > a.temperature {
+            ((temperature - a.temperature) / (b.temperature - a.temperature)).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        (a, b, weight)
+    }
+
+    /// Open-circuit voltage at a given state of charge and temperature
+    ///
+    /// # Arguments
+    ///
+    /// * `soc` - State of charge, from 0.0 to 1.0
+    /// * `temperature` - Battery temperature in degrees Celsius
+    pub fn ocv_at(&self, soc: f32, temperature: f32) -> f32 {
+        if self.ocv.is_empty() {
+            return 0.0;
+        }
+        let (a, b, weight) = self.ocv_tables_at(temperature);
+        a.ocv_at(soc) + (b.ocv_at(soc) - a.ocv_at(soc)) * weight
+    }
+
+    /// State of charge at a given open-circuit voltage and temperature
+    ///
+    /// # Arguments
+    ///
+    /// * `voltage` - Open-circuit voltage in volts
+    /// * `temperature` - Battery temperature in degrees Celsius
+    pub fn soc_at(&self, voltage: f32, temperature: f32) -> f32 {
+        if self.ocv.is_empty() {
+            return 0.0;
+        }
+        let (a, b, weight) = self.ocv_tables_at(temperature);
+        a.soc_at(voltage) + (b.soc_at(voltage) - a.soc_at(voltage)) * weight
+    }
+
+    /// Internal resistance at a given temperature
+    ///
+    /// # Arguments
+    ///
+    /// * `temperature` - Battery temperature in degrees Celsius
+    pub fn resistance_at(&self, temperature: f32) -> f32 {
+        interpolate(
+            self.resistance
+                .iter()
+                .map(|p| (p.temperature, p.resistance)),
+            temperature,
+        )
+    }
+
+    /// Charging current
+    ///
+    /// Returns `None` if the charger does not support the current of the model.
+    pub fn charge_current(&self) -> Option<ChargeCurrent> {
+        ChargeCurrent::from_milliamps(self.charge_limits.charge_current)
+    }
+
+    /// Termination voltage in normal temperature conditions
+    ///
+    /// Returns `None` if the charger does not support the voltage of the model.
+    pub fn termination_voltage(&self) -> Option<ChargerTerminationVoltage> {
+        ChargerTerminationVoltage::from_millivolts(self.charge_limits.termination_voltage)
+    }
+
+    /// Termination voltage in warm temperature conditions
+    ///
+    /// Returns `None` if the model has no warm termination voltage or the charger does not
+    /// support it.
+    pub fn warm_termination_voltage(&self) -> Option<ChargerTerminationVoltage> {
+        self.charge_limits
+            .warm_termination_voltage
+            .and_then(ChargerTerminationVoltage::from_millivolts)
+    }
+
+    /// Check that the model is consistent and supported by the charger
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The model is valid
+    /// * `Err(BatteryModelError)` - The first problem found in the model
+    pub fn validate(&self) -> Result<(), BatteryModelError> {
+        // The binary format stores the capacity as a u16 in mAh
+        if self.capacity.is_nan() || self.capacity <= 0.0 || self.capacity > f32::from(u16::MAX) {
+            return Err(BatteryModelError::InvalidCapacity);
+        }
+
+        if self.ocv.is_empty() {
+            return Err(BatteryModelError::MissingOcvTable);
+        }
+        for table in &self.ocv {
+            table.validate()?;
+        }
+        if !self
+            .ocv
+            .windows(2)
+            .all(|w| w[0].temperature < w[1].temperature)
+        {
+            return Err(BatteryModelError::UnsortedOcvTables);
+        }
+
+        let sorted = self
+            .resistance
+            .windows(2)
+            .all(|w| w[0].temperature < w[1].temperature);
+        let positive = self.resistance.iter().all(|p| p.resistance >= 0.0);
+        if !sorted || !positive {
+            return Err(BatteryModelError::InvalidResistance);
+        }
+
+        if self.charge_current().is_none() {
+            return Err(BatteryModelError::InvalidChargeCurrent);
+        }
+        if self.termination_voltage().is_none()
+            || (self.charge_limits.warm_termination_voltage.is_some()
+                && self.warm_termination_voltage().is_none())
+        {
+            return Err(BatteryModelError::InvalidTerminationVoltage);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+use super::{BatteryModel, ChargeLimits, OcvPoint, OcvTable, ResistancePoint};
+
+/// Typical LiPo open-circuit voltage curve at 25 °C, as `(soc, voltage)`
+const LIPO_OCV_25C: [(f32, f32); 12] = [
+    (0.00, 3.000),
+    (0.05, 3.450),
+    (0.10, 3.600),
+    (0.20, 3.690),
+    (0.30, 3.740),
+    (0.40, 3.780),
+    (0.50, 3.820),
+    (0.60, 3.870),
+    (0.70, 3.930),
+    (0.80, 4.000),
+    (0.90, 4.080),
+    (1.00, 4.180),
+];
+
+/// Typical LiPo open-circuit voltage curve at 0 °C, as `(soc, voltage)`
+const LIPO_OCV_0C: [(f32, f32); 12] = [
+    (0.00, 3.000),
+    (0.05, 3.400),
+    (0.10, 3.560),
+    (0.20, 3.660),
+    (0.30, 3.720),
+    (0.40, 3.760),
+    (0.50, 3.800),
+    (0.60, 3.850),
+    (0.70, 3.910),
+    (0.80, 3.980),
+    (0.90, 4.060),
+    (1.00, 4.170),
+];
+
+/// Typical cylindrical Li-ion open-circuit voltage curve at 25 °C, as `(soc, voltage)`
+const LI_ION_OCV_25C: [(f32, f32); 12] = [
+    (0.00, 2.750),
+    (0.05, 3.300),
+    (0.10, 3.450),
+    (0.20, 3.560),
+    (0.30, 3.630),
+    (0.40, 3.690),
+    (0.50, 3.750),
+    (0.60, 3.820),
+    (0.70, 3.900),
+    (0.80, 3.980),
+    (0.90, 4.070),
+    (1.00, 4.180),
+];
+
+/// Typical internal resistance of a 1000 mAh LiPo cell, as `(temperature, resistance)`
+///
+/// Resistance scales inversely with capacity.
+const LIPO_RESISTANCE_1000MAH: [(f32, f32); 5] = [
+    (-10.0, 0.450),
+    (0.0, 0.300),
+    (25.0, 0.150),
+    (45.0, 0.110),
+    (60.0, 0.100),
+];
+
+/// Build a model from constant tables
+fn model(
+    capacity: f32,
+    ocv: &[(f32, &[(f32, f32)])],
+    resistance_1000mah: &[(f32, f32)],
+    charge_limits: ChargeLimits,
+) -> BatteryModel {
+    let ocv = ocv
+        .iter()
+        .map(|(temperature, points)| OcvTable {
+            temperature: *temperature,
+            points: points
+                .iter()
+                .map(|&(soc, voltage)| OcvPoint { soc, voltage })
+                .collect(),
+        })
+        .collect();
+    let resistance = resistance_1000mah
+        .iter()
+        .map(|&(temperature, resistance)| ResistancePoint {
+            temperature,
+            resistance: resistance * 1000.0 / capacity,
+        })
+        .collect();
+
+    BatteryModel {
+        capacity,
+        ocv,
+        resistance,
+        charge_limits,
+    }
+}
+
+impl BatteryModel {
+    /// LP803448 LiPo cell, 1500 mAh
+    ///
+    /// Charged at the maximum charger current of 800 mA up to 4.20 V.
+    pub fn lp803448() -> Self {
+        model(
+            1500.0,
+            &[(0.0, &LIPO_OCV_0C), (25.0, &LIPO_OCV_25C)],
+            &LIPO_RESISTANCE_1000MAH,
+            ChargeLimits {
+                charge_current: 800,
+                termination_voltage: 4200,
+                warm_termination_voltage: Some(4000),
+            },
+        )
+    }
+
+    /// LP502540 LiPo cell, 600 mAh
+    ///
+    /// Charged at 0.5C (300 mA) up to 4.20 V.
+    pub fn lp502540() -> Self {
+        model(
+            600.0,
+            &[(0.0, &LIPO_OCV_0C), (25.0, &LIPO_OCV_25C)],
+            &LIPO_RESISTANCE_1000MAH,
+            ChargeLimits {
+                charge_current: 300,
+                termination_voltage: 4200,
+                warm_termination_voltage: Some(4000),
+            },
+        )
+    }
+
+    /// LP302535 LiPo cell, 250 mAh
+    ///
+    /// Charged at 0.5C (124 mA) up to 4.20 V.
+    pub fn lp302535() -> Self {
+        model(
+            250.0,
+            &[(0.0, &LIPO_OCV_0C), (25.0, &LIPO_OCV_25C)],
+            &LIPO_RESISTANCE_1000MAH,
+            ChargeLimits {
+                charge_current: 124,
+                termination_voltage: 4200,
+                warm_termination_voltage: Some(4000),
+            },
+        )
+    }
+
+    /// Generic 18650 Li-ion cell, 2600 mAh
+    ///
+    /// Charged at the maximum charger current of 800 mA up to 4.20 V.
+    pub fn li_ion_18650() -> Self {
+        model(
+            2600.0,
+            &[(25.0, &LI_ION_OCV_25C)],
+            &LIPO_RESISTANCE_1000MAH,
+            ChargeLimits {
+                charge_current: 800,
+                termination_voltage: 4200,
+                warm_termination_voltage: Some(4000),
+            },
+        )
+    }
+}
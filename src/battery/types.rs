@@ -0,0 +1,116 @@
+use heapless::Vec;
+
+/// Maximum number of OCV tables in a [`BatteryModel`]
+pub const MAX_OCV_TABLES: usize = 4;
+
+/// Maximum number of points in an [`OcvTable`]
+pub const MAX_OCV_POINTS: usize = 24;
+
+/// Maximum number of points in the internal resistance curve of a [`BatteryModel`]
+pub const MAX_RESISTANCE_POINTS: usize = 8;
+
+/// Point of an open-circuit voltage curve
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OcvPoint {
+    /// State of charge, from 0.0 (empty) to 1.0 (full)
+    pub soc: f32,
+    /// Open-circuit voltage at this state of charge, in volts
+    pub voltage: f32,
+}
+
+/// Open-circuit voltage curve at a given temperature
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OcvTable {
+    /// Battery temperature in degrees Celsius
+    pub temperature: f32,
+    /// Curve points, sorted by increasing state of charge
+    pub points: Vec<OcvPoint, MAX_OCV_POINTS>,
+}
+
+/// Point of an internal resistance curve
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResistancePoint {
+    /// Battery temperature in degrees Celsius
+    pub temperature: f32,
+    /// Battery internal resistance (ESR) at this temperature, in ohms
+    pub resistance: f32,
+}
+
+/// Charging limits of a battery
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChargeLimits {
+    /// Constant charging current in mA, between 32 mA and 800 mA in steps of 2 mA
+    pub charge_current: u16,
+    /// Termination voltage in normal temperature conditions, in mV
+    ///
+    /// Must be one of the voltages of [`crate::charger::ChargerTerminationVoltage`].
+    pub termination_voltage: u16,
+    /// Termination voltage in warm temperature conditions, in mV
+    ///
+    /// Must be one of the voltages of [`crate::charger::ChargerTerminationVoltage`], or
+    /// `None` to keep the charger setting.
+    pub warm_termination_voltage: Option<u16>,
+}
+
+/// Battery model
+///
+/// Describes a battery for the [`crate::fuel_gauge::FuelGauge`] and the charger. Curves are
+/// interpolated linearly and clamped at both ends. Models are checked with
+/// [`Self::validate`] when loaded from their binary or JSON representation.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatteryModel {
+    /// Battery capacity in mAh, at most 65535 mAh
+    pub capacity: f32,
+    /// Open-circuit voltage curves, sorted by increasing temperature
+    pub ocv: Vec<OcvTable, MAX_OCV_TABLES>,
+    /// Internal resistance curve, sorted by increasing temperature
+    pub resistance: Vec<ResistancePoint, MAX_RESISTANCE_POINTS>,
+    /// Charging limits, see [`ChargeLimits`]
+    pub charge_limits: ChargeLimits,
+}
+
+/// Battery model errors
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum BatteryModelError {
+    #[error("battery capacity must be positive and at most 65535 mAh")]
+    InvalidCapacity,
+    #[error("battery model has no OCV table")]
+    MissingOcvTable,
+    #[error(
+        "OCV table must have at least 2 points with increasing state of charge (0.0 to 1.0) and voltage"
+    )]
+    InvalidOcvTable,
+    #[error("OCV tables must be sorted by increasing temperature")]
+    UnsortedOcvTables,
+    #[error("resistance curve must be sorted by increasing temperature with non-negative values")]
+    InvalidResistance,
+    #[error("invalid charge current, it must be between 32 and 800 mA and a multiple of 2")]
+    InvalidChargeCurrent,
+    #[error("termination voltage is not supported by the charger")]
+    InvalidTerminationVoltage,
+    #[error("battery model has too many points")]
+    TooManyPoints,
+    #[error("invalid battery model header")]
+    InvalidHeader,
+    #[error("unsupported battery model format version {0}")]
+    UnsupportedVersion(u8),
+    #[error("battery model data is truncated")]
+    Truncated,
+    #[error("battery model data has trailing bytes")]
+    TrailingBytes,
+    #[error("buffer is too small for the battery model")]
+    BufferTooSmall,
+    #[error("invalid battery model JSON")]
+    InvalidJson,
+}
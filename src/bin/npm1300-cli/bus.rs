@@ -0,0 +1,140 @@
+//! I2C bus and delay of the CLI, either a Linux i2c-dev bus or the simulator
+//!
+//! The i2c-dev bus uses i2cdev rather than the `I2cdev` of linux-embedded-hal. The bus has to
+//! implement the async and the blocking I2C traits on the same type as the simulator, so it
+//! would wrap `I2cdev` anyway, and mapping a transaction onto a single `I2C_RDWR` transfer is
+//! all that wrapper would do.
+
+use std::{thread, time::Duration};
+
+use embedded_hal_async::i2c::{Error, ErrorKind, ErrorType, Operation};
+use npm1300_rs::sim::{Sim, SimError};
+
+/// I2C bus error
+#[derive(thiserror::Error, Debug)]
+pub enum BusError {
+    #[error("simulator: {0}")]
+    Sim(SimError),
+    #[cfg(target_os = "linux")]
+    #[error("i2c-dev: {0}")]
+    Linux(i2cdev::linux::LinuxI2CError),
+}
+
+impl Error for BusError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            BusError::Sim(error) => error.kind(),
+            #[cfg(target_os = "linux")]
+            BusError::Linux(_) => ErrorKind::Other,
+        }
+    }
+}
+
+/// I2C bus of the nPM1300
+pub enum Bus {
+    /// Simulated nPM1300, see [`npm1300_rs::sim`]
+    Sim(Sim),
+    /// Linux i2c-dev bus, for example `/dev/i2c-1`
+    #[cfg(target_os = "linux")]
+    Linux(i2cdev::linux::LinuxI2CBus),
+}
+
+impl Bus {
+    /// Open a Linux i2c-dev bus
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the bus, for example `/dev/i2c-1`
+    #[cfg(target_os = "linux")]
+    pub fn open(path: &str) -> Result<Self, BusError> {
+        i2cdev::linux::LinuxI2CBus::new(path)
+            .map(Bus::Linux)
+            .map_err(BusError::Linux)
+    }
+
+    /// Run an I2C transaction, shared by the async and blocking traits
+    fn run(&mut self, address: u8, operations: &mut [Operation<'_>]) -> Result<(), BusError> {
+        match self {
+            Bus::Sim(sim) => {
+                embedded_hal::i2c::I2c::transaction(sim, address, operations).map_err(BusError::Sim)
+            }
+            #[cfg(target_os = "linux")]
+            Bus::Linux(bus) => {
+                use i2cdev::core::{I2CMessage, I2CTransfer};
+                use i2cdev::linux::LinuxI2CMessage;
+
+                // All the operations are sent as a single I2C_RDWR transfer, with repeated starts
+                let mut messages: Vec<_> = operations
+                    .iter_mut()
+                    .map(|operation| match operation {
+                        Operation::Write(data) => LinuxI2CMessage::write(data),
+                        Operation::Read(buffer) => LinuxI2CMessage::read(buffer),
+                    })
+                    .map(|message| message.with_address(address as u16))
+                    .collect();
+                bus.transfer(&mut messages)
+                    .map(drop)
+                    .map_err(BusError::Linux)
+            }
+        }
+    }
+}
+
+impl ErrorType for Bus {
+    type Error = BusError;
+}
+
+#[cfg(not(feature = "blocking"))]
+impl embedded_hal_async::i2c::I2c for Bus {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.run(address, operations)
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl embedded_hal::i2c::I2c for Bus {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.run(address, operations)
+    }
+}
+
+/// Delay provider of the driver
+#[derive(Clone)]
+pub enum Delay {
+    /// Simulated time, see [`Sim::elapsed_us`]
+    Sim(Sim),
+    /// Sleep the thread
+    Sleep,
+}
+
+impl Delay {
+    /// Wait, shared by the async and blocking traits
+    fn wait(&mut self, ns: u32) {
+        match self {
+            Delay::Sim(sim) => embedded_hal::delay::DelayNs::delay_ns(sim, ns),
+            Delay::Sleep => thread::sleep(Duration::from_nanos(ns as u64)),
+        }
+    }
+}
+
+#[cfg(not(feature = "blocking"))]
+impl embedded_hal_async::delay::DelayNs for Delay {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.wait(ns);
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl embedded_hal::delay::DelayNs for Delay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.wait(ns);
+    }
+}
@@ -0,0 +1,512 @@
+//! Command line tool to bring up and inspect an nPM1300 from a Linux host
+//!
+//! ```text
+//! npm1300-cli --device /dev/i2c-1 dump
+//! npm1300-cli decode dump.txt
+//! npm1300-cli --device /dev/i2c-1 rail buck1 voltage 1800
+//! npm1300-cli --device /dev/i2c-1 charger --current 200 --termination-voltage 4200 --enable
+//! npm1300-cli --sim measure vbat
+//! npm1300-cli --sim watch --count 5
+//! ```
+
+mod bus;
+
+use std::{fs, io, path::PathBuf, process::ExitCode};
+#[cfg(not(feature = "blocking"))]
+use std::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use bus::{Bus, BusError, Delay};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+#[cfg(feature = "blocking")]
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+#[cfg(not(feature = "blocking"))]
+use embedded_hal_async::{delay::DelayNs, i2c::I2c};
+use npm1300_rs::{
+    charger::DischargeCurrentLimit,
+    decode::{self, RegisterKind},
+    ldsw::LdswMode,
+    sim::Sim,
+    units::{Milliamps, Millivolts},
+    NPM1300Error, NtcThermistorType, NPM1300,
+};
+
+type Npm1300 = NPM1300<Bus, Delay>;
+type Error = NPM1300Error<BusError>;
+
+#[derive(Parser)]
+#[command(
+    name = "npm1300-cli",
+    version,
+    about = "Bring up and inspect an nPM1300 PMIC"
+)]
+struct Args {
+    /// I2C bus of the nPM1300, for example /dev/i2c-1
+    #[arg(long, conflicts_with = "sim")]
+    device: Option<String>,
+    /// Run against a simulated nPM1300 instead of a real device
+    #[arg(long)]
+    sim: bool,
+    /// 7-bit I2C address of the nPM1300, decimal or 0x-prefixed hexadecimal
+    #[arg(long, default_value = "0x6B", value_parser = parse_address)]
+    address: u8,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Read and decode all registers
+    Dump {
+        /// Print `address value` pairs instead of the decoded fields
+        #[arg(long)]
+        raw: bool,
+    },
+    /// Decode a register dump, like the output of `dump --raw`, without a device
+    Decode {
+        /// File of the dump, the standard input if not set
+        file: Option<PathBuf>,
+    },
+    /// Control a BUCK regulator or a load switch
+    Rail {
+        rail: Rail,
+        #[command(subcommand)]
+        action: RailAction,
+    },
+    /// Configure the battery charger and print its status
+    Charger {
+        /// Charge current in mA, between 32 and 800 in steps of 2
+        #[arg(long)]
+        current: Option<i16>,
+        /// Termination voltage in mV at normal temperature
+        #[arg(long)]
+        termination_voltage: Option<u16>,
+        /// Battery discharge current limit
+        #[arg(long)]
+        discharge_limit: Option<DischargeLimit>,
+        /// Enable charging
+        #[arg(long, conflicts_with = "disable")]
+        enable: bool,
+        /// Disable charging
+        #[arg(long)]
+        disable: bool,
+    },
+    /// Trigger a measurement and print the result
+    Measure {
+        #[command(subcommand)]
+        measurement: Measurement,
+    },
+    /// Print telemetry periodically
+    Watch {
+        /// Interval between two samples in ms
+        #[arg(long, default_value_t = 1000)]
+        interval: u32,
+        /// Number of samples, unlimited if not set
+        #[arg(long)]
+        count: Option<u32>,
+    },
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum Rail {
+    Buck1,
+    Buck2,
+    Ldsw1,
+    Ldsw2,
+}
+
+#[derive(Subcommand)]
+enum RailAction {
+    /// Enable the rail
+    On,
+    /// Disable the rail
+    Off,
+    /// Set the output voltage in mV, of a BUCK or of a load switch in LDO mode
+    Voltage { millivolts: u16 },
+    /// Select load switch or LDO mode
+    Mode { mode: LdswModeArg },
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum LdswModeArg {
+    LoadSwitch,
+    Ldo,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum DischargeLimit {
+    /// 200 mA
+    Low,
+    /// 1000 mA
+    High,
+}
+
+#[derive(Subcommand)]
+enum Measurement {
+    Vbat,
+    Vsys,
+    Vbus,
+    Ibat,
+    DieTemperature,
+    Ntc {
+        /// NTC thermistor of the battery
+        #[arg(long, default_value = "10k")]
+        thermistor: Thermistor,
+        /// Beta value of the NTC thermistor
+        #[arg(long, default_value_t = 3380)]
+        beta: u16,
+    },
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum Thermistor {
+    #[value(name = "10k")]
+    Ntc10K,
+    #[value(name = "47k")]
+    Ntc47K,
+    #[value(name = "100k")]
+    Ntc100K,
+}
+
+/// Parse an I2C address, decimal or 0x-prefixed hexadecimal
+fn parse_address(address: &str) -> Result<u8, String> {
+    let parsed = match address.strip_prefix("0x") {
+        Some(hex) => u8::from_str_radix(hex, 16),
+        None => address.parse(),
+    };
+    match parsed {
+        Ok(address) if address < 0x80 => Ok(address),
+        _ => Err(format!("invalid 7-bit I2C address {address}")),
+    }
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    if let Command::Rail {
+        rail: Rail::Buck1 | Rail::Buck2,
+        action: RailAction::Mode { .. },
+    } = args.command
+    {
+        Args::command()
+            .error(
+                clap::error::ErrorKind::InvalidValue,
+                "only load switches have a mode",
+            )
+            .exit();
+    }
+    if let Command::Decode { file } = &args.command {
+        return decode_file(file.as_ref());
+    }
+    if args.device.is_none() && !args.sim {
+        Args::command()
+            .error(
+                clap::error::ErrorKind::MissingRequiredArgument,
+                "--device or --sim is required",
+            )
+            .exit();
+    }
+
+    let (bus, delay) = if args.sim {
+        let sim = Sim::new_with_address(args.address);
+        (Bus::Sim(sim.clone()), Delay::Sim(sim))
+    } else {
+        #[cfg(target_os = "linux")]
+        match Bus::open(args.device.as_deref().unwrap_or_default()) {
+            Ok(bus) => (bus, Delay::Sleep),
+            Err(error) => {
+                eprintln!("error: {error}");
+                return ExitCode::FAILURE;
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            eprintln!("error: i2c-dev buses are only supported on Linux, use --sim");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    #[cfg(not(feature = "blocking"))]
+    let result = block_on(run(bus, delay, args.address, args.command));
+    #[cfg(feature = "blocking")]
+    let result = run(bus, delay, args.address, args.command);
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Run a future to completion
+///
+/// The I2C transfers and delays of the CLI block the thread, so the futures of the driver
+/// complete without being woken.
+#[cfg(not(feature = "blocking"))]
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+#[maybe_async::maybe_async]
+async fn run(mut bus: Bus, delay: Delay, address: u8, command: Command) -> Result<(), Error> {
+    // The dump reads the registers directly, the driver has no access by address
+    if let Command::Dump { raw } = command {
+        return dump(&mut bus, address, raw).await;
+    }
+
+    let npm1300 = &mut NPM1300::new_with_address(bus, delay.clone(), address);
+    match command {
+        Command::Dump { .. } | Command::Decode { .. } => unreachable!(),
+        Command::Rail { rail, action } => set_rail(npm1300, rail, action).await,
+        Command::Charger {
+            current,
+            termination_voltage,
+            discharge_limit,
+            enable,
+            disable,
+        } => {
+            if disable {
+                npm1300.disable_battery_charging().await?;
+            }
+            if let Some(current) = current {
+                npm1300.set_charger_current(Milliamps(current)).await?;
+            }
+            if let Some(voltage) = termination_voltage {
+                npm1300
+                    .set_normal_temperature_termination_voltage(Millivolts(voltage))
+                    .await?;
+            }
+            if let Some(limit) = discharge_limit {
+                npm1300
+                    .set_discharge_current_limit(match limit {
+                        DischargeLimit::Low => DischargeCurrentLimit::Low,
+                        DischargeLimit::High => DischargeCurrentLimit::High,
+                    })
+                    .await?;
+            }
+            if enable {
+                npm1300.enable_battery_charging().await?;
+            }
+            let enabled = npm1300.is_charging_enabled().await?;
+            let current = npm1300.get_charger_config_current().await?;
+            let status = npm1300.get_charger_status().await?;
+            println!("charging enabled: {enabled}");
+            println!("charge current: {current} mA");
+            println!("{status:#?}");
+            Ok(())
+        }
+        Command::Measure { measurement } => measure(npm1300, measurement).await,
+        Command::Watch { interval, count } => watch(npm1300, delay, interval, count).await,
+    }
+}
+
+/// Read all registers, skipping the tasks
+#[maybe_async::maybe_async]
+async fn dump(bus: &mut Bus, address: u8, raw: bool) -> Result<(), Error> {
+    for register in decode::registers() {
+        if register.kind == RegisterKind::Command {
+            continue;
+        }
+        let mut value = [0];
+        bus.write_read(address, &register.address.to_be_bytes(), &mut value)
+            .await
+            .map_err(NPM1300Error::I2c)?;
+        let [value] = value;
+        if raw {
+            println!("{:#06x} {value:#04x}", register.address);
+        } else {
+            let decoded =
+                decode::decode(register.address, value).expect("registers of the map decode");
+            println!("{:#06x} {decoded}", register.address);
+        }
+    }
+    Ok(())
+}
+
+/// Decode a register dump and print the registers, flagging reserved and invalid values
+fn decode_file(file: Option<&PathBuf>) -> ExitCode {
+    let dump = match file {
+        Some(file) => fs::read_to_string(file),
+        None => io::read_to_string(io::stdin()),
+    };
+    let registers = match dump.map(|dump| decode::decode_dump(&dump)) {
+        Ok(Ok(registers)) => registers,
+        Ok(Err(error)) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    for register in &registers {
+        println!("{:#06x} {register}", register.register.address);
+    }
+    let flagged = registers
+        .iter()
+        .filter(|register| !register.is_valid())
+        .count();
+    if flagged > 0 {
+        eprintln!(
+            "{flagged} of {} registers have reserved or invalid values",
+            registers.len()
+        );
+    }
+    ExitCode::SUCCESS
+}
+
+/// Control a rail
+#[maybe_async::maybe_async]
+async fn set_rail(npm1300: &mut Npm1300, rail: Rail, action: RailAction) -> Result<(), Error> {
+    match (rail, action) {
+        (Rail::Buck1, RailAction::On) => npm1300.enable_buck1().await,
+        (Rail::Buck2, RailAction::On) => npm1300.enable_buck2().await,
+        (Rail::Ldsw1, RailAction::On) => npm1300.enable_ldsw1().await,
+        (Rail::Ldsw2, RailAction::On) => npm1300.enable_ldsw2().await,
+        (Rail::Buck1, RailAction::Off) => npm1300.disable_buck1().await,
+        (Rail::Buck2, RailAction::Off) => npm1300.disable_buck2().await,
+        (Rail::Ldsw1, RailAction::Off) => npm1300.disable_ldsw1().await,
+        (Rail::Ldsw2, RailAction::Off) => npm1300.disable_ldsw2().await,
+        (Rail::Buck1, RailAction::Voltage { millivolts }) => {
+            npm1300
+                .set_buck1_normal_voltage(Millivolts(millivolts))
+                .await
+        }
+        (Rail::Buck2, RailAction::Voltage { millivolts }) => {
+            npm1300
+                .set_buck2_normal_voltage(Millivolts(millivolts))
+                .await
+        }
+        (Rail::Ldsw1, RailAction::Voltage { millivolts }) => {
+            npm1300.set_ldo1_voltage(Millivolts(millivolts)).await
+        }
+        (Rail::Ldsw2, RailAction::Voltage { millivolts }) => {
+            npm1300.set_ldo2_voltage(Millivolts(millivolts)).await
+        }
+        (Rail::Ldsw1, RailAction::Mode { mode }) => npm1300.set_ldsw1_mode(mode.into()).await,
+        (Rail::Ldsw2, RailAction::Mode { mode }) => npm1300.set_ldsw2_mode(mode.into()).await,
+        (Rail::Buck1 | Rail::Buck2, RailAction::Mode { .. }) => {
+            unreachable!("rejected when parsing the arguments")
+        }
+    }
+}
+
+impl From<LdswModeArg> for LdswMode {
+    fn from(mode: LdswModeArg) -> Self {
+        match mode {
+            LdswModeArg::LoadSwitch => LdswMode::LoadSwitch,
+            LdswModeArg::Ldo => LdswMode::Ldo,
+        }
+    }
+}
+
+/// Trigger a measurement and print the result
+#[maybe_async::maybe_async]
+async fn measure(npm1300: &mut Npm1300, measurement: Measurement) -> Result<(), Error> {
+    match measurement {
+        Measurement::Vbat => {
+            let vbat = npm1300.measure_vbat_millivolts().await?;
+            println!("{} mV", vbat.0);
+        }
+        Measurement::Vsys => {
+            let vsys = npm1300.measure_vsys_millivolts().await?;
+            println!("{} mV", vsys.0);
+        }
+        Measurement::Vbus => {
+            let vbus = npm1300.measure_vbus_millivolts().await?;
+            println!("{} mV", vbus.0);
+        }
+        Measurement::Ibat => {
+            let ibat = measure_ibat(npm1300).await?;
+            println!("{} mA", ibat.0);
+        }
+        Measurement::DieTemperature => {
+            let temperature = npm1300.measure_die_temperature_centi_celsius().await?;
+            println!("{:.2} °C", temperature.0 as f32 / 100.0);
+        }
+        Measurement::Ntc { thermistor, beta } => {
+            let thermistor = match thermistor {
+                Thermistor::Ntc10K => NtcThermistorType::Ntc10K,
+                Thermistor::Ntc47K => NtcThermistorType::Ntc47K,
+                Thermistor::Ntc100K => NtcThermistorType::Ntc100K,
+            };
+            // Restore the thermistor configuration of the device after the measurement
+            let previous = npm1300.get_ntc_resistance_configuration().await?;
+            npm1300
+                .configure_ntc_resistance_kelvin(thermistor, Some(beta))
+                .await?;
+            let temperature = npm1300.measure_ntc_centi_celsius().await;
+            npm1300
+                .configure_ntc_resistance_kelvin(previous, Some(beta))
+                .await?;
+            println!("{:.2} °C", temperature?.0 as f32 / 100.0);
+        }
+    }
+    Ok(())
+}
+
+/// Measure the battery current, with a VBAT measurement triggering the IBAT measurement
+///
+/// IBAT measurement is enabled for the measurement and disabled again if it was disabled.
+#[maybe_async::maybe_async]
+async fn measure_ibat(npm1300: &mut Npm1300) -> Result<Milliamps, Error> {
+    let enabled = npm1300.get_ibat_measurement_configuration().await?;
+    if !enabled {
+        npm1300.configure_ibat_measurement(true).await?;
+    }
+    let ibat = match npm1300.measure_vbat_millivolts().await {
+        Ok(_) => npm1300.measure_ibat_milliamps().await,
+        Err(error) => Err(error),
+    };
+    if !enabled {
+        npm1300.configure_ibat_measurement(false).await?;
+    }
+    ibat
+}
+
+/// Print telemetry periodically
+#[maybe_async::maybe_async]
+async fn watch(
+    npm1300: &mut Npm1300,
+    mut delay: Delay,
+    interval: u32,
+    count: Option<u32>,
+) -> Result<(), Error> {
+    println!("vbat_mv vsys_mv ibat_ma die_c charging buck1 buck2");
+    let mut samples = 0;
+    while count.is_none_or(|count| samples < count) {
+        if samples > 0 {
+            delay.delay_ms(interval).await;
+        }
+        let vbat = npm1300.measure_vbat_millivolts().await?;
+        let vsys = npm1300.measure_vsys_millivolts().await?;
+        let ibat = measure_ibat(npm1300).await?;
+        let die = npm1300.measure_die_temperature_centi_celsius().await?;
+        let charger = npm1300.get_charger_status().await?;
+        let bucks = npm1300.get_buck_status().await?;
+        println!(
+            "{} {} {} {:.1} {} {:?} {:?}",
+            vbat.0,
+            vsys.0,
+            ibat.0,
+            die.0 as f32 / 100.0,
+            charger.is_constant_current_charging || charger.is_constant_voltage_charging,
+            bucks.buck_1_pwrgood(),
+            bucks.buck_2_pwrgood(),
+        );
+        samples += 1;
+    }
+    Ok(())
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "blocking")]
+use crate::hal::prelude::*;
 use crate::{
     common::Task,
     field_sets::Buckstatus,
@@ -12,14 +14,6 @@ mod types;
 // Re-export everything in types.rs
 pub use types::*;
 
-/// Convert a GPIO enum value to its register index
-///
-/// GPIOs are 1-indexed in the nPM1300 so we subtract 1 from the GPIO number
-/// to get the register index
-fn gpio_to_register_index(gpio: Gpio) -> usize {
-    usize::from(u8::from(gpio) - 1)
-}
-
 pub struct Config {
     /// GPIO to enable/disable BUCK regulators
     pub gpio_buck_enable_control: Gpio,
@@ -36,32 +30,31 @@ impl Default for Config {
     }
 }
 
-impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
-    crate::NPM1300<I2c, Delay>
-{
+#[maybe_async::maybe_async]
+impl<I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> crate::NPM1300<I2c, Delay> {
     /// Enable or disable a BUCK regulator
     ///
     /// # Arguments
     ///
-    /// * `buck_index` - Index of the BUCK regulator (0 for BUCK1, 1 for BUCK2)
+    /// * `buck` - The BUCK regulator, see [`Buck`]
     /// * `enable` - true to enable the regulator, false to disable it
     async fn control_buck_power(
         &mut self,
-        buck_index: u8,
+        buck: Buck,
         enable: bool,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
         if enable {
             // Enable the regulator
             self.device
                 .buck()
-                .buckenaset(buck_index.into())
+                .buckenaset(buck.index())
                 .dispatch_async(|command| command.set_taskbuckenaset(Task::Trigger))
                 .await
         } else {
             // Disable the regulator
             self.device
                 .buck()
-                .buckenaclr(buck_index.into())
+                .buckenaclr(buck.index())
                 .dispatch_async(|command| command.set_taskbuckenaclr(Task::Trigger))
                 .await
         }
@@ -69,47 +62,47 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
 
     /// Enable BUCK1
     pub async fn enable_buck1(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.control_buck_power(0, true).await
+        self.control_buck_power(Buck::Buck1, true).await
     }
 
     /// Disable BUCK1
     pub async fn disable_buck1(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.control_buck_power(0, false).await
+        self.control_buck_power(Buck::Buck1, false).await
     }
 
     /// Enable BUCK2
     pub async fn enable_buck2(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.control_buck_power(1, true).await
+        self.control_buck_power(Buck::Buck2, true).await
     }
 
     /// Disable BUCK2
     pub async fn disable_buck2(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.control_buck_power(1, false).await
+        self.control_buck_power(Buck::Buck2, false).await
     }
 
     /// Enable or disable forced PWM mode for a BUCK regulator
     ///
     /// # Arguments
     ///
-    /// * `buck_index` - Index of the BUCK regulator (0 for BUCK1, 1 for BUCK2)
+    /// * `buck` - The BUCK regulator, see [`Buck`]
     /// * `enable` - true to enable forced PWM mode, false to return to auto mode
     async fn set_buck_forced_pwm_mode(
         &mut self,
-        buck_index: u8,
+        buck: Buck,
         enable: bool,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
         if enable {
             // Enable forced PWM mode
             self.device
                 .buck()
-                .buckpwmset(buck_index.into())
+                .buckpwmset(buck.index())
                 .dispatch_async(|command| command.set_taskbuckpwmset(Task::Trigger))
                 .await
         } else {
             // Disable forced PWM mode
             self.device
                 .buck()
-                .buckpwmclr(buck_index.into())
+                .buckpwmclr(buck.index())
                 .dispatch_async(|command| command.set_taskbuckpwmclr(Task::Trigger))
                 .await
         }
@@ -119,39 +112,44 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     pub async fn enable_buck1_forced_pwm_mode(
         &mut self,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.set_buck_forced_pwm_mode(0, true).await
+        self.set_buck_forced_pwm_mode(Buck::Buck1, true).await
     }
 
     /// Disable BUCK1 forced PWM mode and return to auto mode
     pub async fn disable_buck1_forced_pwm_mode(
         &mut self,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.set_buck_forced_pwm_mode(0, false).await
+        self.set_buck_forced_pwm_mode(Buck::Buck1, false).await
     }
 
     /// Enable BUCK2 forced PWM mode
     pub async fn enable_buck2_forced_pwm_mode(
         &mut self,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.set_buck_forced_pwm_mode(1, true).await
+        self.set_buck_forced_pwm_mode(Buck::Buck2, true).await
     }
 
     /// Disable BUCK2 forced PWM mode and return to auto mode
     pub async fn disable_buck2_forced_pwm_mode(
         &mut self,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.set_buck_forced_pwm_mode(1, false).await
+        self.set_buck_forced_pwm_mode(Buck::Buck2, false).await
     }
 
     /// Set BUCK1 normal mode output voltage
     ///
     /// # Arguments
     ///
-    /// * `voltage` - The voltage to set for BUCK1. See [`BuckVoltage`] for available values.
-    pub async fn set_buck1_normal_voltage(
+    /// * `voltage` - The voltage to set for BUCK1, a [`BuckVoltage`] or [`Millivolts`](crate::units::Millivolts)
+    pub async fn set_buck1_normal_voltage<V>(
         &mut self,
-        voltage: BuckVoltage,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        voltage: V,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>>
+    where
+        V: TryInto<BuckVoltage>,
+        crate::NPM1300Error<I2c::Error>: From<V::Error>,
+    {
+        let voltage = voltage.try_into()?;
         // Set BUCK1 normal mode output voltage
         self.device
             .buck()
@@ -189,10 +187,19 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     }
 
     /// Set BUCK2 normal mode output voltage
-    pub async fn set_buck2_normal_voltage(
+    ///
+    /// # Arguments
+    ///
+    /// * `voltage` - The voltage to set for BUCK2, a [`BuckVoltage`] or [`Millivolts`](crate::units::Millivolts)
+    pub async fn set_buck2_normal_voltage<V>(
         &mut self,
-        voltage: BuckVoltage,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        voltage: V,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>>
+    where
+        V: TryInto<BuckVoltage>,
+        crate::NPM1300Error<I2c::Error>: From<V::Error>,
+    {
+        let voltage = voltage.try_into()?;
         // Set BUCK2 normal mode output voltage
         self.device
             .buck()
@@ -211,65 +218,64 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Arguments
     ///
-    /// * `buck_index` - Index of the BUCK regulator (1 for BUCK1, 2 for BUCK2)
+    /// * `buck` - The BUCK regulator, see [`Buck`]
     /// * `voltage` - The voltage to set for the BUCK. See [`BuckVoltage`] for available values.
     /// * `gpio` - The GPIO to set for retention mode. See [`Gpio`] for available values.
     /// * `polarity` - The polarity of the GPIO. See [`GpioPolarity`] for available values.
     async fn configure_buck_retention_mode(
         &mut self,
-        buck_index: u8,
+        buck: Buck,
         voltage: BuckVoltage,
         gpio: Gpio,
         polarity: GpioPolarity,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        // Configure GPIO mode as input
-        self.device
-            .gpios()
-            .gpiomode(gpio_to_register_index(gpio))
-            .write_async(|reg| reg.set_gpiomode(GpioMode::GpiInput))
-            .await?;
+        if let Some(pin) = gpio.pin() {
+            // Configure GPIO mode as input
+            self.device
+                .gpios()
+                .gpiomode(pin.index())
+                .write_async(|reg| reg.set_gpiomode(GpioMode::GpiInput))
+                .await?;
+        }
         // Set retention mode GPIO and its polarity
         self.device
             .buck()
             .buckvretctrl()
-            .modify_async(|reg| match buck_index {
-                1 => {
+            .modify_async(|reg| match buck {
+                Buck::Buck1 => {
                     reg.set_buck_1_vretgpisel(gpio);
                     reg.set_buck_1_vretgpiinv(polarity);
                 }
-                2 => {
+                Buck::Buck2 => {
                     reg.set_buck_2_vretgpisel(gpio);
                     reg.set_buck_2_vretgpiinv(polarity);
                 }
-                _ => panic!("Invalid BUCK index"),
             })
             .await?;
         // Set retention mode output voltage
-        match buck_index {
-            1 => {
+        match buck {
+            Buck::Buck1 => {
                 self.device
                     .buck()
                     .buck_1_retvout()
                     .write_async(|reg| reg.set_value(voltage))
                     .await?
             }
-            2 => {
+            Buck::Buck2 => {
                 self.device
                     .buck()
                     .buck_2_retvout()
                     .write_async(|reg| reg.set_value(voltage))
                     .await?
             }
-            _ => panic!("Invalid BUCK index"),
         }
         // Allow SW to override VSET pin
         self.device
             .buck()
             .buckswctrlsel()
-            .modify_async(|reg| match buck_index {
-                1 => reg.set_buck_1_swctrlsel(Buck1Swctrlsel::Swctrl),
-                2 => reg.set_buck_2_swctrlsel(Buck2Swctrlsel::Swctrl),
-                _ => panic!("Invalid BUCK index"),
+            .modify_async(|reg| match buck {
+                Buck::Buck1 => reg.set_buck_1_swctrlsel(Buck1Swctrlsel::Swctrl),
+                Buck::Buck2 => reg.set_buck_2_swctrlsel(Buck2Swctrlsel::Swctrl),
             })
             .await
     }
@@ -278,16 +284,21 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Arguments
     ///
-    /// * `voltage` - The voltage to set for the BUCK. See [`BuckVoltage`] for available values.
+    /// * `voltage` - The voltage to set for the BUCK, a [`BuckVoltage`] or [`Millivolts`](crate::units::Millivolts)
     /// * `gpio` - The GPIO to set for retention mode. See [`Gpio`] for available values.
     /// * `polarity` - The polarity of the GPIO. See [`GpioPolarity`] for available values.
-    pub async fn configure_buck1_retention_mode(
+    pub async fn configure_buck1_retention_mode<V>(
         &mut self,
-        voltage: BuckVoltage,
+        voltage: V,
         gpio: Gpio,
         polarity: GpioPolarity,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.configure_buck_retention_mode(1, voltage, gpio, polarity)
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>>
+    where
+        V: TryInto<BuckVoltage>,
+        crate::NPM1300Error<I2c::Error>: From<V::Error>,
+    {
+        let voltage = voltage.try_into()?;
+        self.configure_buck_retention_mode(Buck::Buck1, voltage, gpio, polarity)
             .await
     }
 
@@ -295,16 +306,21 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Arguments
     ///
-    /// * `voltage` - The voltage to set for the BUCK. See [`BuckVoltage`] for available values.
+    /// * `voltage` - The voltage to set for the BUCK, a [`BuckVoltage`] or [`Millivolts`](crate::units::Millivolts)
     /// * `gpio` - The GPIO to set for retention mode. See [`Gpio`] for available values.
     /// * `polarity` - The polarity of the GPIO. See [`GpioPolarity`] for available values.
-    pub async fn configure_buck2_retention_mode(
+    pub async fn configure_buck2_retention_mode<V>(
         &mut self,
-        voltage: BuckVoltage,
+        voltage: V,
         gpio: Gpio,
         polarity: GpioPolarity,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.configure_buck_retention_mode(2, voltage, gpio, polarity)
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>>
+    where
+        V: TryInto<BuckVoltage>,
+        crate::NPM1300Error<I2c::Error>: From<V::Error>,
+    {
+        let voltage = voltage.try_into()?;
+        self.configure_buck_retention_mode(Buck::Buck2, voltage, gpio, polarity)
             .await
     }
 
@@ -329,20 +345,20 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     /// Configure BUCK GPIO enable control
     ///
     /// # Arguments
-    /// * `buck_index` - BUCK index
+    /// * `buck` - The BUCK regulator, see [`Buck`]
     /// * `gpio` - GPIO to enable
     /// * `polarity` - Polarity of GPIO
     async fn configure_buck_gpio_enable_control(
         &mut self,
-        buck_index: u8,
+        buck: Buck,
         gpio: Gpio,
         polarity: GpioPolarity,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        if gpio != Gpio::None {
+        if let Some(pin) = gpio.pin() {
             // Configure GPIO mode as input
             self.device
                 .gpios()
-                .gpiomode(gpio_to_register_index(gpio))
+                .gpiomode(pin.index())
                 .write_async(|reg| reg.set_gpiomode(GpioMode::GpiInput))
                 .await?;
 
@@ -350,16 +366,15 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             self.device
                 .buck()
                 .buckenctrl()
-                .modify_async(|reg| match buck_index {
-                    1 => {
+                .modify_async(|reg| match buck {
+                    Buck::Buck1 => {
                         reg.set_buck_1_engpisel(gpio);
                         reg.set_buck_1_engpiinv(polarity);
                     }
-                    2 => {
+                    Buck::Buck2 => {
                         reg.set_buck_2_engpisel(gpio);
                         reg.set_buck_2_engpiinv(polarity);
                     }
-                    _ => panic!("Invalid BUCK index"),
                 })
                 .await?;
         }
@@ -376,7 +391,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         gpio: Gpio,
         polarity: GpioPolarity,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.configure_buck_gpio_enable_control(1, gpio, polarity)
+        self.configure_buck_gpio_enable_control(Buck::Buck1, gpio, polarity)
             .await
     }
 
@@ -390,27 +405,27 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         gpio: Gpio,
         polarity: GpioPolarity,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.configure_buck_gpio_enable_control(2, gpio, polarity)
+        self.configure_buck_gpio_enable_control(Buck::Buck2, gpio, polarity)
             .await
     }
 
     /// Configure BUCK GPIO forced PWM mode control
     ///
     /// # Arguments
-    /// * `buck_index` - BUCK index
+    /// * `buck` - The BUCK regulator, see [`Buck`]
     /// * `gpio` - GPIO to enable
     /// * `polarity` - Polarity of GPIO
     async fn configure_buck_forced_pwm_mode_control(
         &mut self,
-        buck_index: u8,
+        buck: Buck,
         gpio: Gpio,
         polarity: GpioPolarity,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        if gpio != Gpio::None {
+        if let Some(pin) = gpio.pin() {
             // Configure GPIO mode as input
             self.device
                 .gpios()
-                .gpiomode(gpio_to_register_index(gpio))
+                .gpiomode(pin.index())
                 .write_async(|reg| reg.set_gpiomode(GpioMode::GpiInput))
                 .await?;
 
@@ -418,16 +433,15 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             self.device
                 .buck()
                 .buckpwmctrl()
-                .modify_async(|reg| match buck_index {
-                    1 => {
+                .modify_async(|reg| match buck {
+                    Buck::Buck1 => {
                         reg.set_buck_1_pwmgpisel(gpio);
                         reg.set_buck_1_pwmgpiinv(polarity);
                     }
-                    2 => {
+                    Buck::Buck2 => {
                         reg.set_buck_2_pwmgpisel(gpio);
                         reg.set_buck_2_pwmgpiinv(polarity);
                     }
-                    _ => panic!("Invalid BUCK index"),
                 })
                 .await?;
         }
@@ -444,7 +458,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         gpio: Gpio,
         polarity: GpioPolarity,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.configure_buck_forced_pwm_mode_control(1, gpio, polarity)
+        self.configure_buck_forced_pwm_mode_control(Buck::Buck1, gpio, polarity)
             .await
     }
 
@@ -458,7 +472,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         gpio: Gpio,
         polarity: GpioPolarity,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.configure_buck_forced_pwm_mode_control(2, gpio, polarity)
+        self.configure_buck_forced_pwm_mode_control(Buck::Buck2, gpio, polarity)
             .await
     }
 
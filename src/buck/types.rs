@@ -1,5 +1,22 @@
+use crate::units::{nearest_step_index, step_index, Millivolts, UnitError};
+
+/// BUCK regulators of the nPM1300
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum Buck {
+    Buck1 = 0,
+    Buck2 = 1,
+}
+
+impl Buck {
+    /// Get the register index of the BUCK regulator
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
 /// Buck regulator voltages available on the nPM1300
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum BuckVoltage {
     V1_0 = 0,
@@ -71,3 +88,28 @@ impl From<BuckVoltage> for u8 {
         voltage as u8
     }
 }
+
+impl BuckVoltage {
+    /// Get the voltage nearest to `voltage`, clamped to the 1.0 V to 3.3 V range
+    pub fn nearest(voltage: Millivolts) -> Self {
+        Self::try_from(nearest_step_index(voltage.0 as i32, 1000, 3300, 100)).unwrap_or(Self::V3_3)
+    }
+}
+
+// Add conversion from Millivolts to BuckVoltage, rejecting voltages between steps
+impl TryFrom<Millivolts> for BuckVoltage {
+    type Error = UnitError;
+
+    fn try_from(voltage: Millivolts) -> Result<Self, Self::Error> {
+        let index = step_index(voltage.0 as i32, 1000, 3300, 100)?;
+        Self::try_from(index).map_err(|_| UnitError::OutOfRange)
+    }
+}
+
+// Add conversion from BuckVoltage to Millivolts
+impl From<BuckVoltage> for Millivolts {
+    fn from(voltage: BuckVoltage) -> Self {
+        // V3_30 is a duplicate of V3_3
+        Millivolts(1000 + 100 * (voltage as u16).min(23))
+    }
+}
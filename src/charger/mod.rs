@@ -3,13 +3,12 @@ mod types;
 // Re-export everything in types.rs
 pub use types::*;
 
-use libm::roundf;
-
+#[cfg(feature = "blocking")]
+use crate::hal::prelude::*;
 use crate::{common::Task, Bchgilimbatactive, Dietemphigh, NPM1300Error};
 
-impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
-    crate::NPM1300<I2c, Delay>
-{
+#[maybe_async::maybe_async]
+impl<I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> crate::NPM1300<I2c, Delay> {
     /// Clear charger errors
     ///
     /// Clears charger errors in BCHGERRREASON and BCHGERRSENSOR registers.
@@ -64,7 +63,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         self.device
             .charger()
             .bchgenableset()
-            .modify_async(|reg| reg.set_enablecharging(ChargerEnableSet::EnableCharger))
+            .write_async(|reg| reg.set_enablecharging(ChargerEnableSet::EnableCharger))
             .await
     }
 
@@ -76,7 +75,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         self.device
             .charger()
             .bchgenableclr()
-            .modify_async(|reg| reg.set_enablecharging(ChargerEnableClear::DisableCharger))
+            .write_async(|reg| reg.set_enablecharging(ChargerEnableClear::DisableCharger))
             .await
     }
 
@@ -90,7 +89,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         match status.enablecharging() {
             Ok(ChargerEnableSet::EnableCharger) => Ok(true),
             Ok(ChargerEnableSet::NoEffect) => Ok(false),
-            Err(_) => panic!("Failed to read BCHGENABLESET register"),
+            Err(_) => Err(crate::NPM1300Error::UnexpectedRegisterValue),
         }
     }
 
@@ -103,7 +102,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         self.device
             .charger()
             .bchgenableset()
-            .modify_async(|reg| {
+            .write_async(|reg| {
                 reg.set_enablefullchgcool(
                     ChargerEnableFullCurrentChargeInCoolTempSet::EnableFullCurrentChargeInCoolTemp,
                 )
@@ -120,7 +119,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         self.device
             .charger()
             .bchgenableclr()
-            .modify_async(|reg| {
+            .write_async(|reg| {
                 reg.set_enablefullchgcool(
                     ChargerEnableFullCurrentChargeInCoolTempClear::DisableFullCurrentChargeInCoolTemp,
                 )
@@ -142,7 +141,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
                 Ok(true)
             }
             Ok(ChargerEnableFullCurrentChargeInCoolTempSet::NoEffect) => Ok(false),
-            Err(_) => panic!("Failed to read BCHGENABLESET register"),
+            Err(_) => Err(crate::NPM1300Error::UnexpectedRegisterValue),
         }
     }
 
@@ -151,9 +150,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         self.device
             .charger()
             .bchgdisableclr()
-            .modify_async(|reg| {
-                reg.set_disablerecharge(ChargerDisableRechargeClear::EnableRecharge)
-            })
+            .write_async(|reg| reg.set_disablerecharge(ChargerDisableRechargeClear::EnableRecharge))
             .await
     }
 
@@ -164,7 +161,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         self.device
             .charger()
             .bchgdisableset()
-            .modify_async(|reg| reg.set_disablerecharge(ChargerDisableRechargeSet::DisableRecharge))
+            .write_async(|reg| reg.set_disablerecharge(ChargerDisableRechargeSet::DisableRecharge))
             .await
     }
 
@@ -180,7 +177,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         match status.disablerecharge() {
             Ok(ChargerDisableRechargeSet::NoEffect) => Ok(true),
             Ok(ChargerDisableRechargeSet::DisableRecharge) => Ok(false),
-            Err(_) => panic!("Failed to read BCHGDISABLESET register"),
+            Err(_) => Err(crate::NPM1300Error::UnexpectedRegisterValue),
         }
     }
 
@@ -189,7 +186,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         self.device
             .charger()
             .bchgdisableset()
-            .modify_async(|reg| reg.set_disablentc(DisableNtcSet::IgnoreNtc))
+            .write_async(|reg| reg.set_disablentc(DisableNtcSet::IgnoreNtc))
             .await
     }
 
@@ -198,7 +195,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         self.device
             .charger()
             .bchgdisableclr()
-            .modify_async(|reg| reg.set_disablentc(DisableNtcClear::UseNtc))
+            .write_async(|reg| reg.set_disablentc(DisableNtcClear::UseNtc))
             .await
     }
 
@@ -214,7 +211,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         match status.disablentc() {
             Ok(DisableNtcSet::IgnoreNtc) => Ok(true),
             Ok(DisableNtcSet::NoEffect) => Ok(false),
-            Err(_) => panic!("Failed to read BCHGDISABLESET register"),
+            Err(_) => Err(crate::NPM1300Error::UnexpectedRegisterValue),
         }
     }
 
@@ -224,25 +221,26 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Arguments
     ///
-    /// * `current_ma` - The desired charging current in milliamps (mA). Maximum value is 800mA.
+    /// * `current` - The desired charging current, a [`ChargeCurrent`] or
+    ///   [`Milliamps`](crate::units::Milliamps) between 32 mA and 800 mA in steps of 2 mA
     ///
     /// # Errors
     ///
-    /// Returns `NPM1300Error::ChargerCurrentTooHigh` if the requested current exceeds 800mA.
+    /// Returns `NPM1300Error::UnsupportedValue` if the current is outside of the range or odd.
     ///
     /// # Note
     ///
     /// This function will temporarily disable charging while updating the current settings if
     /// charging is enabled, then restore the previous charging state.
-    pub async fn set_charger_current(
+    pub async fn set_charger_current<C>(
         &mut self,
-        current_ma: u16,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        const MAX_CURRENT_MA: u16 = 800;
-
-        if current_ma > MAX_CURRENT_MA {
-            return Err(crate::NPM1300Error::ChargerCurrentTooHigh(current_ma));
-        }
+        current: C,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>>
+    where
+        C: TryInto<ChargeCurrent>,
+        crate::NPM1300Error<I2c::Error>: From<C::Error>,
+    {
+        let current = current.try_into()?;
 
         // Check if charging was enabled
         let was_enabled = self.is_charging_enabled().await?;
@@ -255,8 +253,7 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         // Convert current to register values:
         // MSB = floor(current_ma/4)
         // LSB = 1 if (current_ma/2) is odd, 0 if even
-        let msb = (current_ma / 4) as u8;
-        let lsb = ((current_ma / 2) & 1) as u8;
+        let (msb, lsb) = current.registers();
 
         // Update MSB register
         self.device
@@ -361,11 +358,17 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Arguments
     ///
-    /// * `termination_voltage` - The desired termination voltage setting
-    pub async fn set_normal_temperature_termination_voltage(
+    /// * `termination_voltage` - The desired termination voltage, a [`ChargerTerminationVoltage`]
+    ///   or [`Millivolts`](crate::units::Millivolts)
+    pub async fn set_normal_temperature_termination_voltage<V>(
         &mut self,
-        termination_voltage: ChargerTerminationVoltage,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        termination_voltage: V,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>>
+    where
+        V: TryInto<ChargerTerminationVoltage>,
+        crate::NPM1300Error<I2c::Error>: From<V::Error>,
+    {
+        let termination_voltage = termination_voltage.try_into()?;
         self.device
             .charger()
             .bchgvterm()
@@ -373,6 +376,42 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             .await
     }
 
+    /// Configure the charger for a battery
+    ///
+    /// Sets the charging current and the termination voltages from the charge limits of the
+    /// model.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The battery model, see [`crate::battery::BatteryModel`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::InvalidBatteryModel` if the model does not pass
+    /// [`crate::battery::BatteryModel::validate`].
+    #[cfg(feature = "float")]
+    pub async fn configure_charger_for_battery(
+        &mut self,
+        model: &crate::battery::BatteryModel,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        model
+            .validate()
+            .map_err(crate::NPM1300Error::InvalidBatteryModel)?;
+
+        if let Some(current) = model.charge_current() {
+            self.set_charger_current(current).await?;
+        }
+        if let Some(termination_voltage) = model.termination_voltage() {
+            self.set_normal_temperature_termination_voltage(termination_voltage)
+                .await?;
+        }
+        if let Some(termination_voltage) = model.warm_termination_voltage() {
+            self.set_warm_temperature_termination_voltage(termination_voltage)
+                .await?;
+        }
+        Ok(())
+    }
+
     /// Set the battery charging termination voltage for warm temperature conditions
     ///
     /// Configures the voltage at which constant current charging starts when battery
@@ -380,11 +419,17 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Arguments
     ///
-    /// * `termination_voltage` - The desired termination voltage setting
-    pub async fn set_warm_temperature_termination_voltage(
+    /// * `termination_voltage` - The desired termination voltage, a [`ChargerTerminationVoltage`]
+    ///   or [`Millivolts`](crate::units::Millivolts)
+    pub async fn set_warm_temperature_termination_voltage<V>(
         &mut self,
-        termination_voltage: ChargerTerminationVoltage,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        termination_voltage: V,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>>
+    where
+        V: TryInto<ChargerTerminationVoltage>,
+        crate::NPM1300Error<I2c::Error>: From<V::Error>,
+    {
+        let termination_voltage = termination_voltage.try_into()?;
         self.device
             .charger()
             .bchgvtermr()
@@ -449,14 +494,16 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         desired_resistance: u32,
         reference_resistance_25c: u32,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        // Calculate the 10-bit threshold
-        let threshold = roundf(
-            1024.0 * (desired_resistance as f32)
-                / (desired_resistance as f32 + reference_resistance_25c as f32),
-        );
+        // Calculate the 10-bit threshold, rounded to the nearest integer
+        let total_resistance = desired_resistance as u64 + reference_resistance_25c as u64;
+        if total_resistance == 0 {
+            return Err(crate::NPM1300Error::InvalidNtcThreshold);
+        }
+        let threshold =
+            (1024 * desired_resistance as u64 + total_resistance / 2) / total_resistance;
 
         // Ensure the threshold fits within a 10-bit range
-        if !(0.0..=1023.0).contains(&threshold) {
+        if threshold > 1023 {
             return Err(crate::NPM1300Error::InvalidNtcThreshold);
         }
 
@@ -632,8 +679,8 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             return Err(crate::NPM1300Error::InvalidDieTemperatureThreshold);
         }
 
-        // Calculate the 10-bit threshold
-        let k_die_temp = roundf((394.67 - temperature_celsius as f32) / 0.7926);
+        // Calculate the 10-bit threshold, (394.67 - T) / 0.7926 rounded to the nearest integer
+        let k_die_temp = (3_946_700 - 10_000 * temperature_celsius as u32 + 3963) / 7926;
 
         // Convert the threshold to a 10-bit unsigned integer
         let k_die_temp = k_die_temp as u16;
@@ -686,20 +733,26 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
 
     // TODO: test this behavior
     /// Get the NTC current region
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(NtcThresholdRegion))` - The battery temperature is in the returned region
+    /// * `Ok(None)` - The battery temperature is in the normal region
+    /// * `Err(NPM1300Error)` - An error occurred while reading the NTC status
     pub async fn get_ntc_current_region(
         &mut self,
-    ) -> Result<NtcThresholdRegion, crate::NPM1300Error<I2c::Error>> {
+    ) -> Result<Option<NtcThresholdRegion>, crate::NPM1300Error<I2c::Error>> {
         let status = self.device.charger().ntcstatus().read_async().await?;
         if status.ntccold() == 1 {
-            Ok(NtcThresholdRegion::Cold)
+            Ok(Some(NtcThresholdRegion::Cold))
         } else if status.ntccool() == 1 {
-            Ok(NtcThresholdRegion::Cool)
+            Ok(Some(NtcThresholdRegion::Cool))
         } else if status.ntcwarm() == 1 {
-            Ok(NtcThresholdRegion::Warm)
+            Ok(Some(NtcThresholdRegion::Warm))
         } else if status.ntchot() == 1 {
-            Ok(NtcThresholdRegion::Hot)
+            Ok(Some(NtcThresholdRegion::Hot))
         } else {
-            core::unreachable!();
+            Ok(None)
         }
     }
 
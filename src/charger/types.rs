@@ -1,3 +1,5 @@
+use crate::units::{step_index, Milliamps, Millivolts, UnitError};
+
 /// Main charger enable control set
 ///
 /// This W1S (Write-1-to-Set) register controls the primary enable/disable
@@ -370,7 +372,7 @@ impl From<DisableNtcClear> for u8 {
 }
 
 /// Battery charger termination voltages in normal temperature
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum ChargerTerminationVoltage {
     V3_50 = 0,
@@ -407,9 +409,8 @@ impl From<u8> for ChargerTerminationVoltage {
             11 => Self::V4_35,
             12 => Self::V4_40,
             13 => Self::V4_45,
-            14 => Self::V3_60,
-            15 => Self::V3_60,
-            _ => panic!("Invalid value"),
+            // Reserved values select 3.60 V
+            _ => Self::V3_60,
         }
     }
 }
@@ -421,8 +422,102 @@ impl From<ChargerTerminationVoltage> for u8 {
     }
 }
 
+impl ChargerTerminationVoltage {
+    /// Get the termination voltage matching a voltage in millivolts
+    ///
+    /// Returns `None` if the charger does not support `millivolts`.
+    pub const fn from_millivolts(millivolts: u16) -> Option<Self> {
+        match millivolts {
+            3500 => Some(Self::V3_50),
+            3550 => Some(Self::V3_55),
+            3600 => Some(Self::V3_60),
+            3650 => Some(Self::V3_65),
+            4000 => Some(Self::V4_00),
+            4050 => Some(Self::V4_05),
+            4100 => Some(Self::V4_10),
+            4150 => Some(Self::V4_15),
+            4200 => Some(Self::V4_20),
+            4250 => Some(Self::V4_25),
+            4300 => Some(Self::V4_30),
+            4350 => Some(Self::V4_35),
+            4400 => Some(Self::V4_40),
+            4450 => Some(Self::V4_45),
+            _ => None,
+        }
+    }
+
+    /// Get the termination voltage nearest to `voltage`
+    ///
+    /// Voltages halfway between two termination voltages are rounded down, to stay on the safe
+    /// side of the battery limits.
+    pub fn nearest(voltage: Millivolts) -> Self {
+        let mut nearest = Self::V3_50;
+        for candidate in TERMINATION_VOLTAGES {
+            if Millivolts::from(candidate).0.abs_diff(voltage.0)
+                < Millivolts::from(nearest).0.abs_diff(voltage.0)
+            {
+                nearest = candidate;
+            }
+        }
+        nearest
+    }
+}
+
+/// All termination voltages, sorted by increasing voltage
+const TERMINATION_VOLTAGES: [ChargerTerminationVoltage; 14] = [
+    ChargerTerminationVoltage::V3_50,
+    ChargerTerminationVoltage::V3_55,
+    ChargerTerminationVoltage::V3_60,
+    ChargerTerminationVoltage::V3_65,
+    ChargerTerminationVoltage::V4_00,
+    ChargerTerminationVoltage::V4_05,
+    ChargerTerminationVoltage::V4_10,
+    ChargerTerminationVoltage::V4_15,
+    ChargerTerminationVoltage::V4_20,
+    ChargerTerminationVoltage::V4_25,
+    ChargerTerminationVoltage::V4_30,
+    ChargerTerminationVoltage::V4_35,
+    ChargerTerminationVoltage::V4_40,
+    ChargerTerminationVoltage::V4_45,
+];
+
+// Add conversion from Millivolts, rejecting voltages between steps
+impl TryFrom<Millivolts> for ChargerTerminationVoltage {
+    type Error = UnitError;
+
+    fn try_from(voltage: Millivolts) -> Result<Self, Self::Error> {
+        match Self::from_millivolts(voltage.0) {
+            Some(termination_voltage) => Ok(termination_voltage),
+            None if (3500..=4450).contains(&voltage.0) => Err(UnitError::OutOfStep),
+            None => Err(UnitError::OutOfRange),
+        }
+    }
+}
+
+// Add conversion to Millivolts
+impl From<ChargerTerminationVoltage> for Millivolts {
+    fn from(voltage: ChargerTerminationVoltage) -> Self {
+        Millivolts(match voltage {
+            ChargerTerminationVoltage::V3_50 => 3500,
+            ChargerTerminationVoltage::V3_55 => 3550,
+            ChargerTerminationVoltage::V3_60 => 3600,
+            ChargerTerminationVoltage::V3_65 => 3650,
+            ChargerTerminationVoltage::V4_00 => 4000,
+            ChargerTerminationVoltage::V4_05 => 4050,
+            ChargerTerminationVoltage::V4_10 => 4100,
+            ChargerTerminationVoltage::V4_15 => 4150,
+            ChargerTerminationVoltage::V4_20 => 4200,
+            ChargerTerminationVoltage::V4_25 => 4250,
+            ChargerTerminationVoltage::V4_30 => 4300,
+            ChargerTerminationVoltage::V4_35 => 4350,
+            ChargerTerminationVoltage::V4_40 => 4400,
+            ChargerTerminationVoltage::V4_45 => 4450,
+        })
+    }
+}
+
 /// Battery charger trickle level select
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum ChargerTrickleLevelSelect {
     V2_9 = 0,
@@ -432,10 +527,10 @@ pub enum ChargerTrickleLevelSelect {
 // Add conversion from u8
 impl From<u8> for ChargerTrickleLevelSelect {
     fn from(value: u8) -> Self {
-        match value {
+        // Only the field bit is used
+        match value & 0x01 {
             0 => Self::V2_9,
-            1 => Self::V2_5,
-            _ => panic!("Invalid value"),
+            _ => Self::V2_5,
         }
     }
 }
@@ -449,7 +544,7 @@ impl From<ChargerTrickleLevelSelect> for u8 {
 
 /// Battery charger termination current level select
 /// Expressed as a percentage of the charging current
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum ChargerTerminationCurrentLevelSelect {
     /// 10% of charging current
@@ -467,17 +562,17 @@ impl From<ChargerTerminationCurrentLevelSelect> for u8 {
 
 impl From<u8> for ChargerTerminationCurrentLevelSelect {
     fn from(value: u8) -> Self {
-        match value {
+        // Only the field bit is used
+        match value & 0x01 {
             0 => Self::SEL10,
-            1 => Self::SEL20,
-            _ => unreachable!(),
+            _ => Self::SEL20,
         }
     }
 }
 
 /// Battery charger termination current level select
 /// Expressed as a percentage of the charging current
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum ChargerConfigDisableChargeWarm {
     /// Enable charging if battery is warm
@@ -489,10 +584,10 @@ pub enum ChargerConfigDisableChargeWarm {
 // Add conversion from u8
 impl From<u8> for ChargerConfigDisableChargeWarm {
     fn from(value: u8) -> Self {
-        match value {
+        // Only the field bit is used
+        match value & 0x01 {
             0 => ChargerConfigDisableChargeWarm::ENABLED,
-            1 => ChargerConfigDisableChargeWarm::DISABLED,
-            _ => unreachable!(),
+            _ => ChargerConfigDisableChargeWarm::DISABLED,
         }
     }
 }
@@ -504,7 +599,58 @@ impl From<ChargerConfigDisableChargeWarm> for u8 {
     }
 }
 
+/// Battery charging current, between 32 mA and 800 mA in steps of 2 mA
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct ChargeCurrent(u16);
+
+impl ChargeCurrent {
+    /// Lowest charging current
+    pub const MIN: Self = Self(32);
+
+    /// Highest charging current
+    pub const MAX: Self = Self(800);
+
+    /// Create a charging current from milliamps
+    ///
+    /// Returns `None` if `milliamps` is outside of the 32 mA to 800 mA range or odd.
+    pub const fn from_milliamps(milliamps: u16) -> Option<Self> {
+        if milliamps < Self::MIN.0 || milliamps > Self::MAX.0 || !milliamps.is_multiple_of(2) {
+            return None;
+        }
+        Some(Self(milliamps))
+    }
+
+    /// Charging current in milliamps
+    pub const fn milliamps(self) -> u16 {
+        self.0
+    }
+
+    /// Values of the BCHGISETMSB and BCHGISETLSB registers
+    pub(crate) const fn registers(self) -> (u8, u8) {
+        ((self.0 / 4) as u8, ((self.0 / 2) & 1) as u8)
+    }
+}
+
+// Add conversion from Milliamps, rejecting odd currents
+impl TryFrom<Milliamps> for ChargeCurrent {
+    type Error = UnitError;
+
+    fn try_from(current: Milliamps) -> Result<Self, Self::Error> {
+        let index = step_index(current.0 as i32, 32, 800, 2)?;
+        Ok(Self(32 + 2 * index as u16))
+    }
+}
+
+// Add conversion to Milliamps
+impl From<ChargeCurrent> for Milliamps {
+    fn from(current: ChargeCurrent) -> Self {
+        Milliamps(current.0 as i16)
+    }
+}
+
 /// Discharge current limit settings
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum DischargeCurrentLimit {
     Low,
@@ -535,6 +681,7 @@ pub enum DieTemperatureThresholdType {
     Resume,
 }
 
+#[derive(Copy, Clone, Debug)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub struct ChargerStatus {
     /// Indicates if a battery is physically connected to the system
@@ -616,6 +763,7 @@ pub struct ChargerStatus {
 /// Charger-FSM Error.
 /// Latched error reasons.
 /// Cleared with TASKS_CLEAR_CHG_ERR
+#[derive(Copy, Clone, Debug)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub struct ChargerErrorReason {
     pub ntc_sensor_error: bool,
@@ -630,6 +778,7 @@ pub struct ChargerErrorReason {
 /// Charger-FSM Error.
 /// Latched sensor values.
 /// Cleared with TASKS_CLEAR_CHG_ERR
+#[derive(Copy, Clone, Debug)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub struct ChargerSensorValueDuringError {
     pub sensor_ntc_cold: bool,
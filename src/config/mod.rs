@@ -0,0 +1,1507 @@
+#[cfg(feature = "std")]
+mod powerup;
+mod types;
+
+// Re-export everything in types.rs
+pub use types::*;
+
+#[cfg(feature = "blocking")]
+use crate::hal::prelude::*;
+use crate::{
+    buck::Buck,
+    charger::ChargeCurrent,
+    common::Task,
+    gpios::{GpioConfigBuilder, GpioPin},
+    ldsw::Ldsw,
+    units::Millivolts,
+    Buck1Autoctrlsel, Buck1Enpulldown, Buck1Mode, Buck1Pwrgood, Buck1Swctrlsel, Buck2Autoctrlsel,
+    Buck2Enpulldown, Buck2Mode, Buck2Pwrgood, Buck2Swctrlsel, Ldsw1Activedischarge, Ldsw1Pwrupldo,
+    Ldsw1Pwrupldsw, Ldsw2Activedischarge, Ldsw2Pwrupldo, Ldsw2Pwrupldsw, Longtimresetdis, Pofena,
+    Vbatautoenable, Vbatburstenable, Vbussuspendena,
+};
+
+/// Maximum value of a 10-bit threshold
+const MAX_THRESHOLD: u16 = 1023;
+
+/// Map an invalid register field value to `NPM1300Error::UnexpectedRegisterValue`
+fn unexpected<I2cError>(_: ()) -> crate::NPM1300Error<I2cError> {
+    crate::NPM1300Error::UnexpectedRegisterValue
+}
+
+/// Combine a 10-bit value from its MSB and LSB registers
+fn combine_threshold(msb: u8, lsb: u8) -> u16 {
+    (msb as u16) << 2 | (lsb & 0x03) as u16
+}
+
+/// Split a 10-bit value into its MSB and LSB registers
+fn split_threshold(value: u16) -> (u8, u8) {
+    ((value >> 2) as u8, (value & 0x03) as u8)
+}
+
+/// GPIO pins in register order
+const GPIO_PINS: [GpioPin; 5] = [
+    GpioPin::Gpio0,
+    GpioPin::Gpio1,
+    GpioPin::Gpio2,
+    GpioPin::Gpio3,
+    GpioPin::Gpio4,
+];
+
+/// List the registers differing between two configurations
+///
+/// The registers are listed in the order [`crate::NPM1300::apply_config_changes`] writes them.
+///
+/// # Arguments
+///
+/// * `current` - The configuration of the device, usually from [`crate::NPM1300::read_config`]
+/// * `desired` - The configuration to apply
+///
+/// # Returns
+///
+/// * `ConfigDiff` - The registers to write to go from `current` to `desired`
+pub fn diff(current: &PmicConfig, desired: &PmicConfig) -> ConfigDiff {
+    registers(Some(current), desired)
+}
+
+/// List the registers of a configuration in write order
+///
+/// Only the registers differing from `current` are listed, or all registers if `current` is
+/// `None`.
+fn registers(current: Option<&PmicConfig>, desired: &PmicConfig) -> ConfigDiff {
+    let all = current.is_none();
+    let (c, d) = (current.unwrap_or(desired), desired);
+    let mut registers = ConfigDiff::new();
+    let mut push = |register, changed: bool| {
+        if all || changed {
+            // The capacity is the number of registers, so this never fails
+            let _ = registers.push(register);
+        }
+    };
+
+    // VBUS input
+    push(
+        ConfigRegister::VbusInStartupCurrentLimit,
+        c.vbusin.startup_current_limit != d.vbusin.startup_current_limit,
+    );
+    push(
+        ConfigRegister::VbusInCurrentLimit,
+        c.vbusin.current_limit != d.vbusin.current_limit,
+    );
+    push(
+        ConfigRegister::VbusSuspend,
+        c.vbusin.suspended != d.vbusin.suspended,
+    );
+
+    // ADC
+    push(
+        ConfigRegister::AdcConfig,
+        c.adc.vbat_auto_measurement != d.adc.vbat_auto_measurement
+            || c.adc.vbat_burst_measurement != d.adc.vbat_burst_measurement,
+    );
+    push(
+        ConfigRegister::AdcNtcResistor,
+        c.adc.ntc_thermistor != d.adc.ntc_thermistor,
+    );
+    push(
+        ConfigRegister::AdcAutoTimer,
+        c.adc.ntc_auto_measurement_interval != d.adc.ntc_auto_measurement_interval
+            || c.adc.die_temperature_auto_measurement_interval
+                != d.adc.die_temperature_auto_measurement_interval,
+    );
+    push(
+        ConfigRegister::AdcVbatDelay,
+        c.adc.vbat_delay != d.adc.vbat_delay,
+    );
+    push(
+        ConfigRegister::AdcIbatMeasurement,
+        c.adc.ibat_measurement != d.adc.ibat_measurement,
+    );
+
+    // Battery charger
+    let (cc, dc) = (&c.charger, &d.charger);
+    push(
+        ConfigRegister::ChargerCurrent,
+        cc.charge_current != dc.charge_current,
+    );
+    push(
+        ConfigRegister::ChargerDischargeCurrent,
+        cc.discharge_current_limit != dc.discharge_current_limit,
+    );
+    push(
+        ConfigRegister::ChargerTerminationVoltage,
+        cc.termination_voltage != dc.termination_voltage,
+    );
+    push(
+        ConfigRegister::ChargerWarmTerminationVoltage,
+        cc.warm_termination_voltage != dc.warm_termination_voltage,
+    );
+    push(
+        ConfigRegister::ChargerTrickleLevel,
+        cc.trickle_level != dc.trickle_level,
+    );
+    push(
+        ConfigRegister::ChargerTerminationCurrent,
+        cc.termination_current_level != dc.termination_current_level,
+    );
+    push(
+        ConfigRegister::NtcCold,
+        cc.ntc_thresholds.cold != dc.ntc_thresholds.cold,
+    );
+    push(
+        ConfigRegister::NtcCool,
+        cc.ntc_thresholds.cool != dc.ntc_thresholds.cool,
+    );
+    push(
+        ConfigRegister::NtcWarm,
+        cc.ntc_thresholds.warm != dc.ntc_thresholds.warm,
+    );
+    push(
+        ConfigRegister::NtcHot,
+        cc.ntc_thresholds.hot != dc.ntc_thresholds.hot,
+    );
+    push(
+        ConfigRegister::DieTemperatureStop,
+        cc.die_temperature_thresholds.stop != dc.die_temperature_thresholds.stop,
+    );
+    push(
+        ConfigRegister::DieTemperatureResume,
+        cc.die_temperature_thresholds.resume != dc.die_temperature_thresholds.resume,
+    );
+    push(
+        ConfigRegister::ChargerConfig,
+        cc.charge_if_warm != dc.charge_if_warm,
+    );
+    push(
+        ConfigRegister::ChargerFullChargeInCoolTemp,
+        cc.full_charge_current_in_cool_temp != dc.full_charge_current_in_cool_temp,
+    );
+    push(ConfigRegister::ChargerRecharge, cc.recharge != dc.recharge);
+    push(
+        ConfigRegister::ChargerIgnoreNtc,
+        cc.ignore_ntc != dc.ignore_ntc,
+    );
+    push(ConfigRegister::ChargerEnable, cc.enabled != dc.enabled);
+
+    // GPIOs
+    for (pin, (cg, dg)) in GPIO_PINS.iter().zip(c.gpios.iter().zip(d.gpios.iter())) {
+        push(ConfigRegister::GpioMode(*pin), cg.mode != dg.mode);
+        push(
+            ConfigRegister::GpioDriveStrength(*pin),
+            cg.drive_strength != dg.drive_strength,
+        );
+        push(ConfigRegister::GpioPullUp(*pin), cg.pull_up != dg.pull_up);
+        push(
+            ConfigRegister::GpioPullDown(*pin),
+            cg.pull_down != dg.pull_down,
+        );
+        push(
+            ConfigRegister::GpioOpenDrain(*pin),
+            cg.open_drain != dg.open_drain,
+        );
+        push(
+            ConfigRegister::GpioDebounce(*pin),
+            cg.debounce != dg.debounce,
+        );
+    }
+
+    // BUCK regulators
+    let bucks = [
+        (Buck::Buck1, &c.buck1, &d.buck1),
+        (Buck::Buck2, &c.buck2, &d.buck2),
+    ];
+    for (buck, cb, db) in bucks {
+        push(
+            ConfigRegister::BuckNormalVoltage(buck),
+            cb.normal_voltage != db.normal_voltage,
+        );
+        push(
+            ConfigRegister::BuckRetentionVoltage(buck),
+            cb.retention_voltage != db.retention_voltage,
+        );
+    }
+    let any_buck = |changed: fn(&BuckConfig, &BuckConfig) -> bool| {
+        changed(&c.buck1, &d.buck1) || changed(&c.buck2, &d.buck2)
+    };
+    push(
+        ConfigRegister::BuckSoftwareControl,
+        any_buck(|c, d| c.software_voltage_control != d.software_voltage_control),
+    );
+    push(
+        ConfigRegister::BuckControl,
+        any_buck(|c, d| c.pfm_only != d.pfm_only || c.pull_down != d.pull_down),
+    );
+    push(
+        ConfigRegister::BuckEnableGpio,
+        any_buck(|c, d| c.enable_gpio != d.enable_gpio),
+    );
+    push(
+        ConfigRegister::BuckRetentionGpio,
+        any_buck(|c, d| c.retention_gpio != d.retention_gpio),
+    );
+    push(
+        ConfigRegister::BuckForcedPwmGpio,
+        any_buck(|c, d| c.forced_pwm_gpio != d.forced_pwm_gpio),
+    );
+    for (buck, cb, db) in bucks {
+        push(
+            ConfigRegister::BuckForcedPwm(buck),
+            cb.forced_pwm != db.forced_pwm,
+        );
+        push(ConfigRegister::BuckEnable(buck), cb.enabled != db.enabled);
+    }
+
+    // Load switches
+    let ldsws = [
+        (Ldsw::Ldsw1, &c.ldsw1, &d.ldsw1),
+        (Ldsw::Ldsw2, &c.ldsw2, &d.ldsw2),
+    ];
+    for (ldsw, cl, dl) in ldsws {
+        push(ConfigRegister::LdswMode(ldsw), cl.mode != dl.mode);
+        push(
+            ConfigRegister::LdoVoltage(ldsw),
+            cl.ldo_voltage != dl.ldo_voltage,
+        );
+    }
+    push(
+        ConfigRegister::LdswConfig,
+        ldsws.iter().any(|(_, cl, dl)| {
+            cl.soft_start_current != dl.soft_start_current
+                || cl.active_discharge != dl.active_discharge
+        }),
+    );
+    for (ldsw, cl, dl) in ldsws {
+        push(
+            ConfigRegister::LdswEnableGpio(ldsw),
+            cl.enable_gpio != dl.enable_gpio,
+        );
+        push(ConfigRegister::LdswEnable(ldsw), cl.enabled != dl.enabled);
+    }
+
+    // LEDs
+    for (index, (cl, dl)) in c.leds.iter().zip(d.leds.iter()).enumerate() {
+        push(ConfigRegister::LedMode(index), cl != dl);
+    }
+
+    // Power-failure comparator and ship configuration
+    push(ConfigRegister::Pof, c.pof != d.pof);
+    push(
+        ConfigRegister::ShipHoldConfig,
+        c.ship.press_timer != d.ship.press_timer,
+    );
+    push(
+        ConfigRegister::LongPressResetConfig,
+        c.ship.long_press_reset != d.ship.long_press_reset
+            || c.ship.long_press_reset_buttons != d.ship.long_press_reset_buttons,
+    );
+
+    registers
+}
+
+#[maybe_async::maybe_async]
+impl<I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> crate::NPM1300<I2c, Delay> {
+    /// Read the full configuration of the nPM1300
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PmicConfig)` - The current configuration
+    /// * `Err(NPM1300Error::UnexpectedRegisterValue)` - A register holds a reserved value
+    /// * `Err(NPM1300Error)` - An error occurred while reading the registers
+    pub async fn read_config(&mut self) -> Result<PmicConfig, crate::NPM1300Error<I2c::Error>> {
+        let (buck1, buck2) = self.read_buck_config().await?;
+        let (ldsw1, ldsw2) = self.read_ldsw_config().await?;
+
+        let mut gpios = [GpioConfigBuilder::new().build(); 5];
+        for (pin, gpio) in GPIO_PINS.into_iter().zip(gpios.iter_mut()) {
+            *gpio = self.read_gpio_config(pin).await?;
+        }
+
+        let mut leds = PmicConfig::default().leds;
+        for (index, led) in leds.iter_mut().enumerate() {
+            *led = self
+                .device
+                .leddrv()
+                .leddrvmodesel(index)
+                .read_async()
+                .await?
+                .leddrvmodesel()
+                .map_err(unexpected)?;
+        }
+
+        Ok(PmicConfig {
+            vbusin: self.read_vbusin_config().await?,
+            charger: self.read_charger_config().await?,
+            buck1,
+            buck2,
+            ldsw1,
+            ldsw2,
+            gpios,
+            leds,
+            pof: self.read_pof_config().await?,
+            ship: self.read_ship_config().await?,
+            adc: self.read_adc_config().await?,
+        })
+    }
+
+    /// Apply a full configuration to the nPM1300
+    ///
+    /// The configuration is checked before anything is written. It is then written in a safe
+    /// order: VBUS input and ADC first, then the charger with charging disabled while it is
+    /// reconfigured, the GPIOs before the regulators they control, the regulators and finally
+    /// the LEDs, the power-failure comparator and the ship configuration.
+    ///
+    /// Every register is written, use [`Self::apply_config_changes`] to only write the
+    /// registers that changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration to apply, see [`PmicConfig`]
+    ///
+    /// # Safety
+    ///
+    /// Disabling or changing the voltage of the regulator supplying the host is NOT checked by
+    /// the driver and must be avoided by the caller.
+    ///
+    /// # Errors
+    ///
+    /// * `NPM1300Error::InvalidNtcThreshold` - An NTC threshold does not fit in 10 bits
+    /// * `NPM1300Error::InvalidDieTemperatureThreshold` - A die temperature threshold does not
+    ///   fit in 10 bits
+    /// * `NPM1300Error::InvalidPofVsysThreshold` - Power-failure detection is enabled with a
+    ///   threshold higher than the current VSYS voltage
+    pub async fn apply_config(
+        &mut self,
+        config: &PmicConfig,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.apply_registers(config, &registers(None, config)).await
+    }
+
+    /// Apply a configuration, only writing the registers that differ from the current one
+    ///
+    /// The registers are written in the same order as [`Self::apply_config`]. Charging is only
+    /// disabled if a charger register changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `current` - The configuration of the device, usually from [`Self::read_config`]
+    /// * `desired` - The configuration to apply
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ConfigDiff)` - The registers that were written, see [`diff`]
+    /// * `Err(NPM1300Error)` - The configuration is invalid or an error occurred while writing,
+    ///   see [`Self::apply_config`]
+    pub async fn apply_config_changes(
+        &mut self,
+        current: &PmicConfig,
+        desired: &PmicConfig,
+    ) -> Result<ConfigDiff, crate::NPM1300Error<I2c::Error>> {
+        let registers = diff(current, desired);
+        self.apply_registers(desired, &registers).await?;
+        Ok(registers)
+    }
+
+    /// Read the configuration of the nPM1300 and only write the registers that differ
+    ///
+    /// # Arguments
+    ///
+    /// * `desired` - The configuration to apply
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ConfigDiff)` - The registers that were written, see [`diff`]
+    /// * `Err(NPM1300Error)` - An error occurred, see [`Self::read_config`] and
+    ///   [`Self::apply_config`]
+    pub async fn update_config(
+        &mut self,
+        desired: &PmicConfig,
+    ) -> Result<ConfigDiff, crate::NPM1300Error<I2c::Error>> {
+        let current = self.read_config().await?;
+        self.apply_config_changes(&current, desired).await
+    }
+
+    /// Write the listed registers of a configuration
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration to apply
+    /// * `registers` - The registers to write
+    async fn apply_registers(
+        &mut self,
+        config: &PmicConfig,
+        registers: &[ConfigRegister],
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.check_config(config, registers).await?;
+
+        self.apply_vbusin_config(&config.vbusin, registers).await?;
+        self.apply_adc_config(&config.adc, registers).await?;
+        self.apply_charger_config(&config.charger, registers)
+            .await?;
+        self.apply_gpio_config(&config.gpios, registers).await?;
+        self.apply_buck_config(&config.buck1, &config.buck2, registers)
+            .await?;
+        self.apply_ldsw_config(&config.ldsw1, &config.ldsw2, registers)
+            .await?;
+        for (index, mode) in config.leds.iter().enumerate() {
+            if registers.contains(&ConfigRegister::LedMode(index)) {
+                self.device
+                    .leddrv()
+                    .leddrvmodesel(index)
+                    .write_async(|reg| reg.set_leddrvmodesel(*mode))
+                    .await?;
+            }
+        }
+        if registers.contains(&ConfigRegister::Pof) {
+            self.apply_pof_config(&config.pof).await?;
+        }
+        self.apply_ship_config(&config.ship, registers).await
+    }
+
+    /// Check that a configuration can be applied
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration to check
+    /// * `registers` - The registers that will be written
+    async fn check_config(
+        &mut self,
+        config: &PmicConfig,
+        registers: &[ConfigRegister],
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        let charger = &config.charger;
+
+        let ntc = &charger.ntc_thresholds;
+        if [ntc.cold, ntc.cool, ntc.warm, ntc.hot]
+            .iter()
+            .any(|&threshold| threshold > MAX_THRESHOLD)
+        {
+            return Err(crate::NPM1300Error::InvalidNtcThreshold);
+        }
+
+        let die = &charger.die_temperature_thresholds;
+        if die.stop > MAX_THRESHOLD || die.resume > MAX_THRESHOLD {
+            return Err(crate::NPM1300Error::InvalidDieTemperatureThreshold);
+        }
+
+        // Same check as `set_vsys_threshold`, a threshold above VSYS resets the device
+        if config.pof.enabled && registers.contains(&ConfigRegister::Pof) {
+            let vsys = self.measure_vsys_millivolts().await?;
+            if vsys < Millivolts::from(config.pof.vsys_threshold) {
+                return Err(crate::NPM1300Error::InvalidPofVsysThreshold);
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the VBUS input configuration
+    async fn read_vbusin_config(
+        &mut self,
+    ) -> Result<VbusInConfig, crate::NPM1300Error<I2c::Error>> {
+        Ok(VbusInConfig {
+            current_limit: self
+                .device
+                .vbusin()
+                .vbusinilim_0()
+                .read_async()
+                .await?
+                .vbusinilim_0(),
+            startup_current_limit: self
+                .device
+                .vbusin()
+                .vbusinilimstartup()
+                .read_async()
+                .await?
+                .vbusinilimstartup(),
+            suspended: self
+                .device
+                .vbusin()
+                .vbussuspend()
+                .read_async()
+                .await?
+                .vbussuspendena()
+                == Vbussuspendena::Suspend,
+        })
+    }
+
+    /// Apply the VBUS input configuration
+    async fn apply_vbusin_config(
+        &mut self,
+        config: &VbusInConfig,
+        registers: &[ConfigRegister],
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        if registers.contains(&ConfigRegister::VbusInStartupCurrentLimit) {
+            self.set_vbus_in_startup_current_limit(config.startup_current_limit)
+                .await?;
+        }
+        if registers.contains(&ConfigRegister::VbusInCurrentLimit) {
+            self.set_vbus_in_current_limit(config.current_limit).await?;
+        }
+        if registers.contains(&ConfigRegister::VbusSuspend) {
+            self.set_vbus_mode(config.suspended).await?;
+        }
+        Ok(())
+    }
+
+    /// Read the battery charger configuration
+    async fn read_charger_config(
+        &mut self,
+    ) -> Result<ChargerConfig, crate::NPM1300Error<I2c::Error>> {
+        use crate::charger::NtcThresholdRegion;
+
+        let die_temperature_stop = combine_threshold(
+            self.device
+                .charger()
+                .dietempstop()
+                .read_async()
+                .await?
+                .dietempstopchg(),
+            self.device
+                .charger()
+                .dietempstoplsb()
+                .read_async()
+                .await?
+                .dietempstopchglsb(),
+        );
+        let die_temperature_resume = combine_threshold(
+            self.device
+                .charger()
+                .dietempresume()
+                .read_async()
+                .await?
+                .dietempresumechg(),
+            self.device
+                .charger()
+                .dietempresumelsb()
+                .read_async()
+                .await?
+                .dietempresumechglsb(),
+        );
+
+        Ok(ChargerConfig {
+            enabled: self.is_charging_enabled().await?,
+            charge_current: ChargeCurrent::from_milliamps(self.get_charger_config_current().await?)
+                .ok_or(crate::NPM1300Error::UnexpectedRegisterValue)?,
+            discharge_current_limit: self.get_discharge_current_limit().await?,
+            termination_voltage: self
+                .device
+                .charger()
+                .bchgvterm()
+                .read_async()
+                .await?
+                .bchgvtermnorm(),
+            warm_termination_voltage: self
+                .device
+                .charger()
+                .bchgvtermr()
+                .read_async()
+                .await?
+                .bchgvtermreduced(),
+            trickle_level: self
+                .device
+                .charger()
+                .bchgvtricklesel()
+                .read_async()
+                .await?
+                .bchgvtricklesel(),
+            termination_current_level: self
+                .device
+                .charger()
+                .bchgitermsel()
+                .read_async()
+                .await?
+                .bchgitermsel(),
+            full_charge_current_in_cool_temp: self
+                .is_full_charge_current_in_cool_temp_enabled()
+                .await?,
+            recharge: self.is_battery_recharge_enabled().await?,
+            ignore_ntc: self.is_ntc_measurement_ignored().await?,
+            charge_if_warm: self
+                .device
+                .charger()
+                .bchgconfig()
+                .read_async()
+                .await?
+                .disablechargewarm()
+                == crate::charger::ChargerConfigDisableChargeWarm::ENABLED,
+            ntc_thresholds: NtcThresholds {
+                cold: self.get_ntc_threshold(NtcThresholdRegion::Cold).await?,
+                cool: self.get_ntc_threshold(NtcThresholdRegion::Cool).await?,
+                warm: self.get_ntc_threshold(NtcThresholdRegion::Warm).await?,
+                hot: self.get_ntc_threshold(NtcThresholdRegion::Hot).await?,
+            },
+            die_temperature_thresholds: DieTemperatureThresholds {
+                stop: die_temperature_stop,
+                resume: die_temperature_resume,
+            },
+        })
+    }
+
+    /// Apply the battery charger configuration
+    ///
+    /// Charging is disabled while the charger is reconfigured, and enabled again at the end if
+    /// the configuration enables it.
+    async fn apply_charger_config(
+        &mut self,
+        config: &ChargerConfig,
+        registers: &[ConfigRegister],
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        let reconfigure = registers.iter().any(|register| {
+            matches!(
+                register,
+                ConfigRegister::ChargerCurrent
+                    | ConfigRegister::ChargerDischargeCurrent
+                    | ConfigRegister::ChargerTerminationVoltage
+                    | ConfigRegister::ChargerWarmTerminationVoltage
+                    | ConfigRegister::ChargerTrickleLevel
+                    | ConfigRegister::ChargerTerminationCurrent
+                    | ConfigRegister::NtcCold
+                    | ConfigRegister::NtcCool
+                    | ConfigRegister::NtcWarm
+                    | ConfigRegister::NtcHot
+                    | ConfigRegister::DieTemperatureStop
+                    | ConfigRegister::DieTemperatureResume
+                    | ConfigRegister::ChargerConfig
+                    | ConfigRegister::ChargerFullChargeInCoolTemp
+                    | ConfigRegister::ChargerRecharge
+                    | ConfigRegister::ChargerIgnoreNtc
+            )
+        });
+        if reconfigure {
+            self.disable_battery_charging().await?;
+        }
+
+        if registers.contains(&ConfigRegister::ChargerCurrent) {
+            self.set_charger_current(config.charge_current).await?;
+        }
+        if registers.contains(&ConfigRegister::ChargerDischargeCurrent) {
+            self.set_discharge_current_limit(config.discharge_current_limit)
+                .await?;
+        }
+        if registers.contains(&ConfigRegister::ChargerTerminationVoltage) {
+            self.set_normal_temperature_termination_voltage(config.termination_voltage)
+                .await?;
+        }
+        if registers.contains(&ConfigRegister::ChargerWarmTerminationVoltage) {
+            self.set_warm_temperature_termination_voltage(config.warm_termination_voltage)
+                .await?;
+        }
+        if registers.contains(&ConfigRegister::ChargerTrickleLevel) {
+            self.set_trickle_level(config.trickle_level).await?;
+        }
+        if registers.contains(&ConfigRegister::ChargerTerminationCurrent) {
+            self.set_termination_current_level(config.termination_current_level)
+                .await?;
+        }
+
+        // NTC thresholds
+        let ntc = &config.ntc_thresholds;
+        if registers.contains(&ConfigRegister::NtcCold) {
+            let (msb, lsb) = split_threshold(ntc.cold);
+            self.device
+                .charger()
+                .ntccold()
+                .write_async(|reg| reg.set_ntccoldlvlmsb(msb))
+                .await?;
+            self.device
+                .charger()
+                .ntccoldlsb()
+                .write_async(|reg| reg.set_ntccoldlvllsb(lsb))
+                .await?;
+        }
+        if registers.contains(&ConfigRegister::NtcCool) {
+            let (msb, lsb) = split_threshold(ntc.cool);
+            self.device
+                .charger()
+                .ntccool()
+                .write_async(|reg| reg.set_ntccoollvlmsb(msb))
+                .await?;
+            self.device
+                .charger()
+                .ntccoollsb()
+                .write_async(|reg| reg.set_ntccoollvllsb(lsb))
+                .await?;
+        }
+        if registers.contains(&ConfigRegister::NtcWarm) {
+            let (msb, lsb) = split_threshold(ntc.warm);
+            self.device
+                .charger()
+                .ntcwarm()
+                .write_async(|reg| reg.set_ntcwarmlvlmsb(msb))
+                .await?;
+            self.device
+                .charger()
+                .ntcwarmlsb()
+                .write_async(|reg| reg.set_ntcwarmlvllsb(lsb))
+                .await?;
+        }
+        if registers.contains(&ConfigRegister::NtcHot) {
+            let (msb, lsb) = split_threshold(ntc.hot);
+            self.device
+                .charger()
+                .ntchot()
+                .write_async(|reg| reg.set_ntchotlvlmsb(msb))
+                .await?;
+            self.device
+                .charger()
+                .ntchotlsb()
+                .write_async(|reg| reg.set_ntchotlvllsb(lsb))
+                .await?;
+        }
+
+        // Die temperature thresholds
+        let die = &config.die_temperature_thresholds;
+        if registers.contains(&ConfigRegister::DieTemperatureStop) {
+            let (msb, lsb) = split_threshold(die.stop);
+            self.device
+                .charger()
+                .dietempstop()
+                .write_async(|reg| reg.set_dietempstopchg(msb))
+                .await?;
+            self.device
+                .charger()
+                .dietempstoplsb()
+                .write_async(|reg| reg.set_dietempstopchglsb(lsb))
+                .await?;
+        }
+        if registers.contains(&ConfigRegister::DieTemperatureResume) {
+            let (msb, lsb) = split_threshold(die.resume);
+            self.device
+                .charger()
+                .dietempresume()
+                .write_async(|reg| reg.set_dietempresumechg(msb))
+                .await?;
+            self.device
+                .charger()
+                .dietempresumelsb()
+                .write_async(|reg| reg.set_dietempresumechglsb(lsb))
+                .await?;
+        }
+
+        if registers.contains(&ConfigRegister::ChargerConfig) {
+            self.set_charge_if_battery_warm(config.charge_if_warm)
+                .await?;
+        }
+        if registers.contains(&ConfigRegister::ChargerFullChargeInCoolTemp) {
+            if config.full_charge_current_in_cool_temp {
+                self.enable_battery_charger_full_charge_in_cool_temp()
+                    .await?;
+            } else {
+                self.disable_battery_charger_full_charge_in_cool_temp()
+                    .await?;
+            }
+        }
+        if registers.contains(&ConfigRegister::ChargerRecharge) {
+            if config.recharge {
+                self.enable_battery_recharge().await?;
+            } else {
+                self.disable_battery_recharge().await?;
+            }
+        }
+        if registers.contains(&ConfigRegister::ChargerIgnoreNtc) {
+            if config.ignore_ntc {
+                self.ignore_ntc_measurements().await?;
+            } else {
+                self.use_ntc_measurements().await?;
+            }
+        }
+
+        // Only enable charging once the charger is fully configured
+        if reconfigure || registers.contains(&ConfigRegister::ChargerEnable) {
+            if config.enabled {
+                self.enable_battery_charging().await?;
+            } else if !reconfigure {
+                self.disable_battery_charging().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the configuration of the GPIOs
+    async fn apply_gpio_config(
+        &mut self,
+        configs: &[crate::gpios::GpioConfig; 5],
+        registers: &[ConfigRegister],
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        for (pin, config) in GPIO_PINS.into_iter().zip(configs.iter()) {
+            let index = pin.index();
+            if registers.contains(&ConfigRegister::GpioMode(pin)) {
+                self.device
+                    .gpios()
+                    .gpiomode(index)
+                    .write_async(|reg| reg.set_gpiomode(config.mode))
+                    .await?;
+            }
+            if registers.contains(&ConfigRegister::GpioDriveStrength(pin)) {
+                self.device
+                    .gpios()
+                    .gpiodrive(index)
+                    .write_async(|reg| reg.set_gpiodrive(config.drive_strength))
+                    .await?;
+            }
+            if registers.contains(&ConfigRegister::GpioPullUp(pin)) {
+                self.device
+                    .gpios()
+                    .gpiopuen(index)
+                    .write_async(|reg| reg.set_gpiopuen(config.pull_up))
+                    .await?;
+            }
+            if registers.contains(&ConfigRegister::GpioPullDown(pin)) {
+                self.device
+                    .gpios()
+                    .gpiopden(index)
+                    .write_async(|reg| reg.set_gpiopden(config.pull_down))
+                    .await?;
+            }
+            if registers.contains(&ConfigRegister::GpioOpenDrain(pin)) {
+                self.device
+                    .gpios()
+                    .gpioopendrain(index)
+                    .write_async(|reg| reg.set_gpioopendrain(config.open_drain))
+                    .await?;
+            }
+            if registers.contains(&ConfigRegister::GpioDebounce(pin)) {
+                self.device
+                    .gpios()
+                    .gpiodebounce(index)
+                    .write_async(|reg| reg.set_gpiodebounce(config.debounce))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the configuration of both BUCK regulators
+    async fn read_buck_config(
+        &mut self,
+    ) -> Result<(BuckConfig, BuckConfig), crate::NPM1300Error<I2c::Error>> {
+        let status = self.device.buck().buckstatus().read_async().await?;
+        let enctrl = self.device.buck().buckenctrl().read_async().await?;
+        let vretctrl = self.device.buck().buckvretctrl().read_async().await?;
+        let pwmctrl = self.device.buck().buckpwmctrl().read_async().await?;
+        let swctrlsel = self.device.buck().buckswctrlsel().read_async().await?;
+        let ctrl0 = self.device.buck().buckctrl_0().read_async().await?;
+
+        let buck1 = BuckConfig {
+            enabled: status.buck_1_pwrgood() == Buck1Pwrgood::Buckpowered,
+            normal_voltage: self
+                .device
+                .buck()
+                .buck_1_normvout()
+                .read_async()
+                .await?
+                .value()
+                .map_err(unexpected)?,
+            retention_voltage: self
+                .device
+                .buck()
+                .buck_1_retvout()
+                .read_async()
+                .await?
+                .value()
+                .map_err(unexpected)?,
+            software_voltage_control: swctrlsel.buck_1_swctrlsel() == Buck1Swctrlsel::Swctrl,
+            forced_pwm: status.buck_1_mode() == Buck1Mode::Pwmmode,
+            pfm_only: ctrl0.buck_1_autoctrlsel() == Buck1Autoctrlsel::Pfm,
+            pull_down: ctrl0.buck_1_enpulldown() == Buck1Enpulldown::High,
+            enable_gpio: GpioControl {
+                gpio: enctrl.buck_1_engpisel().map_err(unexpected)?,
+                polarity: enctrl.buck_1_engpiinv().map_err(unexpected)?,
+            },
+            retention_gpio: GpioControl {
+                gpio: vretctrl.buck_1_vretgpisel().map_err(unexpected)?,
+                polarity: vretctrl.buck_1_vretgpiinv().map_err(unexpected)?,
+            },
+            forced_pwm_gpio: GpioControl {
+                gpio: pwmctrl.buck_1_pwmgpisel().map_err(unexpected)?,
+                polarity: pwmctrl.buck_1_pwmgpiinv().map_err(unexpected)?,
+            },
+        };
+
+        let buck2 = BuckConfig {
+            enabled: status.buck_2_pwrgood() == Buck2Pwrgood::Buckpowered,
+            normal_voltage: self
+                .device
+                .buck()
+                .buck_2_normvout()
+                .read_async()
+                .await?
+                .value()
+                .map_err(unexpected)?,
+            retention_voltage: self
+                .device
+                .buck()
+                .buck_2_retvout()
+                .read_async()
+                .await?
+                .value()
+                .map_err(unexpected)?,
+            software_voltage_control: swctrlsel.buck_2_swctrlsel() == Buck2Swctrlsel::Swctrl,
+            forced_pwm: status.buck_2_mode() == Buck2Mode::Pwmmode,
+            pfm_only: ctrl0.buck_2_autoctrlsel() == Buck2Autoctrlsel::Pfm,
+            pull_down: ctrl0.buck_2_enpulldown() == Buck2Enpulldown::High,
+            enable_gpio: GpioControl {
+                gpio: enctrl.buck_2_engpisel().map_err(unexpected)?,
+                polarity: enctrl.buck_2_engpiinv().map_err(unexpected)?,
+            },
+            retention_gpio: GpioControl {
+                gpio: vretctrl.buck_2_vretgpisel().map_err(unexpected)?,
+                polarity: vretctrl.buck_2_vretgpiinv().map_err(unexpected)?,
+            },
+            forced_pwm_gpio: GpioControl {
+                gpio: pwmctrl.buck_2_pwmgpisel().map_err(unexpected)?,
+                polarity: pwmctrl.buck_2_pwmgpiinv().map_err(unexpected)?,
+            },
+        };
+
+        Ok((buck1, buck2))
+    }
+
+    /// Apply the configuration of both BUCK regulators
+    ///
+    /// The output voltages and controls are written before the regulators are enabled.
+    async fn apply_buck_config(
+        &mut self,
+        buck1: &BuckConfig,
+        buck2: &BuckConfig,
+        registers: &[ConfigRegister],
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        // Output voltages
+        if registers.contains(&ConfigRegister::BuckNormalVoltage(Buck::Buck1)) {
+            self.device
+                .buck()
+                .buck_1_normvout()
+                .write_async(|reg| reg.set_value(buck1.normal_voltage))
+                .await?;
+        }
+        if registers.contains(&ConfigRegister::BuckRetentionVoltage(Buck::Buck1)) {
+            self.device
+                .buck()
+                .buck_1_retvout()
+                .write_async(|reg| reg.set_value(buck1.retention_voltage))
+                .await?;
+        }
+        if registers.contains(&ConfigRegister::BuckNormalVoltage(Buck::Buck2)) {
+            self.device
+                .buck()
+                .buck_2_normvout()
+                .write_async(|reg| reg.set_value(buck2.normal_voltage))
+                .await?;
+        }
+        if registers.contains(&ConfigRegister::BuckRetentionVoltage(Buck::Buck2)) {
+            self.device
+                .buck()
+                .buck_2_retvout()
+                .write_async(|reg| reg.set_value(buck2.retention_voltage))
+                .await?;
+        }
+        if registers.contains(&ConfigRegister::BuckSoftwareControl) {
+            self.device
+                .buck()
+                .buckswctrlsel()
+                .write_async(|reg| {
+                    reg.set_buck_1_swctrlsel(if buck1.software_voltage_control {
+                        Buck1Swctrlsel::Swctrl
+                    } else {
+                        Buck1Swctrlsel::Vsetandswctrl
+                    });
+                    reg.set_buck_2_swctrlsel(if buck2.software_voltage_control {
+                        Buck2Swctrlsel::Swctrl
+                    } else {
+                        Buck2Swctrlsel::Vsetandswctrl
+                    });
+                })
+                .await?;
+        }
+
+        // Operating modes and pull-downs
+        if registers.contains(&ConfigRegister::BuckControl) {
+            self.device
+                .buck()
+                .buckctrl_0()
+                .write_async(|reg| {
+                    reg.set_buck_1_autoctrlsel(if buck1.pfm_only {
+                        Buck1Autoctrlsel::Pfm
+                    } else {
+                        Buck1Autoctrlsel::Auto
+                    });
+                    reg.set_buck_2_autoctrlsel(if buck2.pfm_only {
+                        Buck2Autoctrlsel::Pfm
+                    } else {
+                        Buck2Autoctrlsel::Auto
+                    });
+                    reg.set_buck_1_enpulldown(if buck1.pull_down {
+                        Buck1Enpulldown::High
+                    } else {
+                        Buck1Enpulldown::Low
+                    });
+                    reg.set_buck_2_enpulldown(if buck2.pull_down {
+                        Buck2Enpulldown::High
+                    } else {
+                        Buck2Enpulldown::Low
+                    });
+                })
+                .await?;
+        }
+
+        // GPIO controls
+        if registers.contains(&ConfigRegister::BuckEnableGpio) {
+            self.device
+                .buck()
+                .buckenctrl()
+                .write_async(|reg| {
+                    reg.set_buck_1_engpisel(buck1.enable_gpio.gpio);
+                    reg.set_buck_1_engpiinv(buck1.enable_gpio.polarity);
+                    reg.set_buck_2_engpisel(buck2.enable_gpio.gpio);
+                    reg.set_buck_2_engpiinv(buck2.enable_gpio.polarity);
+                })
+                .await?;
+        }
+        if registers.contains(&ConfigRegister::BuckRetentionGpio) {
+            self.device
+                .buck()
+                .buckvretctrl()
+                .write_async(|reg| {
+                    reg.set_buck_1_vretgpisel(buck1.retention_gpio.gpio);
+                    reg.set_buck_1_vretgpiinv(buck1.retention_gpio.polarity);
+                    reg.set_buck_2_vretgpisel(buck2.retention_gpio.gpio);
+                    reg.set_buck_2_vretgpiinv(buck2.retention_gpio.polarity);
+                })
+                .await?;
+        }
+        if registers.contains(&ConfigRegister::BuckForcedPwmGpio) {
+            self.device
+                .buck()
+                .buckpwmctrl()
+                .write_async(|reg| {
+                    reg.set_buck_1_pwmgpisel(buck1.forced_pwm_gpio.gpio);
+                    reg.set_buck_1_pwmgpiinv(buck1.forced_pwm_gpio.polarity);
+                    reg.set_buck_2_pwmgpisel(buck2.forced_pwm_gpio.gpio);
+                    reg.set_buck_2_pwmgpiinv(buck2.forced_pwm_gpio.polarity);
+                })
+                .await?;
+        }
+
+        // Forced PWM mode and enable
+        for (buck, config) in [(Buck::Buck1, buck1), (Buck::Buck2, buck2)] {
+            if registers.contains(&ConfigRegister::BuckForcedPwm(buck)) {
+                if config.forced_pwm {
+                    self.device
+                        .buck()
+                        .buckpwmset(buck.index())
+                        .dispatch_async(|command| command.set_taskbuckpwmset(Task::Trigger))
+                        .await?;
+                } else {
+                    self.device
+                        .buck()
+                        .buckpwmclr(buck.index())
+                        .dispatch_async(|command| command.set_taskbuckpwmclr(Task::Trigger))
+                        .await?;
+                }
+            }
+            if registers.contains(&ConfigRegister::BuckEnable(buck)) {
+                if config.enabled {
+                    self.device
+                        .buck()
+                        .buckenaset(buck.index())
+                        .dispatch_async(|command| command.set_taskbuckenaset(Task::Trigger))
+                        .await?;
+                } else {
+                    self.device
+                        .buck()
+                        .buckenaclr(buck.index())
+                        .dispatch_async(|command| command.set_taskbuckenaclr(Task::Trigger))
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the configuration of both load switches
+    async fn read_ldsw_config(
+        &mut self,
+    ) -> Result<(LdswConfig, LdswConfig), crate::NPM1300Error<I2c::Error>> {
+        let status = self.device.ldsw().ldswstatus().read_async().await?;
+        let config = self.device.ldsw().ldswconfig().read_async().await?;
+        let gpisel1 = self.device.ldsw().ldsw_1_gpisel().read_async().await?;
+        let gpisel2 = self.device.ldsw().ldsw_2_gpisel().read_async().await?;
+
+        let ldsw1 = LdswConfig {
+            enabled: status.ldsw_1_pwrupldsw() == Ldsw1Pwrupldsw::Ldswpowered
+                || status.ldsw_1_pwrupldo() == Ldsw1Pwrupldo::Ldopowered,
+            mode: self
+                .device
+                .ldsw()
+                .ldsw_1_ldosel()
+                .read_async()
+                .await?
+                .value()
+                .map_err(unexpected)?,
+            ldo_voltage: self
+                .device
+                .ldsw()
+                .ldsw_1_voutsel()
+                .read_async()
+                .await?
+                .value()
+                .map_err(unexpected)?,
+            soft_start_current: config.ldsw_1_softstartsel().map_err(unexpected)?,
+            active_discharge: config.ldsw_1_activedischarge() == Ldsw1Activedischarge::Enabled,
+            enable_gpio: GpioControl {
+                gpio: gpisel1.ldsw_1_gpisel().map_err(unexpected)?,
+                polarity: gpisel1.ldsw_1_gpiinv().map_err(unexpected)?,
+            },
+        };
+
+        let ldsw2 = LdswConfig {
+            enabled: status.ldsw_2_pwrupldsw() == Ldsw2Pwrupldsw::Ldswpowered
+                || status.ldsw_2_pwrupldo() == Ldsw2Pwrupldo::Ldopowered,
+            mode: self
+                .device
+                .ldsw()
+                .ldsw_2_ldosel()
+                .read_async()
+                .await?
+                .value()
+                .map_err(unexpected)?,
+            ldo_voltage: self
+                .device
+                .ldsw()
+                .ldsw_2_voutsel()
+                .read_async()
+                .await?
+                .value()
+                .map_err(unexpected)?,
+            soft_start_current: config.ldsw_2_softstartsel().map_err(unexpected)?,
+            active_discharge: config.ldsw_2_activedischarge() == Ldsw2Activedischarge::Enabled,
+            enable_gpio: GpioControl {
+                gpio: gpisel2.ldsw_2_gpisel().map_err(unexpected)?,
+                polarity: gpisel2.ldsw_2_gpiinv().map_err(unexpected)?,
+            },
+        };
+
+        Ok((ldsw1, ldsw2))
+    }
+
+    /// Apply the configuration of both load switches
+    ///
+    /// The modes, voltages and controls are written before the load switches are enabled.
+    async fn apply_ldsw_config(
+        &mut self,
+        ldsw1: &LdswConfig,
+        ldsw2: &LdswConfig,
+        registers: &[ConfigRegister],
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        if registers.contains(&ConfigRegister::LdswMode(Ldsw::Ldsw1)) {
+            self.set_ldsw1_mode(ldsw1.mode).await?;
+        }
+        if registers.contains(&ConfigRegister::LdswMode(Ldsw::Ldsw2)) {
+            self.set_ldsw2_mode(ldsw2.mode).await?;
+        }
+        if registers.contains(&ConfigRegister::LdoVoltage(Ldsw::Ldsw1)) {
+            self.set_ldo1_voltage(ldsw1.ldo_voltage).await?;
+        }
+        if registers.contains(&ConfigRegister::LdoVoltage(Ldsw::Ldsw2)) {
+            self.set_ldo2_voltage(ldsw2.ldo_voltage).await?;
+        }
+        if registers.contains(&ConfigRegister::LdswConfig) {
+            self.device
+                .ldsw()
+                .ldswconfig()
+                .write_async(|reg| {
+                    reg.set_ldsw_1_softstartsel(ldsw1.soft_start_current);
+                    reg.set_ldsw_2_softstartsel(ldsw2.soft_start_current);
+                    reg.set_ldsw_1_activedischarge(if ldsw1.active_discharge {
+                        Ldsw1Activedischarge::Enabled
+                    } else {
+                        Ldsw1Activedischarge::Disabled
+                    });
+                    reg.set_ldsw_2_activedischarge(if ldsw2.active_discharge {
+                        Ldsw2Activedischarge::Enabled
+                    } else {
+                        Ldsw2Activedischarge::Disabled
+                    });
+                })
+                .await?;
+        }
+        if registers.contains(&ConfigRegister::LdswEnableGpio(Ldsw::Ldsw1)) {
+            self.device
+                .ldsw()
+                .ldsw_1_gpisel()
+                .write_async(|reg| {
+                    reg.set_ldsw_1_gpisel(ldsw1.enable_gpio.gpio);
+                    reg.set_ldsw_1_gpiinv(ldsw1.enable_gpio.polarity);
+                })
+                .await?;
+        }
+        if registers.contains(&ConfigRegister::LdswEnableGpio(Ldsw::Ldsw2)) {
+            self.device
+                .ldsw()
+                .ldsw_2_gpisel()
+                .write_async(|reg| {
+                    reg.set_ldsw_2_gpisel(ldsw2.enable_gpio.gpio);
+                    reg.set_ldsw_2_gpiinv(ldsw2.enable_gpio.polarity);
+                })
+                .await?;
+        }
+
+        for (ldsw, config) in [(Ldsw::Ldsw1, ldsw1), (Ldsw::Ldsw2, ldsw2)] {
+            if !registers.contains(&ConfigRegister::LdswEnable(ldsw)) {
+                continue;
+            }
+            if config.enabled {
+                self.device
+                    .ldsw()
+                    .ldswset(ldsw.index())
+                    .dispatch_async(|command| command.set_taskldswset(Task::Trigger))
+                    .await?;
+            } else {
+                self.device
+                    .ldsw()
+                    .ldswclr(ldsw.index())
+                    .dispatch_async(|command| command.set_taskldswclr(Task::Trigger))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the configuration of a GPIO
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The GPIO pin, see [`GpioPin`]
+    async fn read_gpio_config(
+        &mut self,
+        pin: GpioPin,
+    ) -> Result<crate::gpios::GpioConfig, crate::NPM1300Error<I2c::Error>> {
+        let index = pin.index();
+        Ok(GpioConfigBuilder::new()
+            .mode(
+                self.device
+                    .gpios()
+                    .gpiomode(index)
+                    .read_async()
+                    .await?
+                    .gpiomode()
+                    .map_err(unexpected)?,
+            )
+            .drive_strength(
+                self.device
+                    .gpios()
+                    .gpiodrive(index)
+                    .read_async()
+                    .await?
+                    .gpiodrive()
+                    .map_err(unexpected)?,
+            )
+            .pull_up(
+                self.device
+                    .gpios()
+                    .gpiopuen(index)
+                    .read_async()
+                    .await?
+                    .gpiopuen()
+                    .map_err(unexpected)?,
+            )
+            .pull_down(
+                self.device
+                    .gpios()
+                    .gpiopden(index)
+                    .read_async()
+                    .await?
+                    .gpiopden()
+                    .map_err(unexpected)?,
+            )
+            .open_drain(
+                self.device
+                    .gpios()
+                    .gpioopendrain(index)
+                    .read_async()
+                    .await?
+                    .gpioopendrain()
+                    .map_err(unexpected)?,
+            )
+            .debounce(
+                self.device
+                    .gpios()
+                    .gpiodebounce(index)
+                    .read_async()
+                    .await?
+                    .gpiodebounce()
+                    .map_err(unexpected)?,
+            )
+            .build())
+    }
+
+    /// Read the power-failure comparator configuration
+    async fn read_pof_config(&mut self) -> Result<PofConfig, crate::NPM1300Error<I2c::Error>> {
+        let pofconfig = self.device.pof().pofconfig().read_async().await?;
+        Ok(PofConfig {
+            enabled: pofconfig.pofena() == Pofena::Enabled,
+            warning_polarity: pofconfig.pofwarnpolarity(),
+            vsys_threshold: pofconfig.pofvsysthreshsel(),
+        })
+    }
+
+    /// Apply the power-failure comparator configuration
+    ///
+    /// The threshold must already be checked against VSYS, see `check_config`.
+    async fn apply_pof_config(
+        &mut self,
+        config: &PofConfig,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.device
+            .pof()
+            .pofconfig()
+            .write_async(|reg| {
+                reg.set_pofena(if config.enabled {
+                    Pofena::Enabled
+                } else {
+                    Pofena::Off
+                });
+                reg.set_pofwarnpolarity(config.warning_polarity);
+                reg.set_pofvsysthreshsel(config.vsys_threshold);
+            })
+            .await
+    }
+
+    /// Read the ship and hibernate modes configuration
+    async fn read_ship_config(&mut self) -> Result<ShipConfig, crate::NPM1300Error<I2c::Error>> {
+        let lpresetconfig = self.device.ship().lpresetconfig().read_async().await?;
+        Ok(ShipConfig {
+            press_timer: self
+                .device
+                .ship()
+                .shphldconfig()
+                .read_async()
+                .await?
+                .shphldtim(),
+            long_press_reset: lpresetconfig.longtimresetdis() == Longtimresetdis::Enabled,
+            long_press_reset_buttons: lpresetconfig.longtimtwobuttonsel(),
+        })
+    }
+
+    /// Apply the ship and hibernate modes configuration
+    async fn apply_ship_config(
+        &mut self,
+        config: &ShipConfig,
+        registers: &[ConfigRegister],
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        let press_timer = registers.contains(&ConfigRegister::ShipHoldConfig);
+        let long_press_reset = registers.contains(&ConfigRegister::LongPressResetConfig);
+        if press_timer {
+            self.device
+                .ship()
+                .shphldconfig()
+                .write_async(|reg| reg.set_shphldtim(config.press_timer))
+                .await?;
+        }
+        if long_press_reset {
+            self.device
+                .ship()
+                .lpresetconfig()
+                .write_async(|reg| {
+                    reg.set_longtimresetdis(if config.long_press_reset {
+                        Longtimresetdis::Enabled
+                    } else {
+                        Longtimresetdis::Disabled
+                    });
+                    reg.set_longtimtwobuttonsel(config.long_press_reset_buttons);
+                })
+                .await?;
+        }
+
+        // Load the new configuration
+        if press_timer || long_press_reset {
+            self.device
+                .ship()
+                .taskshphldcfgstrobe()
+                .dispatch_async(|command| command.set_taskshphldconfigstrobe(Task::Trigger))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Read the ADC configuration
+    async fn read_adc_config(&mut self) -> Result<AdcConfig, crate::NPM1300Error<I2c::Error>> {
+        let adcconfig = self.device.adc().adcconfig().read_async().await?;
+        let autotimconf = self.device.adc().adcautotimconf().read_async().await?;
+        Ok(AdcConfig {
+            vbat_auto_measurement: adcconfig.vbatautoenable() == Vbatautoenable::Autoenable,
+            vbat_burst_measurement: adcconfig.vbatburstenable() == Vbatburstenable::Burstmode,
+            ntc_thermistor: self.get_ntc_resistance_configuration().await?,
+            ntc_beta: self.ntc_beta,
+            ntc_auto_measurement_interval: autotimconf.ntcautotim(),
+            die_temperature_auto_measurement_interval: autotimconf.tempautotim(),
+            vbat_delay: self.get_vbat_delay_configuration().await?,
+            ibat_measurement: self.get_ibat_measurement_configuration().await?,
+        })
+    }
+
+    /// Apply the ADC configuration
+    async fn apply_adc_config(
+        &mut self,
+        config: &AdcConfig,
+        registers: &[ConfigRegister],
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        if registers.contains(&ConfigRegister::AdcConfig) {
+            self.device
+                .adc()
+                .adcconfig()
+                .write_async(|reg| {
+                    reg.set_vbatautoenable(if config.vbat_auto_measurement {
+                        Vbatautoenable::Autoenable
+                    } else {
+                        Vbatautoenable::Noauto
+                    });
+                    reg.set_vbatburstenable(if config.vbat_burst_measurement {
+                        Vbatburstenable::Burstmode
+                    } else {
+                        Vbatburstenable::Singlemode
+                    });
+                })
+                .await?;
+        }
+        if registers.contains(&ConfigRegister::AdcNtcResistor) {
+            self.device
+                .adc()
+                .adcntcrsel()
+                .write_async(|reg| reg.set_adcntcrsel(config.ntc_thermistor))
+                .await?;
+        }
+        // Not stored in the nPM1300, so updating it costs no write
+        if config.ntc_beta.is_some() {
+            self.ntc_beta = config.ntc_beta;
+        }
+
+        if registers.contains(&ConfigRegister::AdcAutoTimer) {
+            self.device
+                .adc()
+                .adcautotimconf()
+                .write_async(|reg| {
+                    reg.set_ntcautotim(config.ntc_auto_measurement_interval);
+                    reg.set_tempautotim(config.die_temperature_auto_measurement_interval);
+                })
+                .await?;
+            // Update toggle register
+            self.device
+                .adc()
+                .taskautotimupdate()
+                .dispatch_async(|command| command.set_taskautotimupdate(Task::Trigger))
+                .await?;
+        }
+
+        if registers.contains(&ConfigRegister::AdcVbatDelay) {
+            self.device
+                .adc()
+                .adcdeltimconf()
+                .write_async(|reg| reg.set_vbatdeltim(config.vbat_delay))
+                .await?;
+        }
+        if registers.contains(&ConfigRegister::AdcIbatMeasurement) {
+            self.configure_ibat_measurement(config.ibat_measurement)
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,689 @@
+use std::{fmt::Write, string::String};
+
+use serde_json::Value;
+
+use super::{
+    AdcConfig, BuckConfig, ChargerConfig, GpioControl, LdswConfig, PmicConfig, PowerUpError,
+};
+use crate::{
+    charger::{
+        ChargeCurrent, ChargerTerminationCurrentLevelSelect, ChargerTrickleLevelSelect,
+        DischargeCurrentLimit,
+    },
+    gpios::{
+        Gpio, GpioConfigBuilder, GpioDebounce, GpioDriveStrength, GpioMode, GpioOpenDrain,
+        GpioPullDown, GpioPullUp,
+    },
+    ldsw::{LdswMode, LdswSoftStartCurrent},
+    leds::LedMode,
+    units::{Milliamps, Millivolts},
+    Longtimtwobuttonsel, NtcThermistorType, Pofwarnpolarity, Shphldtim,
+};
+
+/// Read an optional number
+fn number(value: &Value, key: &'static str) -> Result<Option<f64>, PowerUpError> {
+    match value.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(field) => field
+            .as_f64()
+            .filter(|number| number.is_finite())
+            .map(Some)
+            .ok_or(PowerUpError::InvalidValue(key)),
+    }
+}
+
+/// Read an optional boolean
+fn boolean(value: &Value, key: &'static str) -> Result<Option<bool>, PowerUpError> {
+    match value.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(field) => field
+            .as_bool()
+            .map(Some)
+            .ok_or(PowerUpError::InvalidValue(key)),
+    }
+}
+
+/// Read an optional string
+fn string<'a>(value: &'a Value, key: &'static str) -> Result<Option<&'a str>, PowerUpError> {
+    match value.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(field) => field
+            .as_str()
+            .map(Some)
+            .ok_or(PowerUpError::InvalidValue(key)),
+    }
+}
+
+/// Read an optional array, empty if missing
+fn array<'a>(value: &'a Value, key: &'static str) -> Result<&'a [Value], PowerUpError> {
+    match value.get(key) {
+        None | Some(Value::Null) => Ok(&[]),
+        Some(field) => field
+            .as_array()
+            .map(Vec::as_slice)
+            .ok_or(PowerUpError::InvalidValue(key)),
+    }
+}
+
+/// Read an optional voltage in volts and convert it to a register setting
+fn voltage<T: TryFrom<Millivolts>>(
+    value: &Value,
+    key: &'static str,
+) -> Result<Option<T>, PowerUpError> {
+    number(value, key)?
+        .map(|volts| {
+            let millivolts = (volts * 1000.0).round();
+            if !(0.0..=u16::MAX as f64).contains(&millivolts) {
+                return Err(PowerUpError::InvalidValue(key));
+            }
+            T::try_from(Millivolts(millivolts as u16)).map_err(|_| PowerUpError::InvalidValue(key))
+        })
+        .transpose()
+}
+
+/// Read an optional whole number in a range, a fraction is invalid
+fn integer(
+    value: &Value,
+    key: &'static str,
+    range: core::ops::RangeInclusive<f64>,
+) -> Result<Option<f64>, PowerUpError> {
+    number(value, key)?
+        .map(|number| {
+            if range.contains(&number) && number.fract() == 0.0 {
+                Ok(number)
+            } else {
+                Err(PowerUpError::InvalidValue(key))
+            }
+        })
+        .transpose()
+}
+
+/// Parse a GPIO selection, `Off` for no GPIO
+fn gpio(value: &Value, key: &'static str) -> Result<Option<Gpio>, PowerUpError> {
+    string(value, key)?
+        .map(|name| match name.to_ascii_uppercase().as_str() {
+            "OFF" | "NONE" => Ok(Gpio::None),
+            "GPIO0" => Ok(Gpio::Gpio0),
+            "GPIO1" => Ok(Gpio::Gpio1),
+            "GPIO2" => Ok(Gpio::Gpio2),
+            "GPIO3" => Ok(Gpio::Gpio3),
+            "GPIO4" => Ok(Gpio::Gpio4),
+            _ => Err(PowerUpError::InvalidValue(key)),
+        })
+        .transpose()
+}
+
+/// Raw 10-bit NTC threshold of a temperature, see [`crate::NPM1300::set_ntc_threshold`]
+///
+/// # Arguments
+///
+/// * `celsius` - The threshold temperature in degrees Celsius
+/// * `beta` - The beta value of the NTC thermistor in kelvin
+fn ntc_threshold(celsius: f64, beta: f64) -> u16 {
+    // R / R25 = exp(beta * (1 / T - 1 / T25)), threshold = 1024 * R / (R + R25)
+    let ratio = (beta * (1.0 / (celsius + 273.15) - 1.0 / 298.15)).exp();
+    (1024.0 * ratio / (ratio + 1.0)).round().clamp(0.0, 1023.0) as u16
+}
+
+/// Raw 10-bit die temperature threshold, see
+/// [`crate::NPM1300::set_die_temperature_threshold`]
+///
+/// # Arguments
+///
+/// * `celsius` - The threshold temperature in degrees Celsius
+fn die_temperature_threshold(celsius: f64) -> u16 {
+    ((394.67 - celsius) / 0.7926).round().clamp(0.0, 1023.0) as u16
+}
+
+/// Apply the `charger` object of an export
+fn parse_charger(
+    value: &Value,
+    charger: &mut ChargerConfig,
+    adc: &mut AdcConfig,
+) -> Result<(), PowerUpError> {
+    if let Some(enabled) = boolean(value, "enabled")? {
+        charger.enabled = enabled;
+    }
+    if let Some(current) = integer(value, "iChg", 32.0..=800.0)? {
+        // Steps of 2 mA
+        charger.charge_current = ChargeCurrent::from_milliamps(current as u16)
+            .ok_or(PowerUpError::InvalidValue("iChg"))?;
+    }
+    if let Some(limit) = integer(value, "iBatLim", 0.0..=u16::MAX as f64)? {
+        // The export uses the nominal limit in mA, around 200 mA for the low setting and
+        // 1000 mA or more for the high setting
+        charger.discharge_current_limit = if limit < 500.0 {
+            DischargeCurrentLimit::Low
+        } else {
+            DischargeCurrentLimit::High
+        };
+    }
+    if let Some(voltage) = voltage(value, "vTerm")? {
+        charger.termination_voltage = voltage;
+    }
+    if let Some(voltage) = voltage(value, "vTermR")? {
+        charger.warm_termination_voltage = voltage;
+    }
+    if let Some(volts) = number(value, "vTrickleFast")? {
+        charger.trickle_level = match (volts * 10.0).round() as i32 {
+            25 => ChargerTrickleLevelSelect::V2_5,
+            29 => ChargerTrickleLevelSelect::V2_9,
+            _ => return Err(PowerUpError::InvalidValue("vTrickleFast")),
+        };
+    }
+    if let Some(level) = string(value, "iTerm")? {
+        charger.termination_current_level = match level.trim_end_matches('%').trim() {
+            "10" => ChargerTerminationCurrentLevelSelect::SEL10,
+            "20" => ChargerTerminationCurrentLevelSelect::SEL20,
+            _ => return Err(PowerUpError::InvalidValue("iTerm")),
+        };
+    }
+    if let Some(recharge) = boolean(value, "enableRecharging")? {
+        charger.recharge = recharge;
+    }
+
+    if let Some(thermistor) = string(value, "ntcThermistor")? {
+        adc.ntc_thermistor = match thermistor
+            .chars()
+            .filter(char::is_ascii_digit)
+            .collect::<String>()
+            .as_str()
+        {
+            "" => NtcThermistorType::None,
+            "10" => NtcThermistorType::Ntc10K,
+            "47" => NtcThermistorType::Ntc47K,
+            "100" => NtcThermistorType::Ntc100K,
+            _ => return Err(PowerUpError::InvalidValue("ntcThermistor")),
+        };
+    }
+    if let Some(beta) = integer(value, "ntcBeta", 1.0..=u16::MAX as f64)? {
+        adc.ntc_beta = Some(beta as u16);
+
+        // The NTC thresholds are exported as temperatures, so they need the beta value
+        let thresholds = &mut charger.ntc_thresholds;
+        for (key, threshold) in [
+            ("tCold", &mut thresholds.cold),
+            ("tCool", &mut thresholds.cool),
+            ("tWarm", &mut thresholds.warm),
+            ("tHot", &mut thresholds.hot),
+        ] {
+            if let Some(celsius) = integer(value, key, -40.0..=125.0)? {
+                *threshold = ntc_threshold(celsius, beta);
+            }
+        }
+    }
+
+    let thresholds = &mut charger.die_temperature_thresholds;
+    if let Some(celsius) = integer(value, "tChgStop", 50.0..=110.0)? {
+        thresholds.stop = die_temperature_threshold(celsius);
+    }
+    if let Some(celsius) = integer(value, "tChgResume", 50.0..=110.0)? {
+        thresholds.resume = die_temperature_threshold(celsius);
+    }
+    Ok(())
+}
+
+/// Apply an element of the `bucks` array of an export
+fn parse_buck(value: &Value, buck: &mut BuckConfig) -> Result<(), PowerUpError> {
+    if let Some(enabled) = boolean(value, "enabled")? {
+        buck.enabled = enabled;
+    }
+    if let Some(voltage) = voltage(value, "vOutNormal")? {
+        buck.normal_voltage = voltage;
+    }
+    if let Some(voltage) = voltage(value, "vOutRetention")? {
+        buck.retention_voltage = voltage;
+    }
+    if let Some(mode) = string(value, "mode")? {
+        buck.software_voltage_control = match mode {
+            "software" => true,
+            "vSet" => false,
+            _ => return Err(PowerUpError::InvalidValue("mode")),
+        };
+    }
+    if let Some(control) = string(value, "modeControl")? {
+        buck.forced_pwm_gpio = GpioControl::default();
+        (buck.forced_pwm, buck.pfm_only) = match control.to_ascii_uppercase().as_str() {
+            "AUTO" => (false, false),
+            "PWM" => (true, false),
+            "PFM" => (false, true),
+            _ => {
+                buck.forced_pwm_gpio.gpio = gpio(value, "modeControl")?.unwrap_or(Gpio::None);
+                (false, false)
+            }
+        };
+    }
+    if let Some(gpio) = gpio(value, "onOffControl")? {
+        buck.enable_gpio = GpioControl {
+            gpio,
+            ..GpioControl::default()
+        };
+    }
+    if let Some(gpio) = gpio(value, "retentionControl")? {
+        buck.retention_gpio = GpioControl {
+            gpio,
+            ..GpioControl::default()
+        };
+    }
+    if let Some(active_discharge) = boolean(value, "activeDischarge")? {
+        buck.pull_down = active_discharge;
+    }
+    Ok(())
+}
+
+/// Apply an element of the `ldos` array of an export
+fn parse_ldsw(value: &Value, ldsw: &mut LdswConfig) -> Result<(), PowerUpError> {
+    if let Some(enabled) = boolean(value, "enabled")? {
+        ldsw.enabled = enabled;
+    }
+    if let Some(mode) = string(value, "mode")? {
+        ldsw.mode = match mode {
+            "LDO" => LdswMode::Ldo,
+            "ldoSwitch" | "load_switch" => LdswMode::LoadSwitch,
+            _ => return Err(PowerUpError::InvalidValue("mode")),
+        };
+    }
+    if let Some(voltage) = voltage(value, "voltage")? {
+        ldsw.ldo_voltage = voltage;
+    }
+    if let Some(current) = integer(value, "softStart", 0.0..=u8::MAX as f64)? {
+        ldsw.soft_start_current = match current as u8 {
+            10 => LdswSoftStartCurrent::MA10,
+            20 => LdswSoftStartCurrent::MA20,
+            35 => LdswSoftStartCurrent::MA35,
+            50 => LdswSoftStartCurrent::MA50,
+            _ => return Err(PowerUpError::InvalidValue("softStart")),
+        };
+    }
+    if let Some(active_discharge) = boolean(value, "activeDischarge")? {
+        ldsw.active_discharge = active_discharge;
+    }
+    if let Some(gpio) = gpio(value, "onOffControl")? {
+        ldsw.enable_gpio = GpioControl {
+            gpio,
+            ..GpioControl::default()
+        };
+    }
+    Ok(())
+}
+
+/// Parse an element of the `gpios` array of an export
+fn parse_gpio(
+    value: &Value,
+    gpio: crate::gpios::GpioConfig,
+) -> Result<crate::gpios::GpioConfig, PowerUpError> {
+    let mut builder = GpioConfigBuilder::new()
+        .mode(gpio.mode)
+        .drive_strength(gpio.drive_strength)
+        .pull_up(gpio.pull_up)
+        .pull_down(gpio.pull_down)
+        .open_drain(gpio.open_drain)
+        .debounce(gpio.debounce);
+
+    if let Some(mode) = string(value, "mode")? {
+        builder = builder.mode(match mode {
+            "Input" => GpioMode::GpiInput,
+            "Input logic 1" => GpioMode::GpiLogic1,
+            "Input logic 0" => GpioMode::GpiLogic0,
+            "Input rising edge event" => GpioMode::GpiEventRise,
+            "Input falling edge event" => GpioMode::GpiEventFall,
+            "Output interrupt" => GpioMode::GpoIrq,
+            "Output reset" => GpioMode::GpoReset,
+            "Output power loss warning" => GpioMode::GpoPowerLossWarning,
+            "Output logic 1" => GpioMode::GpoLogic1,
+            "Output logic 0" => GpioMode::GpoLogic0,
+            _ => return Err(PowerUpError::InvalidValue("mode")),
+        });
+    }
+    if let Some(pull) = string(value, "pull")? {
+        let (pull_up, pull_down) = match pull.to_ascii_lowercase().as_str() {
+            "pull up" => (GpioPullUp::Enable, GpioPullDown::Disable),
+            "pull down" => (GpioPullUp::Disable, GpioPullDown::Enable),
+            "pull disable" | "pull disabled" => (GpioPullUp::Disable, GpioPullDown::Disable),
+            _ => return Err(PowerUpError::InvalidValue("pull")),
+        };
+        builder = builder.pull_up(pull_up).pull_down(pull_down);
+    }
+    if let Some(drive) = integer(value, "drive", 0.0..=u8::MAX as f64)? {
+        builder = builder.drive_strength(match drive as u8 {
+            1 => GpioDriveStrength::Drive1mA,
+            6 => GpioDriveStrength::Drive6mA,
+            _ => return Err(PowerUpError::InvalidValue("drive")),
+        });
+    }
+    if let Some(open_drain) = boolean(value, "openDrain")? {
+        builder = builder.open_drain(if open_drain {
+            GpioOpenDrain::Enable
+        } else {
+            GpioOpenDrain::Disable
+        });
+    }
+    if let Some(debounce) = boolean(value, "debounce")? {
+        builder = builder.debounce(if debounce {
+            GpioDebounce::Enable
+        } else {
+            GpioDebounce::Disable
+        });
+    }
+    Ok(builder.build())
+}
+
+impl PmicConfig {
+    /// Import a configuration exported by the nPM PowerUP desktop application
+    ///
+    /// Settings missing from the export keep their reset value, settings the driver does not
+    /// configure (boost, timer, fuel gauge) are ignored. The NTC thresholds are only imported
+    /// together with the NTC beta value, as the export stores them as temperatures.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - The exported JSON
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PmicConfig)` - The imported configuration
+    /// * `Err(PowerUpError)` - The JSON is malformed, has no `deviceType` or one other than the
+    ///   nPM1300, or has a setting the nPM1300 does not support
+    pub fn from_powerup_json(json: &str) -> Result<Self, PowerUpError> {
+        let export: Value = serde_json::from_str(json).map_err(|_| PowerUpError::InvalidJson)?;
+        if !export.is_object() {
+            return Err(PowerUpError::InvalidJson);
+        }
+        let device =
+            string(&export, "deviceType")?.ok_or(PowerUpError::InvalidValue("deviceType"))?;
+        if !device.eq_ignore_ascii_case("npm1300") {
+            return Err(PowerUpError::UnsupportedDevice);
+        }
+
+        let mut config = PmicConfig::default();
+
+        if let Some(usb_power) = export.get("usbPower") {
+            if let Some(amps) = number(usb_power, "currentLimiter")? {
+                let limit = Milliamps((amps * 1000.0).round().clamp(0.0, i16::MAX as f64) as i16)
+                    .try_into()
+                    .map_err(|_| PowerUpError::InvalidValue("currentLimiter"))?;
+                config.vbusin.current_limit = limit;
+            }
+        }
+
+        if let Some(charger) = export.get("charger") {
+            parse_charger(charger, &mut config.charger, &mut config.adc)?;
+        }
+
+        for (value, buck) in array(&export, "bucks")?
+            .iter()
+            .zip([&mut config.buck1, &mut config.buck2])
+        {
+            parse_buck(value, buck)?;
+        }
+
+        for (value, ldsw) in array(&export, "ldos")?
+            .iter()
+            .zip([&mut config.ldsw1, &mut config.ldsw2])
+        {
+            parse_ldsw(value, ldsw)?;
+        }
+
+        for (value, gpio) in array(&export, "gpios")?.iter().zip(config.gpios.iter_mut()) {
+            *gpio = parse_gpio(value, *gpio)?;
+        }
+
+        for (value, led) in array(&export, "leds")?.iter().zip(config.leds.iter_mut()) {
+            if let Some(mode) = string(value, "mode")? {
+                *led = match mode.to_ascii_lowercase().as_str() {
+                    "charger error" => LedMode::ChargingError,
+                    "charging" => LedMode::Charging,
+                    "host" => LedMode::Host,
+                    "not used" => LedMode::NotUsed,
+                    _ => return Err(PowerUpError::InvalidValue("mode")),
+                };
+            }
+        }
+
+        if let Some(pof) = export.get("pof") {
+            if let Some(enabled) = boolean(pof, "enable")? {
+                config.pof.enabled = enabled;
+            }
+            if let Some(threshold) = voltage(pof, "threshold")? {
+                config.pof.vsys_threshold = threshold;
+            }
+            if let Some(polarity) = string(pof, "polarity")? {
+                config.pof.warning_polarity = match polarity.to_ascii_lowercase().as_str() {
+                    "active low" => Pofwarnpolarity::Low,
+                    "active high" => Pofwarnpolarity::High,
+                    _ => return Err(PowerUpError::InvalidValue("polarity")),
+                };
+            }
+        }
+
+        if let Some(ship) = export.get("ship") {
+            if let Some(time) = integer(ship, "timeToActive", 0.0..=u16::MAX as f64)? {
+                config.ship.press_timer = match time as u16 {
+                    16 => Shphldtim::Ms16,
+                    32 => Shphldtim::Ms32,
+                    64 => Shphldtim::Ms64,
+                    96 => Shphldtim::Ms96,
+                    304 => Shphldtim::Ms304,
+                    608 => Shphldtim::Ms608,
+                    1008 => Shphldtim::Ms1008,
+                    3008 => Shphldtim::Ms3008,
+                    _ => return Err(PowerUpError::InvalidValue("timeToActive")),
+                };
+            }
+            if let Some(reset) = string(ship, "longPressReset")? {
+                let (enabled, buttons) = match reset {
+                    "disabled" => (false, Longtimtwobuttonsel::Shphld),
+                    "one_button" => (true, Longtimtwobuttonsel::Shphld),
+                    "two_button" => (true, Longtimtwobuttonsel::Shphldgpio0),
+                    _ => return Err(PowerUpError::InvalidValue("longPressReset")),
+                };
+                config.ship.long_press_reset = enabled;
+                config.ship.long_press_reset_buttons = buttons;
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Generate the Rust source of a `const` holding this configuration
+    ///
+    /// Meant for build scripts, so firmware can include a configuration imported with
+    /// [`Self::from_powerup_json`] without parsing it at runtime:
+    ///
+    /// ```ignore
+    /// // build.rs
+    /// let json = std::fs::read_to_string("pmic.json").unwrap();
+    /// let config = npm1300_rs::config::PmicConfig::from_powerup_json(&json).unwrap();
+    /// let out = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    /// std::fs::write(out.join("pmic.rs"), config.to_rust_const("PMIC_CONFIG")).unwrap();
+    ///
+    /// // main.rs
+    /// include!(concat!(env!("OUT_DIR"), "/pmic.rs"));
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the constant
+    pub fn to_rust_const(&self, name: &str) -> String {
+        let mut out = String::new();
+        // Writing to a String never fails
+        let _ = self.write_rust_const(&mut out, name);
+        out
+    }
+
+    /// Write the Rust source of a `const` holding this configuration
+    fn write_rust_const(&self, out: &mut String, name: &str) -> core::fmt::Result {
+        const CRATE: &str = "npm1300_rs";
+
+        let gpio_control = |control: &GpioControl| {
+            format!(
+                "{CRATE}::config::GpioControl {{ gpio: {CRATE}::gpios::Gpio::{:?}, polarity: {CRATE}::gpios::GpioPolarity::{:?} }}",
+                control.gpio, control.polarity
+            )
+        };
+        let buck = |buck: &BuckConfig| {
+            format!(
+                "{CRATE}::config::BuckConfig {{
+        enabled: {},
+        normal_voltage: {CRATE}::buck::BuckVoltage::{:?},
+        retention_voltage: {CRATE}::buck::BuckVoltage::{:?},
+        software_voltage_control: {},
+        forced_pwm: {},
+        pfm_only: {},
+        pull_down: {},
+        enable_gpio: {},
+        retention_gpio: {},
+        forced_pwm_gpio: {},
+    }}",
+                buck.enabled,
+                buck.normal_voltage,
+                buck.retention_voltage,
+                buck.software_voltage_control,
+                buck.forced_pwm,
+                buck.pfm_only,
+                buck.pull_down,
+                gpio_control(&buck.enable_gpio),
+                gpio_control(&buck.retention_gpio),
+                gpio_control(&buck.forced_pwm_gpio),
+            )
+        };
+        let ldsw = |ldsw: &LdswConfig| {
+            format!(
+                "{CRATE}::config::LdswConfig {{
+        enabled: {},
+        mode: {CRATE}::ldsw::LdswMode::{:?},
+        ldo_voltage: {CRATE}::ldsw::LdswVoltage::{:?},
+        soft_start_current: {CRATE}::ldsw::LdswSoftStartCurrent::{:?},
+        active_discharge: {},
+        enable_gpio: {},
+    }}",
+                ldsw.enabled,
+                ldsw.mode,
+                ldsw.ldo_voltage,
+                ldsw.soft_start_current,
+                ldsw.active_discharge,
+                gpio_control(&ldsw.enable_gpio),
+            )
+        };
+
+        let (vbusin, charger, adc) = (&self.vbusin, &self.charger, &self.adc);
+        writeln!(
+            out,
+            "pub const {name}: {CRATE}::config::PmicConfig = {CRATE}::config::PmicConfig {{"
+        )?;
+        writeln!(
+            out,
+            "    vbusin: {CRATE}::config::VbusInConfig {{
+        current_limit: {CRATE}::sysreg::VbusInCurrentLimit::{:?},
+        startup_current_limit: {CRATE}::sysreg::VbusInCurrentLimit::{:?},
+        suspended: {},
+    }},",
+            vbusin.current_limit, vbusin.startup_current_limit, vbusin.suspended
+        )?;
+        writeln!(
+            out,
+            "    charger: {CRATE}::config::ChargerConfig {{
+        enabled: {},
+        charge_current: {CRATE}::charger::ChargeCurrent::from_milliamps({}).unwrap(),
+        discharge_current_limit: {CRATE}::charger::DischargeCurrentLimit::{:?},
+        termination_voltage: {CRATE}::charger::ChargerTerminationVoltage::{:?},
+        warm_termination_voltage: {CRATE}::charger::ChargerTerminationVoltage::{:?},
+        trickle_level: {CRATE}::charger::ChargerTrickleLevelSelect::{:?},
+        termination_current_level: {CRATE}::charger::ChargerTerminationCurrentLevelSelect::{:?},
+        full_charge_current_in_cool_temp: {},
+        recharge: {},
+        ignore_ntc: {},
+        charge_if_warm: {},
+        ntc_thresholds: {CRATE}::config::NtcThresholds {{ cold: {}, cool: {}, warm: {}, hot: {} }},
+        die_temperature_thresholds: {CRATE}::config::DieTemperatureThresholds {{ stop: {}, resume: {} }},
+    }},",
+            charger.enabled,
+            charger.charge_current.milliamps(),
+            charger.discharge_current_limit,
+            charger.termination_voltage,
+            charger.warm_termination_voltage,
+            charger.trickle_level,
+            charger.termination_current_level,
+            charger.full_charge_current_in_cool_temp,
+            charger.recharge,
+            charger.ignore_ntc,
+            charger.charge_if_warm,
+            charger.ntc_thresholds.cold,
+            charger.ntc_thresholds.cool,
+            charger.ntc_thresholds.warm,
+            charger.ntc_thresholds.hot,
+            charger.die_temperature_thresholds.stop,
+            charger.die_temperature_thresholds.resume,
+        )?;
+        writeln!(out, "    buck1: {},", buck(&self.buck1))?;
+        writeln!(out, "    buck2: {},", buck(&self.buck2))?;
+        writeln!(out, "    ldsw1: {},", ldsw(&self.ldsw1))?;
+        writeln!(out, "    ldsw2: {},", ldsw(&self.ldsw2))?;
+        writeln!(out, "    gpios: [")?;
+        for gpio in &self.gpios {
+            writeln!(
+                out,
+                "        {CRATE}::gpios::GpioConfigBuilder::new()
+            .mode({CRATE}::gpios::GpioMode::{:?})
+            .drive_strength({CRATE}::gpios::GpioDriveStrength::{:?})
+            .pull_up({CRATE}::gpios::GpioPullUp::{:?})
+            .pull_down({CRATE}::gpios::GpioPullDown::{:?})
+            .open_drain({CRATE}::gpios::GpioOpenDrain::{:?})
+            .debounce({CRATE}::gpios::GpioDebounce::{:?})
+            .build(),",
+                gpio.mode,
+                gpio.drive_strength,
+                gpio.pull_up,
+                gpio.pull_down,
+                gpio.open_drain,
+                gpio.debounce,
+            )?;
+        }
+        writeln!(out, "    ],")?;
+        write!(out, "    leds: [")?;
+        for led in &self.leds {
+            write!(out, "{CRATE}::leds::LedMode::{led:?}, ")?;
+        }
+        writeln!(out, "],")?;
+        writeln!(
+            out,
+            "    pof: {CRATE}::config::PofConfig {{
+        enabled: {},
+        warning_polarity: {CRATE}::Pofwarnpolarity::{:?},
+        vsys_threshold: {CRATE}::VsysThreshold::{:?},
+    }},",
+            self.pof.enabled, self.pof.warning_polarity, self.pof.vsys_threshold
+        )?;
+        writeln!(
+            out,
+            "    ship: {CRATE}::config::ShipConfig {{
+        press_timer: {CRATE}::Shphldtim::{:?},
+        long_press_reset: {},
+        long_press_reset_buttons: {CRATE}::Longtimtwobuttonsel::{:?},
+    }},",
+            self.ship.press_timer, self.ship.long_press_reset, self.ship.long_press_reset_buttons
+        )?;
+        writeln!(
+            out,
+            "    adc: {CRATE}::config::AdcConfig {{
+        vbat_auto_measurement: {},
+        vbat_burst_measurement: {},
+        ntc_thermistor: {CRATE}::NtcThermistorType::{:?},
+        ntc_beta: {:?},
+        ntc_auto_measurement_interval: {CRATE}::Ntcautotim::{:?},
+        die_temperature_auto_measurement_interval: {CRATE}::Tempautotim::{:?},
+        vbat_delay: {},
+        ibat_measurement: {},
+    }},",
+            adc.vbat_auto_measurement,
+            adc.vbat_burst_measurement,
+            adc.ntc_thermistor,
+            adc.ntc_beta,
+            adc.ntc_auto_measurement_interval,
+            adc.die_temperature_auto_measurement_interval,
+            adc.vbat_delay,
+            adc.ibat_measurement,
+        )?;
+        writeln!(out, "}};")
+    }
+}
@@ -0,0 +1,494 @@
+use crate::{
+    buck::{Buck, BuckVoltage},
+    charger::{
+        ChargeCurrent, ChargerTerminationCurrentLevelSelect, ChargerTerminationVoltage,
+        ChargerTrickleLevelSelect, DischargeCurrentLimit,
+    },
+    gpios::{Gpio, GpioConfig, GpioPin, GpioPolarity},
+    ldsw::{Ldsw, LdswMode, LdswSoftStartCurrent, LdswVoltage},
+    leds::LedMode,
+    sysreg::VbusInCurrentLimit,
+    Longtimtwobuttonsel, NtcThermistorType, Ntcautotim, Pofwarnpolarity, Shphldtim, Tempautotim,
+    VsysThreshold,
+};
+
+/// Full configuration of the nPM1300
+///
+/// Read it from the device with [`crate::NPM1300::read_config`] and write it with
+/// [`crate::NPM1300::apply_config`]. The default is the reset value of every register.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct PmicConfig {
+    /// VBUS input
+    pub vbusin: VbusInConfig,
+    /// Battery charger
+    pub charger: ChargerConfig,
+    /// BUCK1 regulator
+    pub buck1: BuckConfig,
+    /// BUCK2 regulator
+    pub buck2: BuckConfig,
+    /// LDSW1 load switch
+    pub ldsw1: LdswConfig,
+    /// LDSW2 load switch
+    pub ldsw2: LdswConfig,
+    /// GPIO0 to GPIO4
+    pub gpios: [GpioConfig; 5],
+    /// LED0 to LED2 modes
+    pub leds: [LedMode; 3],
+    /// Power-failure comparator
+    pub pof: PofConfig,
+    /// Ship and hibernate modes
+    pub ship: ShipConfig,
+    /// ADC
+    pub adc: AdcConfig,
+}
+
+impl Default for PmicConfig {
+    fn default() -> Self {
+        Self {
+            vbusin: VbusInConfig::default(),
+            charger: ChargerConfig::default(),
+            buck1: BuckConfig::reset(BuckVoltage::V1_2),
+            buck2: BuckConfig::reset(BuckVoltage::V1_8),
+            ldsw1: LdswConfig::default(),
+            ldsw2: LdswConfig::default(),
+            gpios: [GpioConfig::default(); 5],
+            leds: [LedMode::ChargingError; 3],
+            pof: PofConfig::default(),
+            ship: ShipConfig::default(),
+            adc: AdcConfig::default(),
+        }
+    }
+}
+
+/// GPIO controlling a regulator or a regulator mode
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct GpioControl {
+    /// The controlling GPIO, [`Gpio::None`] for no GPIO control
+    pub gpio: Gpio,
+    /// The polarity of the GPIO
+    pub polarity: GpioPolarity,
+}
+
+impl Default for GpioControl {
+    fn default() -> Self {
+        Self {
+            gpio: Gpio::None,
+            polarity: GpioPolarity::NotInverted,
+        }
+    }
+}
+
+/// VBUS input configuration
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct VbusInConfig {
+    /// Input current limit
+    pub current_limit: VbusInCurrentLimit,
+    /// Input current limit at startup
+    pub startup_current_limit: VbusInCurrentLimit,
+    /// VBUS input suspended
+    pub suspended: bool,
+}
+
+impl Default for VbusInConfig {
+    fn default() -> Self {
+        Self {
+            current_limit: VbusInCurrentLimit::MA500,
+            startup_current_limit: VbusInCurrentLimit::MA500,
+            suspended: false,
+        }
+    }
+}
+
+/// Battery charger configuration
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct ChargerConfig {
+    /// Battery charging enabled
+    pub enabled: bool,
+    /// Charging current
+    pub charge_current: ChargeCurrent,
+    /// Discharge current limit
+    pub discharge_current_limit: DischargeCurrentLimit,
+    /// Termination voltage in the normal temperature region
+    pub termination_voltage: ChargerTerminationVoltage,
+    /// Termination voltage in the warm temperature region
+    pub warm_termination_voltage: ChargerTerminationVoltage,
+    /// Trickle charging level
+    pub trickle_level: ChargerTrickleLevelSelect,
+    /// Termination current level
+    pub termination_current_level: ChargerTerminationCurrentLevelSelect,
+    /// Full charge current in the cool temperature region, otherwise 50% of the charge current
+    pub full_charge_current_in_cool_temp: bool,
+    /// Battery recharge once charged
+    pub recharge: bool,
+    /// Ignore the NTC thermistor temperature limits
+    pub ignore_ntc: bool,
+    /// Charging allowed when the battery is warm
+    pub charge_if_warm: bool,
+    /// NTC thermistor temperature region thresholds
+    pub ntc_thresholds: NtcThresholds,
+    /// Die temperature thresholds
+    pub die_temperature_thresholds: DieTemperatureThresholds,
+}
+
+impl Default for ChargerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            charge_current: ChargeCurrent::MIN,
+            discharge_current_limit: DischargeCurrentLimit::High,
+            termination_voltage: ChargerTerminationVoltage::V3_60,
+            warm_termination_voltage: ChargerTerminationVoltage::V3_60,
+            trickle_level: ChargerTrickleLevelSelect::V2_9,
+            termination_current_level: ChargerTerminationCurrentLevelSelect::SEL10,
+            full_charge_current_in_cool_temp: false,
+            recharge: true,
+            ignore_ntc: false,
+            charge_if_warm: true,
+            ntc_thresholds: NtcThresholds::default(),
+            die_temperature_thresholds: DieTemperatureThresholds::default(),
+        }
+    }
+}
+
+/// NTC thermistor thresholds of the temperature regions
+///
+/// The thresholds are the raw 10-bit values, see [`crate::NPM1300::set_ntc_threshold`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct NtcThresholds {
+    /// Cold threshold
+    pub cold: u16,
+    /// Cool threshold
+    pub cool: u16,
+    /// Warm threshold
+    pub warm: u16,
+    /// Hot threshold
+    pub hot: u16,
+}
+
+impl Default for NtcThresholds {
+    fn default() -> Self {
+        Self {
+            cold: 749,
+            cool: 658,
+            warm: 337,
+            hot: 237,
+        }
+    }
+}
+
+/// Die temperature thresholds for charging
+///
+/// The thresholds are the raw 10-bit values, see
+/// [`crate::NPM1300::set_die_temperature_threshold`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct DieTemperatureThresholds {
+    /// Charging stops above this threshold
+    pub stop: u16,
+    /// Charging resumes below this threshold
+    pub resume: u16,
+}
+
+impl Default for DieTemperatureThresholds {
+    fn default() -> Self {
+        Self {
+            stop: 360,
+            resume: 372,
+        }
+    }
+}
+
+/// BUCK regulator configuration
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct BuckConfig {
+    /// Regulator enabled
+    ///
+    /// Read back from the power good status, so a regulator enabled by its GPIO reads as
+    /// enabled.
+    pub enabled: bool,
+    /// Output voltage in normal mode
+    pub normal_voltage: BuckVoltage,
+    /// Output voltage in retention mode
+    pub retention_voltage: BuckVoltage,
+    /// Output voltage set by software, otherwise by the VSET pin
+    pub software_voltage_control: bool,
+    /// Forced PWM mode
+    ///
+    /// Read back from the mode status, so forced PWM mode set by its GPIO reads as enabled.
+    pub forced_pwm: bool,
+    /// PFM mode only, otherwise auto switching between PFM and PWM
+    pub pfm_only: bool,
+    /// Pull-down on the enable pin
+    pub pull_down: bool,
+    /// GPIO enabling the regulator
+    pub enable_gpio: GpioControl,
+    /// GPIO selecting the retention voltage
+    pub retention_gpio: GpioControl,
+    /// GPIO selecting forced PWM mode
+    pub forced_pwm_gpio: GpioControl,
+}
+
+impl BuckConfig {
+    /// Reset configuration of a regulator with its reset output voltage
+    fn reset(voltage: BuckVoltage) -> Self {
+        Self {
+            enabled: false,
+            normal_voltage: voltage,
+            retention_voltage: voltage,
+            software_voltage_control: false,
+            forced_pwm: false,
+            pfm_only: false,
+            pull_down: false,
+            enable_gpio: GpioControl::default(),
+            retention_gpio: GpioControl::default(),
+            forced_pwm_gpio: GpioControl::default(),
+        }
+    }
+}
+
+/// Load switch configuration
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct LdswConfig {
+    /// Load switch or LDO enabled
+    pub enabled: bool,
+    /// Load switch or LDO mode
+    pub mode: LdswMode,
+    /// LDO output voltage, only used in [`LdswMode::Ldo`]
+    pub ldo_voltage: LdswVoltage,
+    /// Soft-start current limit
+    pub soft_start_current: LdswSoftStartCurrent,
+    /// Active discharge
+    pub active_discharge: bool,
+    /// GPIO enabling the load switch
+    pub enable_gpio: GpioControl,
+}
+
+impl Default for LdswConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mode: LdswMode::LoadSwitch,
+            ldo_voltage: LdswVoltage::V1_0,
+            soft_start_current: LdswSoftStartCurrent::MA10,
+            active_discharge: false,
+            enable_gpio: GpioControl::default(),
+        }
+    }
+}
+
+/// Power-failure comparator configuration
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct PofConfig {
+    /// Power-failure detection enabled
+    pub enabled: bool,
+    /// Polarity of the power-failure warning GPIO
+    pub warning_polarity: Pofwarnpolarity,
+    /// VSYS threshold of the power-failure detection
+    pub vsys_threshold: VsysThreshold,
+}
+
+impl Default for PofConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            warning_polarity: Pofwarnpolarity::Low,
+            vsys_threshold: VsysThreshold::V28,
+        }
+    }
+}
+
+/// Ship and hibernate modes configuration
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct ShipConfig {
+    /// Ship hold button press timer
+    pub press_timer: Shphldtim,
+    /// Long press reset enabled
+    pub long_press_reset: bool,
+    /// Buttons performing the long press reset
+    pub long_press_reset_buttons: Longtimtwobuttonsel,
+}
+
+impl Default for ShipConfig {
+    fn default() -> Self {
+        Self {
+            press_timer: Shphldtim::Ms96,
+            long_press_reset: true,
+            long_press_reset_buttons: Longtimtwobuttonsel::Shphld,
+        }
+    }
+}
+
+/// ADC configuration
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct AdcConfig {
+    /// VBAT auto measurement every second
+    pub vbat_auto_measurement: bool,
+    /// VBAT burst measurements
+    pub vbat_burst_measurement: bool,
+    /// Battery NTC thermistor
+    pub ntc_thermistor: NtcThermistorType,
+    /// Beta value of the NTC thermistor in kelvin
+    ///
+    /// The beta value is not stored in the nPM1300. When applying, `None` keeps the beta value
+    /// already known by the driver.
+    pub ntc_beta: Option<u16>,
+    /// NTC auto measurement interval while charging
+    pub ntc_auto_measurement_interval: Ntcautotim,
+    /// Die temperature auto measurement interval while charging
+    pub die_temperature_auto_measurement_interval: Tempautotim,
+    /// Raw delayed VBAT measurement delay, see
+    /// [`crate::NPM1300::get_vbat_delay_configuration`]
+    pub vbat_delay: u8,
+    /// IBAT measurement after every VBAT measurement
+    pub ibat_measurement: bool,
+}
+
+impl Default for AdcConfig {
+    fn default() -> Self {
+        Self {
+            vbat_auto_measurement: false,
+            vbat_burst_measurement: false,
+            ntc_thermistor: NtcThermistorType::Ntc10K,
+            ntc_beta: None,
+            ntc_auto_measurement_interval: Ntcautotim::Ms1024,
+            die_temperature_auto_measurement_interval: Tempautotim::Ms4,
+            vbat_delay: 0,
+            ibat_measurement: false,
+        }
+    }
+}
+
+/// Number of registers covered by [`PmicConfig`]
+pub const CONFIG_REGISTER_COUNT: usize = 83;
+
+/// Registers differing between two configurations, in the order they are written
+pub type ConfigDiff = heapless::Vec<ConfigRegister, CONFIG_REGISTER_COUNT>;
+
+/// Register, or group of registers, of a [`PmicConfig`]
+///
+/// 10-bit values split in an MSB and an LSB register are a single entry, as both registers are
+/// always written together. The same goes for the SET and CLR task registers of a setting.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum ConfigRegister {
+    /// VBUSINILIMSTARTUP
+    VbusInStartupCurrentLimit,
+    /// VBUSINILIM0
+    VbusInCurrentLimit,
+    /// VBUSSUSPEND
+    VbusSuspend,
+    /// ADCCONFIG
+    AdcConfig,
+    /// ADCNTCRSEL
+    AdcNtcResistor,
+    /// ADCAUTOTIMCONF
+    AdcAutoTimer,
+    /// ADCDELTIMCONF
+    AdcVbatDelay,
+    /// ADCIBATMEASEN
+    AdcIbatMeasurement,
+    /// BCHGISETMSB and BCHGISETLSB
+    ChargerCurrent,
+    /// BCHGISETDISCHARGEMSB and BCHGISETDISCHARGELSB
+    ChargerDischargeCurrent,
+    /// BCHGVTERM
+    ChargerTerminationVoltage,
+    /// BCHGVTERMR
+    ChargerWarmTerminationVoltage,
+    /// BCHGVTRICKLESEL
+    ChargerTrickleLevel,
+    /// BCHGITERMSEL
+    ChargerTerminationCurrent,
+    /// NTCCOLD and NTCCOLDLSB
+    NtcCold,
+    /// NTCCOOL and NTCCOOLLSB
+    NtcCool,
+    /// NTCWARM and NTCWARMLSB
+    NtcWarm,
+    /// NTCHOT and NTCHOTLSB
+    NtcHot,
+    /// DIETEMPSTOP and DIETEMPSTOPLSB
+    DieTemperatureStop,
+    /// DIETEMPRESUME and DIETEMPRESUMELSB
+    DieTemperatureResume,
+    /// BCHGCONFIG
+    ChargerConfig,
+    /// BCHGENABLESET and BCHGENABLECLR, full charge current in the cool temperature region
+    ChargerFullChargeInCoolTemp,
+    /// BCHGDISABLESET and BCHGDISABLECLR, battery recharge
+    ChargerRecharge,
+    /// BCHGDISABLESET and BCHGDISABLECLR, NTC measurements
+    ChargerIgnoreNtc,
+    /// BCHGENABLESET and BCHGENABLECLR, battery charging
+    ChargerEnable,
+    /// GPIOMODE
+    GpioMode(GpioPin),
+    /// GPIODRIVE
+    GpioDriveStrength(GpioPin),
+    /// GPIOPUEN
+    GpioPullUp(GpioPin),
+    /// GPIOPDEN
+    GpioPullDown(GpioPin),
+    /// GPIOOPENDRAIN
+    GpioOpenDrain(GpioPin),
+    /// GPIODEBOUNCE
+    GpioDebounce(GpioPin),
+    /// BUCKxNORMVOUT
+    BuckNormalVoltage(Buck),
+    /// BUCKxRETVOUT
+    BuckRetentionVoltage(Buck),
+    /// BUCKSWCTRLSEL, shared by both regulators
+    BuckSoftwareControl,
+    /// BUCKCTRL0, shared by both regulators
+    BuckControl,
+    /// BUCKENCTRL, shared by both regulators
+    BuckEnableGpio,
+    /// BUCKVRETCTRL, shared by both regulators
+    BuckRetentionGpio,
+    /// BUCKPWMCTRL, shared by both regulators
+    BuckForcedPwmGpio,
+    /// BUCKxPWMSET and BUCKxPWMCLR
+    BuckForcedPwm(Buck),
+    /// BUCKxENASET and BUCKxENACLR
+    BuckEnable(Buck),
+    /// LDSWxLDOSEL
+    LdswMode(Ldsw),
+    /// LDSWxVOUTSEL
+    LdoVoltage(Ldsw),
+    /// LDSWCONFIG, shared by both load switches
+    LdswConfig,
+    /// LDSWxGPISEL
+    LdswEnableGpio(Ldsw),
+    /// TASKLDSWxSET and TASKLDSWxCLR
+    LdswEnable(Ldsw),
+    /// LEDDRVxMODESEL
+    LedMode(usize),
+    /// POFCONFIG
+    Pof,
+    /// SHPHLDCONFIG
+    ShipHoldConfig,
+    /// LPRESETCONFIG
+    LongPressResetConfig,
+}
+
+/// nPM PowerUP configuration import errors
+#[cfg(feature = "std")]
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PowerUpError {
+    #[error("invalid nPM PowerUP JSON")]
+    InvalidJson,
+    #[error("nPM PowerUP export is not for the nPM1300")]
+    UnsupportedDevice,
+    #[error("invalid or unsupported value for `{0}`")]
+    InvalidValue(&'static str),
+}
@@ -0,0 +1,289 @@
+//! Decoder of register values into the fields and enums of the device manifest
+//!
+//! The register map is read from `device.yaml`, the manifest the driver is generated from, so
+//! register values read from a device, for example with `npm1300-cli dump --raw`, can be
+//! checked on a host:
+//!
+//! ```ignore
+//! let register = decode(0x040F, 0x01)?;
+//! assert!(register.is_valid());
+//! println!("{register}");
+//! ```
+
+use core::fmt::Debug;
+
+use bitflags::Flags;
+
+use crate::{buck, charger, common, events, gpios, ldsw, leds, sysreg};
+
+mod registers;
+mod types;
+
+pub(crate) use registers::RegisterMap;
+#[cfg(feature = "sim")]
+pub(crate) use registers::WriteMode;
+// Re-export everything in types.rs
+pub use types::*;
+
+/// Get the objects of the register map, in manifest order
+pub fn registers() -> &'static [RegisterInfo] {
+    &RegisterMap::get().registers
+}
+
+/// Get the object of the register map at an address
+///
+/// # Arguments
+///
+/// * `address` - The register address, including the block offset
+pub fn register_at(address: u16) -> Option<&'static RegisterInfo> {
+    let map = RegisterMap::get();
+    map.index_of_address(address)
+        .map(|index| &map.registers[index])
+}
+
+/// Get an object of the register map by name, see [`RegisterInfo::name`]
+///
+/// # Arguments
+///
+/// * `name` - The name of the object, for example `BUCK.BUCKSTATUS`
+pub fn register_named(name: &str) -> Option<&'static RegisterInfo> {
+    let map = RegisterMap::get();
+    map.index_of_name(name).map(|index| &map.registers[index])
+}
+
+/// Decode a register value into its fields
+///
+/// Enum values are named as the generated low-level API or the driver types name them, like
+/// `Buck1Swctrlsel::Swctrl` or `VbusInCurrentLimit::MA500`. Values without a variant, variants
+/// named `UNUSED` in the manifest and bits set outside of the fields are flagged.
+///
+/// # Arguments
+///
+/// * `address` - The address of the register
+/// * `value` - The value of the register
+///
+/// # Errors
+///
+/// Returns `UnknownRegister` if no register is at the address
+pub fn decode(address: u16, value: u8) -> Result<DecodedRegister, UnknownRegister> {
+    let register = register_at(address).ok_or(UnknownRegister(address))?;
+    Ok(decode_register(register, value))
+}
+
+/// Decode a register dump, for example the output of `npm1300-cli dump --raw`
+///
+/// Every line holds a register address and its value in hexadecimal, with an optional `0x`
+/// prefix, separated by spaces, a comma, a colon or an equal sign. Empty lines and text after
+/// a `#` are skipped.
+///
+/// ```text
+/// # BUCK.BUCKSWCTRLSEL and CHARGER.BCHGERRREASON
+/// 0x040F 0x01
+/// 0x0336, 0x04
+/// ```
+///
+/// # Arguments
+///
+/// * `dump` - The register dump
+///
+/// # Errors
+///
+/// Returns `DumpError::InvalidLine` if a line is not an address and a value, and
+/// `DumpError::UnknownRegister` if no register is at an address
+pub fn decode_dump(dump: &str) -> Result<Vec<DecodedRegister>, DumpError> {
+    let mut registers = Vec::new();
+    for (line, text) in dump.lines().enumerate() {
+        let line = line + 1;
+        let content = text.split('#').next().unwrap_or_default();
+        let tokens: Vec<&str> = content
+            .split(|c: char| c.is_whitespace() || matches!(c, ',' | ':' | '='))
+            .filter(|token| !token.is_empty())
+            .collect();
+        let (address, value) = match tokens[..] {
+            [] => continue,
+            [address, value] => (parse_hex(address), parse_hex(value)),
+            _ => (None, None),
+        };
+        let (Some(address), Some(value)) = (address, value.and_then(|v| u8::try_from(v).ok()))
+        else {
+            return Err(DumpError::InvalidLine {
+                line,
+                text: text.into(),
+            });
+        };
+        let register =
+            decode(address, value).map_err(|_| DumpError::UnknownRegister { line, address })?;
+        registers.push(register);
+    }
+    Ok(registers)
+}
+
+/// Parse a hexadecimal number, with an optional `0x` prefix
+fn parse_hex(number: &str) -> Option<u16> {
+    let digits = number
+        .strip_prefix("0x")
+        .or_else(|| number.strip_prefix("0X"))
+        .unwrap_or(number);
+    u16::from_str_radix(digits, 16).ok()
+}
+
+/// Decode a value of a register of the map
+fn decode_register(register: &'static RegisterInfo, value: u8) -> DecodedRegister {
+    let mut field_bits = 0u8;
+    let fields = register
+        .fields
+        .iter()
+        .map(|field| {
+            field_bits |= field.mask();
+            let raw = field.extract(value);
+            DecodedField {
+                field,
+                raw,
+                value: decode_field(field, raw),
+            }
+        })
+        .collect();
+
+    DecodedRegister {
+        register,
+        value,
+        fields,
+        reserved_bits: value & !field_bits,
+    }
+}
+
+/// Decode the value of a field
+fn decode_field(field: &FieldInfo, raw: u8) -> FieldValue {
+    match &field.conversion {
+        FieldConversion::None => FieldValue::Integer,
+        FieldConversion::Enum {
+            name,
+            variants,
+            fallible,
+        } => match variants.iter().find(|variant| variant.value == raw) {
+            Some(variant) if variant.name.starts_with("Unused") => {
+                FieldValue::Reserved(Some(format!("{name}::{}", variant.name)))
+            }
+            Some(variant) => FieldValue::Variant(format!("{name}::{}", variant.name)),
+            None if *fallible => FieldValue::Invalid,
+            None => FieldValue::Reserved(
+                variants
+                    .iter()
+                    .find(|variant| variant.default)
+                    .map(|variant| format!("{name}::{}", variant.name)),
+            ),
+        },
+        FieldConversion::External { path, .. } => {
+            decode_external(path, raw).unwrap_or(FieldValue::Integer)
+        }
+    }
+}
+
+/// Decode the value of a field converted to a type of the driver
+///
+/// Returns `None` if the type is not known.
+fn decode_external(path: &str, raw: u8) -> Option<FieldValue> {
+    let value = match path.strip_prefix("crate::")? {
+        "common::Task" => try_convert::<common::Task>(raw),
+        "buck::BuckVoltage" => try_convert::<buck::BuckVoltage>(raw),
+        "ldsw::LdswVoltage" => try_convert::<ldsw::LdswVoltage>(raw),
+        "ldsw::LdswMode" => try_convert::<ldsw::LdswMode>(raw),
+        "ldsw::LdswSoftStartCurrent" => try_convert::<ldsw::LdswSoftStartCurrent>(raw),
+        "leds::LedMode" => try_convert::<leds::LedMode>(raw),
+        "gpios::Gpio" => try_convert::<gpios::Gpio>(raw),
+        "gpios::GpioPolarity" => try_convert::<gpios::GpioPolarity>(raw),
+        "gpios::GpioStatus" => try_convert::<gpios::GpioStatus>(raw),
+        "gpios::GpioMode" => try_convert::<gpios::GpioMode>(raw),
+        "gpios::GpioDriveStrength" => try_convert::<gpios::GpioDriveStrength>(raw),
+        "gpios::GpioPullUp" => try_convert::<gpios::GpioPullUp>(raw),
+        "gpios::GpioPullDown" => try_convert::<gpios::GpioPullDown>(raw),
+        "gpios::GpioOpenDrain" => try_convert::<gpios::GpioOpenDrain>(raw),
+        "gpios::GpioDebounce" => try_convert::<gpios::GpioDebounce>(raw),
+        "charger::ChargerEnableSet" => try_convert::<charger::ChargerEnableSet>(raw),
+        "charger::ChargerEnableClear" => try_convert::<charger::ChargerEnableClear>(raw),
+        "charger::ChargerEnableFullCurrentChargeInCoolTempSet" => {
+            try_convert::<charger::ChargerEnableFullCurrentChargeInCoolTempSet>(raw)
+        }
+        "charger::ChargerEnableFullCurrentChargeInCoolTempClear" => {
+            try_convert::<charger::ChargerEnableFullCurrentChargeInCoolTempClear>(raw)
+        }
+        "charger::ChargerDisableRechargeSet" => {
+            try_convert::<charger::ChargerDisableRechargeSet>(raw)
+        }
+        "charger::ChargerDisableRechargeClear" => {
+            try_convert::<charger::ChargerDisableRechargeClear>(raw)
+        }
+        "charger::DisableNtcSet" => try_convert::<charger::DisableNtcSet>(raw),
+        "charger::DisableNtcClear" => try_convert::<charger::DisableNtcClear>(raw),
+        "charger::ChargerTerminationVoltage" => convert::<charger::ChargerTerminationVoltage>(raw),
+        "charger::ChargerTrickleLevelSelect" => convert::<charger::ChargerTrickleLevelSelect>(raw),
+        "charger::ChargerTerminationCurrentLevelSelect" => {
+            convert::<charger::ChargerTerminationCurrentLevelSelect>(raw)
+        }
+        "charger::ChargerConfigDisableChargeWarm" => {
+            convert::<charger::ChargerConfigDisableChargeWarm>(raw)
+        }
+        "sysreg::VbusInCurrentLimit" => convert::<sysreg::VbusInCurrentLimit>(raw),
+        "sysreg::VbusInCcCmp" => convert::<sysreg::VbusInCcCmp>(raw),
+        "events::AdcEvents" => flags::<events::AdcEvents>(raw),
+        "events::BatteryTemperatureEvents" => flags::<events::BatteryTemperatureEvents>(raw),
+        "events::ChargerEvents" => flags::<events::ChargerEvents>(raw),
+        "events::BatteryEvents" => flags::<events::BatteryEvents>(raw),
+        "events::ShipHoldEvents" => flags::<events::ShipHoldEvents>(raw),
+        "events::VbusEvents" => flags::<events::VbusEvents>(raw),
+        "events::VbusThermalEvents" => flags::<events::VbusThermalEvents>(raw),
+        "events::GpioEvents" => flags::<events::GpioEvents>(raw),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Decode a value with an infallible conversion, flagging the values that don't convert back
+fn convert<T>(raw: u8) -> FieldValue
+where
+    T: From<u8> + Into<u8> + Copy + Debug,
+{
+    let value = T::from(raw);
+    let variant = format!("{}::{value:?}", type_name::<T>());
+    if value.into() == raw {
+        FieldValue::Variant(variant)
+    } else {
+        FieldValue::Reserved(Some(variant))
+    }
+}
+
+/// Decode a value with a fallible conversion, flagging the values that don't convert back
+fn try_convert<T>(raw: u8) -> FieldValue
+where
+    T: TryFrom<u8> + Into<u8> + Copy + Debug,
+{
+    match T::try_from(raw) {
+        Ok(value) if value.into() == raw => {
+            FieldValue::Variant(format!("{}::{value:?}", type_name::<T>()))
+        }
+        Ok(value) => FieldValue::Reserved(Some(format!("{}::{value:?}", type_name::<T>()))),
+        Err(_) => FieldValue::Invalid,
+    }
+}
+
+/// Decode event flags, flagging the bits without an event
+fn flags<T>(raw: u8) -> FieldValue
+where
+    T: Flags<Bits = u8>,
+{
+    let value = T::from_bits_retain(raw);
+    let mut names = String::new();
+    bitflags::parser::to_writer(&value, &mut names).expect("writing to a String never fails");
+    let variant = format!("{}({names})", type_name::<T>());
+    if T::from_bits(raw).is_some() {
+        FieldValue::Variant(variant)
+    } else {
+        FieldValue::Reserved(Some(variant))
+    }
+}
+
+/// Get the name of a type without its path
+fn type_name<T>() -> &'static str {
+    let name = core::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
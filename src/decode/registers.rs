@@ -0,0 +1,289 @@
+//! Register map read from the device manifest, shared by the decoder and the simulator
+
+use std::{collections::HashMap, sync::OnceLock};
+
+use yaml_rust2::{Yaml, YamlLoader};
+
+use super::{FieldConversion, FieldInfo, RegisterInfo, RegisterKind, VariantInfo};
+
+/// The device manifest the driver is generated from
+const MANIFEST: &str = include_str!("../../device.yaml");
+
+/// How a write to a register changes its value
+#[cfg(feature = "sim")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum WriteMode {
+    /// The written value replaces the value
+    Value,
+    /// Writing 1 sets a bit, the value is shared with the clear register
+    Set,
+    /// Writing 1 clears a bit of the value of the set register at this index
+    Clear(usize),
+}
+
+/// Register map with lookups by address and by name
+pub(crate) struct RegisterMap {
+    pub(crate) registers: Vec<RegisterInfo>,
+    #[cfg(feature = "sim")]
+    write_modes: Vec<WriteMode>,
+    by_address: HashMap<u16, usize>,
+    by_name: HashMap<String, usize>,
+}
+
+impl RegisterMap {
+    /// Get the register map, parsed from the manifest on first use
+    pub(crate) fn get() -> &'static RegisterMap {
+        static MAP: OnceLock<RegisterMap> = OnceLock::new();
+        MAP.get_or_init(|| RegisterMap::parse(MANIFEST))
+    }
+
+    /// Get the index of the register at an address
+    pub(crate) fn index_of_address(&self, address: u16) -> Option<usize> {
+        self.by_address.get(&address).copied()
+    }
+
+    /// Get the index of a register by name
+    pub(crate) fn index_of_name(&self, name: &str) -> Option<usize> {
+        self.by_name.get(name).copied()
+    }
+
+    /// Get how a write changes the value of a register
+    #[cfg(feature = "sim")]
+    pub(crate) fn write_mode(&self, index: usize) -> WriteMode {
+        self.write_modes[index]
+    }
+
+    /// Get the index of the register holding the value of a register
+    ///
+    /// A set register and its clear register, like `MAIN.EVENTSADCSET` and `MAIN.EVENTSADCCLR`,
+    /// read the same value, held by the set register.
+    #[cfg(feature = "sim")]
+    pub(crate) fn storage_of(&self, index: usize) -> usize {
+        match self.write_modes[index] {
+            WriteMode::Clear(set) => set,
+            WriteMode::Value | WriteMode::Set => index,
+        }
+    }
+
+    /// Parse the register map from a manifest
+    ///
+    /// The manifest is embedded at compile time, so an invalid manifest is a bug and panics.
+    fn parse(manifest: &str) -> RegisterMap {
+        let documents = YamlLoader::load_from_str(manifest).expect("device.yaml is valid YAML");
+        let blocks = documents[0]
+            .as_hash()
+            .expect("device.yaml is a map of blocks");
+
+        let mut registers = Vec::new();
+        for (block_name, block) in blocks {
+            let block_name = block_name.as_str().expect("block names are strings");
+            if block["type"].as_str() != Some("block") {
+                continue;
+            }
+            let offset = integer(&block["address_offset"]);
+            let objects = block["objects"]
+                .as_hash()
+                .expect("blocks have a map of objects");
+
+            for (object_name, object) in objects {
+                let object_name = object_name.as_str().expect("object names are strings");
+                let kind = match object["type"].as_str() {
+                    Some("register") => RegisterKind::Register {
+                        reset_value: match &object["reset_value"] {
+                            Yaml::BadValue => 0,
+                            value => integer(value) as u8,
+                        },
+                        read_only: matches!(
+                            object["access"].as_str(),
+                            Some("ReadOnly") | Some("RO")
+                        ),
+                        mask: mask(integer(&object["size_bits"])),
+                    },
+                    Some("command") => RegisterKind::Command,
+                    _ => continue,
+                };
+                let address = offset + integer(&object["address"]);
+                let fields = parse_fields(match kind {
+                    RegisterKind::Register { .. } => &object["fields"],
+                    RegisterKind::Command => &object["fields_in"],
+                });
+
+                match &object["repeat"] {
+                    Yaml::BadValue => registers.push(RegisterInfo {
+                        name: format!("{block_name}.{object_name}"),
+                        address: address as u16,
+                        kind,
+                        fields,
+                    }),
+                    repeat => {
+                        let stride = integer(&repeat["stride"]);
+                        for index in 0..integer(&repeat["count"]) {
+                            registers.push(RegisterInfo {
+                                name: format!("{block_name}.{object_name}[{index}]"),
+                                address: (address + index * stride) as u16,
+                                kind,
+                                fields: fields.clone(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        let by_address = registers
+            .iter()
+            .enumerate()
+            .map(|(index, register)| (register.address, index))
+            .collect();
+        let by_name: HashMap<String, usize> = registers
+            .iter()
+            .enumerate()
+            .map(|(index, register)| (register.name.clone(), index))
+            .collect();
+
+        RegisterMap {
+            #[cfg(feature = "sim")]
+            write_modes: write_modes(&registers, &by_name),
+            registers,
+            by_address,
+            by_name,
+        }
+    }
+}
+
+/// Get how a write changes the value of each register, pairing the set and clear registers
+#[cfg(feature = "sim")]
+fn write_modes(registers: &[RegisterInfo], by_name: &HashMap<String, usize>) -> Vec<WriteMode> {
+    let mut write_modes = vec![WriteMode::Value; registers.len()];
+    for (index, register) in registers.iter().enumerate() {
+        if !matches!(register.kind, RegisterKind::Register { .. }) {
+            continue;
+        }
+        let clear = register
+            .name
+            .strip_suffix("SET")
+            .and_then(|base| by_name.get(&format!("{base}CLR")));
+        if let Some(&clear) = clear {
+            write_modes[index] = WriteMode::Set;
+            write_modes[clear] = WriteMode::Clear(index);
+        }
+    }
+    write_modes
+}
+
+/// Parse the fields of an object, sorted by position
+fn parse_fields(fields: &Yaml) -> Vec<FieldInfo> {
+    let Some(fields) = fields.as_hash() else {
+        return Vec::new();
+    };
+    let mut fields: Vec<FieldInfo> = fields
+        .iter()
+        .map(|(name, field)| FieldInfo {
+            name: name.as_str().expect("field names are strings").into(),
+            start: integer(&field["start"]) as u8,
+            end: integer(&field["end"]) as u8,
+            conversion: match (&field["conversion"], &field["try_conversion"]) {
+                (Yaml::BadValue, Yaml::BadValue) => FieldConversion::None,
+                (conversion, Yaml::BadValue) => parse_conversion(conversion, false),
+                (_, conversion) => parse_conversion(conversion, true),
+            },
+        })
+        .collect();
+    fields.sort_by_key(|field| field.start);
+    fields
+}
+
+/// Parse the conversion of a field, a type path or an enum declared inline
+///
+/// # Arguments
+///
+/// * `conversion` - The `conversion` or `try_conversion` of the field
+/// * `fallible` - The conversion is a `try_conversion`
+fn parse_conversion(conversion: &Yaml, fallible: bool) -> FieldConversion {
+    if let Some(path) = conversion.as_str() {
+        return FieldConversion::External {
+            path: path.into(),
+            fallible,
+        };
+    }
+    let conversion = conversion
+        .as_hash()
+        .expect("conversions are a type path or an enum");
+
+    // Like device-driver, a variant without a value takes the value after the previous one
+    let mut variants = Vec::new();
+    let mut next = 0;
+    for (name, variant) in conversion {
+        let name = name.as_str().expect("variant names are strings");
+        if name == "name" || name == "description" {
+            continue;
+        }
+        let (value, default) = match &variant["value"] {
+            Yaml::String(value) if value == "default" || value == "catch_all" => (next, true),
+            value => (integer(value) as u8, false),
+        };
+        variants.push(VariantInfo {
+            name: pascal_case(name),
+            value,
+            default,
+        });
+        next = value.wrapping_add(1);
+    }
+
+    FieldConversion::Enum {
+        name: pascal_case(
+            conversion[&Yaml::String("name".into())]
+                .as_str()
+                .expect("enum names are strings"),
+        ),
+        variants,
+        fallible,
+    }
+}
+
+/// Convert a manifest name to the name device-driver generates, like `BUCK1SWCTRLSEL` to
+/// `Buck1Swctrlsel`
+fn pascal_case(name: &str) -> String {
+    let mut pascal = String::with_capacity(name.len());
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if c == '_' {
+            previous = None;
+            continue;
+        }
+        let starts_word = match previous {
+            None => true,
+            Some(previous) => {
+                previous.is_ascii_digit() != c.is_ascii_digit()
+                    || (previous.is_ascii_lowercase() && c.is_ascii_uppercase())
+            }
+        };
+        if starts_word {
+            pascal.push(c.to_ascii_uppercase());
+        } else {
+            pascal.push(c.to_ascii_lowercase());
+        }
+        previous = Some(c);
+    }
+    pascal
+}
+
+/// Get an integer of the manifest
+fn integer(value: &Yaml) -> i64 {
+    value
+        .as_i64()
+        .expect("device.yaml addresses and sizes are integers")
+}
+
+/// Get the mask of the bits of a register
+///
+/// # Arguments
+///
+/// * `size_bits` - The size of the register in bits
+fn mask(size_bits: i64) -> u8 {
+    if size_bits >= 8 {
+        0xFF
+    } else {
+        (1 << size_bits) - 1
+    }
+}
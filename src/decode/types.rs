@@ -0,0 +1,186 @@
+/// Kind of an object of the register map
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RegisterKind {
+    /// Register holding a value
+    Register {
+        /// Value after a reset
+        reset_value: u8,
+        /// Writes are ignored, the value is set by the nPM1300
+        read_only: bool,
+        /// Bits of the register, the other bits read as 0
+        mask: u8,
+    },
+    /// Task, writing 1 triggers it and reading returns 0
+    Command,
+}
+
+/// Object of the register map, as declared in `device.yaml`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RegisterInfo {
+    /// Name of the object as `BLOCK.OBJECT`, with the index of repeated objects as
+    /// `BLOCK.OBJECT[index]`, for example `BUCK.BUCKENASET[1]`
+    pub name: String,
+    /// Register address, including the block offset
+    pub address: u16,
+    /// Kind of the object
+    pub kind: RegisterKind,
+    /// Fields of the object, from the least significant bit
+    pub fields: Vec<FieldInfo>,
+}
+
+/// Field of a register or command, as declared in `device.yaml`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldInfo {
+    /// Name of the field
+    pub name: String,
+    /// First bit of the field
+    pub start: u8,
+    /// Bit after the last bit of the field
+    pub end: u8,
+    /// Conversion of the value of the field
+    pub conversion: FieldConversion,
+}
+
+impl FieldInfo {
+    /// Get the value of the field in a register value
+    pub fn extract(&self, value: u8) -> u8 {
+        (value & self.mask()) >> self.start
+    }
+
+    /// Get the bits of the field in a register value
+    pub fn mask(&self) -> u8 {
+        let width = self.end - self.start;
+        let mask: u8 = if width >= 8 { 0xFF } else { (1 << width) - 1 };
+        mask << self.start
+    }
+}
+
+/// Conversion of the value of a field, as declared in `device.yaml`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldConversion {
+    /// The value is an integer
+    None,
+    /// Enum generated from the manifest
+    Enum {
+        /// Name of the enum, like `Buck1Swctrlsel`
+        name: String,
+        /// Variants of the enum
+        variants: Vec<VariantInfo>,
+        /// The conversion rejects the values without a variant
+        fallible: bool,
+    },
+    /// Type of the driver, like `crate::sysreg::VbusInCurrentLimit`
+    External {
+        /// Path of the type
+        path: String,
+        /// The conversion can fail, the type implements `TryFrom<u8>` instead of `From<u8>`
+        fallible: bool,
+    },
+}
+
+/// Variant of an enum generated from the manifest
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VariantInfo {
+    /// Name of the variant, like `Swctrl`
+    pub name: String,
+    /// Value of the variant
+    pub value: u8,
+    /// The variant is the default, the values without a variant convert to it
+    pub default: bool,
+}
+
+/// Register value decoded into its fields, see [`super::decode`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedRegister {
+    /// The register
+    pub register: &'static RegisterInfo,
+    /// The value of the register
+    pub value: u8,
+    /// The decoded fields
+    pub fields: Vec<DecodedField>,
+    /// Bits set in the value outside of the fields
+    pub reserved_bits: u8,
+}
+
+impl DecodedRegister {
+    /// Check that the value has no reserved or invalid field value and no reserved bit set
+    pub fn is_valid(&self) -> bool {
+        self.reserved_bits == 0
+            && self
+                .fields
+                .iter()
+                .all(|field| matches!(field.value, FieldValue::Integer | FieldValue::Variant(_)))
+    }
+}
+
+impl core::fmt::Display for DecodedRegister {
+    /// Format as `BUCK.BUCKSWCTRLSEL = 0x01 { BUCK1SWCTRLSEL: 1 Buck1Swctrlsel::Swctrl, ... }`
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} = {:#04x}", self.register.name, self.value)?;
+        if !self.fields.is_empty() {
+            let fields = self
+                .fields
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            write!(f, " {{ {fields} }}")?;
+        }
+        if self.reserved_bits != 0 {
+            write!(f, " reserved bits {:#04x} set", self.reserved_bits)?;
+        }
+        Ok(())
+    }
+}
+
+/// Field of a [`DecodedRegister`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedField {
+    /// The field
+    pub field: &'static FieldInfo,
+    /// The value of the field
+    pub raw: u8,
+    /// The meaning of the value
+    pub value: FieldValue,
+}
+
+impl core::fmt::Display for DecodedField {
+    /// Format as `BUCK1SWCTRLSEL: 1 Buck1Swctrlsel::Swctrl`
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}: {}", self.field.name, self.raw)?;
+        match &self.value {
+            FieldValue::Integer => Ok(()),
+            FieldValue::Variant(variant) => write!(f, " {variant}"),
+            FieldValue::Reserved(Some(variant)) => write!(f, " reserved, read as {variant}"),
+            FieldValue::Reserved(None) => write!(f, " reserved"),
+            FieldValue::Invalid => write!(f, " invalid"),
+        }
+    }
+}
+
+/// Meaning of the value of a field
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldValue {
+    /// The field is an integer
+    Integer,
+    /// Value of the conversion of the field, like `Buck1Swctrlsel::Swctrl`
+    Variant(String),
+    /// Reserved value, with the value it converts to when the conversion accepts it
+    Reserved(Option<String>),
+    /// Value rejected by the conversion of the field
+    Invalid,
+}
+
+/// Error parsing a register dump, see [`super::decode_dump`]
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum DumpError {
+    #[error("line {line}: expected a register address and value, got {text:?}")]
+    InvalidLine { line: usize, text: String },
+    #[error("line {line}: no register at address {address:#06x}")]
+    UnknownRegister { line: usize, address: u16 },
+}
+
+/// Error for an address without a register, see [`super::decode`]
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("no register at address {0:#06x}")]
+pub struct UnknownRegister(pub u16);
@@ -0,0 +1,428 @@
+#[cfg(feature = "blocking")]
+use crate::hal::prelude::*;
+
+mod types;
+
+// Re-export everything in types.rs
+pub use types::*;
+
+#[maybe_async::maybe_async]
+impl<I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> crate::NPM1300<I2c, Delay> {
+    /// Enable interrupts for a set of events
+    ///
+    /// Enabled events assert the interrupt output of any GPIO configured as
+    /// [`crate::gpios::GpioMode::GpoIrq`]. Events that are not part of `events` keep their
+    /// current configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `events` - The events to enable, see [`Events`]
+    pub async fn enable_events(
+        &mut self,
+        events: Events,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        if !events.adc.is_empty() {
+            self.device
+                .main()
+                .inteneventsadcset()
+                .write_async(|reg| reg.set_value(events.adc))
+                .await?;
+        }
+        if !events.battery_temperature.is_empty() {
+            self.device
+                .main()
+                .inteneventsbcharger_0_set()
+                .write_async(|reg| reg.set_value(events.battery_temperature))
+                .await?;
+        }
+        if !events.charger.is_empty() {
+            self.device
+                .main()
+                .inteneventsbcharger_1_set()
+                .write_async(|reg| reg.set_value(events.charger))
+                .await?;
+        }
+        if !events.battery.is_empty() {
+            self.device
+                .main()
+                .inteneventsbcharger_2_set()
+                .write_async(|reg| reg.set_value(events.battery))
+                .await?;
+        }
+        if !events.ship_hold.is_empty() {
+            self.device
+                .main()
+                .inteneventsshphldset()
+                .write_async(|reg| reg.set_value(events.ship_hold))
+                .await?;
+        }
+        if !events.vbus.is_empty() {
+            self.device
+                .main()
+                .inteneventsvbusin_0_set()
+                .write_async(|reg| reg.set_value(events.vbus))
+                .await?;
+        }
+        if !events.vbus_thermal.is_empty() {
+            self.device
+                .main()
+                .inteneventsvbusin_1_set()
+                .write_async(|reg| reg.set_value(events.vbus_thermal))
+                .await?;
+        }
+        if !events.gpio.is_empty() {
+            self.device
+                .main()
+                .inteneventsgpioset()
+                .write_async(|reg| reg.set_value(events.gpio))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Disable interrupts for a set of events
+    ///
+    /// Disabled events are still latched and can be read with [`Self::get_events`], they just
+    /// no longer assert the interrupt output.
+    ///
+    /// # Arguments
+    ///
+    /// * `events` - The events to disable, see [`Events`]
+    pub async fn disable_events(
+        &mut self,
+        events: Events,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        if !events.adc.is_empty() {
+            self.device
+                .main()
+                .inteneventsadcclr()
+                .write_async(|reg| reg.set_value(events.adc))
+                .await?;
+        }
+        if !events.battery_temperature.is_empty() {
+            self.device
+                .main()
+                .inteneventsbcharger_0_clr()
+                .write_async(|reg| reg.set_value(events.battery_temperature))
+                .await?;
+        }
+        if !events.charger.is_empty() {
+            self.device
+                .main()
+                .inteneventsbcharger_1_clr()
+                .write_async(|reg| reg.set_value(events.charger))
+                .await?;
+        }
+        if !events.battery.is_empty() {
+            self.device
+                .main()
+                .inteneventsbcharger_2_clr()
+                .write_async(|reg| reg.set_value(events.battery))
+                .await?;
+        }
+        if !events.ship_hold.is_empty() {
+            self.device
+                .main()
+                .inteneventsshphldclr()
+                .write_async(|reg| reg.set_value(events.ship_hold))
+                .await?;
+        }
+        if !events.vbus.is_empty() {
+            self.device
+                .main()
+                .inteneventsvbusin_0_clr()
+                .write_async(|reg| reg.set_value(events.vbus))
+                .await?;
+        }
+        if !events.vbus_thermal.is_empty() {
+            self.device
+                .main()
+                .inteneventsvbusin_1_clr()
+                .write_async(|reg| reg.set_value(events.vbus_thermal))
+                .await?;
+        }
+        if !events.gpio.is_empty() {
+            self.device
+                .main()
+                .inteneventsgpioclr()
+                .write_async(|reg| reg.set_value(events.gpio))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Get the events that currently have their interrupt enabled
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Events)` - The enabled events
+    /// * `Err(NPM1300Error)` - An error occurred while reading the interrupt enable registers
+    pub async fn get_enabled_events(&mut self) -> Result<Events, crate::NPM1300Error<I2c::Error>> {
+        Ok(Events {
+            adc: self
+                .device
+                .main()
+                .inteneventsadcset()
+                .read_async()
+                .await?
+                .value(),
+            battery_temperature: self
+                .device
+                .main()
+                .inteneventsbcharger_0_set()
+                .read_async()
+                .await?
+                .value(),
+            charger: self
+                .device
+                .main()
+                .inteneventsbcharger_1_set()
+                .read_async()
+                .await?
+                .value(),
+            battery: self
+                .device
+                .main()
+                .inteneventsbcharger_2_set()
+                .read_async()
+                .await?
+                .value(),
+            ship_hold: self
+                .device
+                .main()
+                .inteneventsshphldset()
+                .read_async()
+                .await?
+                .value(),
+            vbus: self
+                .device
+                .main()
+                .inteneventsvbusin_0_set()
+                .read_async()
+                .await?
+                .value(),
+            vbus_thermal: self
+                .device
+                .main()
+                .inteneventsvbusin_1_set()
+                .read_async()
+                .await?
+                .value(),
+            gpio: self
+                .device
+                .main()
+                .inteneventsgpioset()
+                .read_async()
+                .await?
+                .value(),
+        })
+    }
+
+    /// Get the pending events
+    ///
+    /// Events are latched by the nPM1300 whether or not their interrupt is enabled, and stay
+    /// pending until cleared with [`Self::clear_events`].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Events)` - The pending events
+    /// * `Err(NPM1300Error)` - An error occurred while reading the event registers
+    pub async fn get_events(&mut self) -> Result<Events, crate::NPM1300Error<I2c::Error>> {
+        Ok(Events {
+            adc: self
+                .device
+                .main()
+                .eventsadcset()
+                .read_async()
+                .await?
+                .value(),
+            battery_temperature: self
+                .device
+                .main()
+                .eventsbcharger_0_set()
+                .read_async()
+                .await?
+                .value(),
+            charger: self
+                .device
+                .main()
+                .eventsbcharger_1_set()
+                .read_async()
+                .await?
+                .value(),
+            battery: self
+                .device
+                .main()
+                .eventsbcharger_2_set()
+                .read_async()
+                .await?
+                .value(),
+            ship_hold: self
+                .device
+                .main()
+                .eventsshphldset()
+                .read_async()
+                .await?
+                .value(),
+            vbus: self
+                .device
+                .main()
+                .eventsvbusin_0_set()
+                .read_async()
+                .await?
+                .value(),
+            vbus_thermal: self
+                .device
+                .main()
+                .eventsvbusin_1_set()
+                .read_async()
+                .await?
+                .value(),
+            gpio: self
+                .device
+                .main()
+                .eventsgpioset()
+                .read_async()
+                .await?
+                .value(),
+        })
+    }
+
+    /// Clear a set of pending events
+    ///
+    /// Clearing the events also releases the interrupt output once no enabled event is
+    /// pending anymore.
+    ///
+    /// # Arguments
+    ///
+    /// * `events` - The events to clear, see [`Events`]
+    pub async fn clear_events(
+        &mut self,
+        events: Events,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        if !events.adc.is_empty() {
+            self.device
+                .main()
+                .eventsadcclr()
+                .write_async(|reg| reg.set_value(events.adc))
+                .await?;
+        }
+        if !events.battery_temperature.is_empty() {
+            self.device
+                .main()
+                .eventsbcharger_0_clr()
+                .write_async(|reg| reg.set_value(events.battery_temperature))
+                .await?;
+        }
+        if !events.charger.is_empty() {
+            self.device
+                .main()
+                .eventsbcharger_1_clr()
+                .write_async(|reg| reg.set_value(events.charger))
+                .await?;
+        }
+        if !events.battery.is_empty() {
+            self.device
+                .main()
+                .eventsbcharger_2_clr()
+                .write_async(|reg| reg.set_value(events.battery))
+                .await?;
+        }
+        if !events.ship_hold.is_empty() {
+            self.device
+                .main()
+                .eventsshphldclr()
+                .write_async(|reg| reg.set_value(events.ship_hold))
+                .await?;
+        }
+        if !events.vbus.is_empty() {
+            self.device
+                .main()
+                .eventsvbusin_0_clr()
+                .write_async(|reg| reg.set_value(events.vbus))
+                .await?;
+        }
+        if !events.vbus_thermal.is_empty() {
+            self.device
+                .main()
+                .eventsvbusin_1_clr()
+                .write_async(|reg| reg.set_value(events.vbus_thermal))
+                .await?;
+        }
+        if !events.gpio.is_empty() {
+            self.device
+                .main()
+                .eventsgpioclr()
+                .write_async(|reg| reg.set_value(events.gpio))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Wait for the nPM1300 to report events on its interrupt output
+    ///
+    /// Waits until the interrupt line is asserted, then reads all pending events and clears
+    /// them in one pass. If events are already pending when called, this returns immediately.
+    ///
+    /// The PMIC GPIO wired to `irq` must be configured as [`crate::gpios::GpioMode::GpoIrq`]
+    /// and the events of interest enabled with [`Self::enable_events`]. The interrupt output
+    /// is active high.
+    ///
+    /// # Arguments
+    ///
+    /// * `irq` - Host pin connected to the nPM1300 interrupt output
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Events)` - The events that were pending, now cleared
+    /// * `Err(NPM1300Error)` - An error occurred while waiting on the pin or accessing the events
+    #[maybe_async::async_impl]
+    pub async fn wait_for_events<Irq: crate::hal::Wait>(
+        &mut self,
+        irq: &mut Irq,
+    ) -> Result<Events, crate::NPM1300Error<I2c::Error>> {
+        irq.wait_for_high()
+            .await
+            .map_err(|_| crate::NPM1300Error::InterruptPin)?;
+
+        let events = self.get_events().await?;
+        self.clear_events(events).await?;
+
+        Ok(events)
+    }
+
+    /// Wait for the nPM1300 to report events on its interrupt output
+    ///
+    /// Polls the interrupt line until it is asserted, then reads all pending events and clears
+    /// them in one pass. If events are already pending when called, this returns immediately.
+    ///
+    /// The PMIC GPIO wired to `irq` must be configured as [`crate::gpios::GpioMode::GpoIrq`]
+    /// and the events of interest enabled with [`Self::enable_events`]. The interrupt output
+    /// is active high.
+    ///
+    /// # Arguments
+    ///
+    /// * `irq` - Host pin connected to the nPM1300 interrupt output
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Events)` - The events that were pending, now cleared
+    /// * `Err(NPM1300Error)` - An error occurred while waiting on the pin or accessing the events
+    #[maybe_async::sync_impl]
+    pub fn wait_for_events<Irq: crate::hal::InputPin>(
+        &mut self,
+        irq: &mut Irq,
+    ) -> Result<Events, crate::NPM1300Error<I2c::Error>> {
+        while !irq
+            .is_high()
+            .map_err(|_| crate::NPM1300Error::InterruptPin)?
+        {
+            self.delay.delay_us(100);
+        }
+
+        let events = self.get_events()?;
+        self.clear_events(events)?;
+
+        Ok(events)
+    }
+}
@@ -0,0 +1,348 @@
+/// ADC events
+///
+/// Generated when an ADC measurement result is ready to be read.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct AdcEvents(u8);
+
+bitflags::bitflags! {
+    impl AdcEvents: u8 {
+        /// VBAT measurement finished
+        const VBAT_READY = 1 << 0;
+        /// Battery NTC thermistor measurement finished
+        const NTC_READY = 1 << 1;
+        /// Die temperature measurement finished
+        const DIE_TEMPERATURE_READY = 1 << 2;
+        /// VSYS measurement finished
+        const VSYS_READY = 1 << 3;
+        /// VSET1 pin measurement finished
+        const VSET1_READY = 1 << 4;
+        /// VSET2 pin measurement finished
+        const VSET2_READY = 1 << 5;
+        /// IBAT measurement finished
+        const IBAT_READY = 1 << 6;
+        /// VBUS measurement finished
+        const VBUS_READY = 1 << 7;
+    }
+}
+
+// Add conversion from u8
+impl From<u8> for AdcEvents {
+    fn from(value: u8) -> Self {
+        Self::from_bits_retain(value)
+    }
+}
+
+// Add conversion to u8
+impl From<AdcEvents> for u8 {
+    fn from(events: AdcEvents) -> Self {
+        events.bits()
+    }
+}
+
+/// Battery temperature events
+///
+/// Generated when the battery NTC thermistor or the die temperature crosses one of the
+/// charger temperature thresholds.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct BatteryTemperatureEvents(u8);
+
+bitflags::bitflags! {
+    impl BatteryTemperatureEvents: u8 {
+        /// Battery temperature entered the cold region
+        const NTC_COLD = 1 << 0;
+        /// Battery temperature entered the cool region
+        const NTC_COOL = 1 << 1;
+        /// Battery temperature entered the warm region
+        const NTC_WARM = 1 << 2;
+        /// Battery temperature entered the hot region
+        const NTC_HOT = 1 << 3;
+        /// Die temperature is above DIETEMPSTOP, charging is paused
+        const DIE_TEMPERATURE_HIGH = 1 << 4;
+        /// Die temperature is below DIETEMPRESUME, charging resumes
+        const DIE_TEMPERATURE_RESUME = 1 << 5;
+    }
+}
+
+// Add conversion from u8
+impl From<u8> for BatteryTemperatureEvents {
+    fn from(value: u8) -> Self {
+        Self::from_bits_retain(value)
+    }
+}
+
+// Add conversion to u8
+impl From<BatteryTemperatureEvents> for u8 {
+    fn from(events: BatteryTemperatureEvents) -> Self {
+        events.bits()
+    }
+}
+
+/// Battery charger events
+///
+/// Generated when the charger changes charging phase or enters an error state.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct ChargerEvents(u8);
+
+bitflags::bitflags! {
+    impl ChargerEvents: u8 {
+        /// Supplement mode activated
+        const SUPPLEMENT = 1 << 0;
+        /// Trickle charging started
+        const TRICKLE = 1 << 1;
+        /// Constant current charging started
+        const CONSTANT_CURRENT = 1 << 2;
+        /// Constant voltage charging started
+        const CONSTANT_VOLTAGE = 1 << 3;
+        /// Charging completed, battery is full
+        const COMPLETED = 1 << 4;
+        /// Charging error
+        const ERROR = 1 << 5;
+    }
+}
+
+// Add conversion from u8
+impl From<u8> for ChargerEvents {
+    fn from(value: u8) -> Self {
+        Self::from_bits_retain(value)
+    }
+}
+
+// Add conversion to u8
+impl From<ChargerEvents> for u8 {
+    fn from(events: ChargerEvents) -> Self {
+        events.bits()
+    }
+}
+
+/// Battery events
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct BatteryEvents(u8);
+
+bitflags::bitflags! {
+    impl BatteryEvents: u8 {
+        /// Battery detected
+        const DETECTED = 1 << 0;
+        /// Battery removed
+        const LOST = 1 << 1;
+        /// Battery voltage dropped below the recharge threshold
+        const RECHARGE = 1 << 2;
+    }
+}
+
+// Add conversion from u8
+impl From<u8> for BatteryEvents {
+    fn from(value: u8) -> Self {
+        Self::from_bits_retain(value)
+    }
+}
+
+// Add conversion to u8
+impl From<BatteryEvents> for u8 {
+    fn from(events: BatteryEvents) -> Self {
+        events.bits()
+    }
+}
+
+/// Ship hold events
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct ShipHoldEvents(u8);
+
+bitflags::bitflags! {
+    impl ShipHoldEvents: u8 {
+        /// SHPHLD button pressed
+        const PRESSED = 1 << 0;
+        /// SHPHLD button released
+        const RELEASED = 1 << 1;
+        /// SHPHLD button held long enough to exit ship mode
+        const EXIT = 1 << 2;
+        /// Watchdog timeout warning
+        const WATCHDOG_WARNING = 1 << 3;
+    }
+}
+
+// Add conversion from u8
+impl From<u8> for ShipHoldEvents {
+    fn from(value: u8) -> Self {
+        Self::from_bits_retain(value)
+    }
+}
+
+// Add conversion to u8
+impl From<ShipHoldEvents> for u8 {
+    fn from(events: ShipHoldEvents) -> Self {
+        events.bits()
+    }
+}
+
+/// VBUS input events
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct VbusEvents(u8);
+
+bitflags::bitflags! {
+    impl VbusEvents: u8 {
+        /// VBUS detected (USB plugged in)
+        const DETECTED = 1 << 0;
+        /// VBUS removed (USB unplugged)
+        const REMOVED = 1 << 1;
+        /// VBUS over-voltage detected
+        const OVERVOLTAGE_DETECTED = 1 << 2;
+        /// VBUS over-voltage removed
+        const OVERVOLTAGE_REMOVED = 1 << 3;
+        /// VBUS under-voltage detected
+        const UNDERVOLTAGE_DETECTED = 1 << 4;
+        /// VBUS under-voltage removed
+        const UNDERVOLTAGE_REMOVED = 1 << 5;
+    }
+}
+
+// Add conversion from u8
+impl From<u8> for VbusEvents {
+    fn from(value: u8) -> Self {
+        Self::from_bits_retain(value)
+    }
+}
+
+// Add conversion to u8
+impl From<VbusEvents> for u8 {
+    fn from(events: VbusEvents) -> Self {
+        events.bits()
+    }
+}
+
+/// VBUS input thermal and USB-C CC line events
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct VbusThermalEvents(u8);
+
+bitflags::bitflags! {
+    impl VbusThermalEvents: u8 {
+        /// Die thermal warning detected
+        const THERMAL_WARNING_DETECTED = 1 << 0;
+        /// Die thermal warning removed
+        const THERMAL_WARNING_REMOVED = 1 << 1;
+        /// Die thermal shutdown detected
+        const THERMAL_SHUTDOWN_DETECTED = 1 << 2;
+        /// Die thermal shutdown removed
+        const THERMAL_SHUTDOWN_REMOVED = 1 << 3;
+        /// Voltage changed on the CC1 line
+        const CC1_CHANGED = 1 << 4;
+        /// Voltage changed on the CC2 line
+        const CC2_CHANGED = 1 << 5;
+    }
+}
+
+// Add conversion from u8
+impl From<u8> for VbusThermalEvents {
+    fn from(value: u8) -> Self {
+        Self::from_bits_retain(value)
+    }
+}
+
+// Add conversion to u8
+impl From<VbusThermalEvents> for u8 {
+    fn from(events: VbusThermalEvents) -> Self {
+        events.bits()
+    }
+}
+
+/// GPIO events
+///
+/// Only generated for GPIOs configured as [`crate::gpios::GpioMode::GpiEventRise`] or
+/// [`crate::gpios::GpioMode::GpiEventFall`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct GpioEvents(u8);
+
+bitflags::bitflags! {
+    impl GpioEvents: u8 {
+        /// Edge detected on GPIO0
+        const GPIO0 = 1 << 0;
+        /// Edge detected on GPIO1
+        const GPIO1 = 1 << 1;
+        /// Edge detected on GPIO2
+        const GPIO2 = 1 << 2;
+        /// Edge detected on GPIO3
+        const GPIO3 = 1 << 3;
+        /// Edge detected on GPIO4
+        const GPIO4 = 1 << 4;
+    }
+}
+
+// Add conversion from u8
+impl From<u8> for GpioEvents {
+    fn from(value: u8) -> Self {
+        Self::from_bits_retain(value)
+    }
+}
+
+// Add conversion to u8
+impl From<GpioEvents> for u8 {
+    fn from(events: GpioEvents) -> Self {
+        events.bits()
+    }
+}
+
+/// Set of events across all event groups of the nPM1300
+///
+/// Used both to select which events to enable, disable or clear, and to report which
+/// events are pending.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct Events {
+    pub adc: AdcEvents,
+    pub battery_temperature: BatteryTemperatureEvents,
+    pub charger: ChargerEvents,
+    pub battery: BatteryEvents,
+    pub ship_hold: ShipHoldEvents,
+    pub vbus: VbusEvents,
+    pub vbus_thermal: VbusThermalEvents,
+    pub gpio: GpioEvents,
+}
+
+impl Events {
+    /// An empty set of events
+    pub const fn empty() -> Self {
+        Self {
+            adc: AdcEvents::empty(),
+            battery_temperature: BatteryTemperatureEvents::empty(),
+            charger: ChargerEvents::empty(),
+            battery: BatteryEvents::empty(),
+            ship_hold: ShipHoldEvents::empty(),
+            vbus: VbusEvents::empty(),
+            vbus_thermal: VbusThermalEvents::empty(),
+            gpio: GpioEvents::empty(),
+        }
+    }
+
+    /// Every event of every group
+    pub const fn all() -> Self {
+        Self {
+            adc: AdcEvents::all(),
+            battery_temperature: BatteryTemperatureEvents::all(),
+            charger: ChargerEvents::all(),
+            battery: BatteryEvents::all(),
+            ship_hold: ShipHoldEvents::all(),
+            vbus: VbusEvents::all(),
+            vbus_thermal: VbusThermalEvents::all(),
+            gpio: GpioEvents::all(),
+        }
+    }
+
+    /// Returns `true` if no event is set in any group
+    pub const fn is_empty(&self) -> bool {
+        self.adc.is_empty()
+            && self.battery_temperature.is_empty()
+            && self.charger.is_empty()
+            && self.battery.is_empty()
+            && self.ship_hold.is_empty()
+            && self.vbus.is_empty()
+            && self.vbus_thermal.is_empty()
+            && self.gpio.is_empty()
+    }
+}
@@ -0,0 +1,219 @@
+use libm::fabsf;
+
+use crate::battery::BatteryModel;
+
+mod types;
+
+// Re-export everything in types.rs
+pub use types::*;
+
+/// Current below which the battery is considered at rest, in mA
+///
+/// At rest the terminal voltage is close to the open-circuit voltage, so the voltage
+/// correction is trusted more.
+const REST_CURRENT: f32 = 5.0;
+
+/// Weight of the voltage based estimate when the battery is at rest
+const REST_CORRECTION_GAIN: f32 = 0.05;
+
+/// Weight of the voltage based estimate when the battery is under load
+const LOAD_CORRECTION_GAIN: f32 = 0.005;
+
+/// Smoothing factor of the average current used for time estimates
+const CURRENT_FILTER_GAIN: f32 = 0.1;
+
+/// Estimate the state of charge from a loaded battery measurement
+///
+/// The open-circuit voltage is recovered by removing the voltage drop over the internal
+/// resistance.
+fn soc_from_measurement(model: &BatteryModel, input: &FuelGaugeInput) -> f32 {
+    let resistance = model.resistance_at(input.temperature);
+    let ocv = input.voltage - input.current / 1000.0 * resistance;
+    model.soc_at(ocv, input.temperature)
+}
+
+/// Host-side battery fuel gauge
+///
+/// The nPM1300 has no fuel gauge of its own, so the state of charge is estimated on the host
+/// from VBAT, IBAT and the battery temperature. Charge is counted by integrating the battery
+/// current, and the drift of the count is corrected with the voltage based estimate from
+/// the battery model.
+///
+/// The gauge is updated periodically with [`Self::update`], or with
+/// [`crate::NPM1300::update_fuel_gauge`] to take the measurements in one call. An update
+/// every few seconds is enough.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct FuelGauge<'a> {
+    model: &'a BatteryModel,
+    soc: Option<f32>,
+    average_current: f32,
+}
+
+impl<'a> FuelGauge<'a> {
+    /// Create a new fuel gauge
+    ///
+    /// The state of charge is initialized from the battery voltage on the first update.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The battery model, see [`BatteryModel`]
+    pub const fn new(model: &'a BatteryModel) -> Self {
+        Self {
+            model,
+            soc: None,
+            average_current: 0.0,
+        }
+    }
+
+    /// Get the battery model
+    pub const fn model(&self) -> &'a BatteryModel {
+        self.model
+    }
+
+    /// Reset the gauge, the state of charge is initialized again on the next update
+    pub fn reset(&mut self) {
+        self.soc = None;
+        self.average_current = 0.0;
+    }
+
+    /// Update the gauge with a new measurement
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The battery measurement, see [`FuelGaugeInput`]
+    /// * `elapsed` - Time since the previous update
+    ///
+    /// # Returns
+    ///
+    /// The new estimate, see [`FuelGaugeState`]
+    pub fn update(
+        &mut self,
+        input: FuelGaugeInput,
+        elapsed: core::time::Duration,
+    ) -> FuelGaugeState {
+        let voltage_soc = soc_from_measurement(self.model, &input);
+
+        let soc = match self.soc {
+            None => {
+                self.average_current = input.current;
+                voltage_soc
+            }
+            Some(soc) => {
+                // Coulomb counting
+                let hours = elapsed.as_secs_f32() / 3600.0;
+                let counted_soc = soc + input.current * hours / self.model.capacity;
+
+                // Voltage correction
+                let gain = if fabsf(input.current) < REST_CURRENT {
+                    REST_CORRECTION_GAIN
+                } else {
+                    LOAD_CORRECTION_GAIN
+                };
+
+                self.average_current +=
+                    CURRENT_FILTER_GAIN * (input.current - self.average_current);
+
+                counted_soc + gain * (voltage_soc - counted_soc)
+            }
+        };
+        self.soc = Some(soc.clamp(0.0, 1.0));
+
+        self.state()
+    }
+
+    /// Get the latest estimate without updating the gauge
+    pub fn state(&self) -> FuelGaugeState {
+        let soc = self.soc.unwrap_or(0.0);
+        let current = self.average_current;
+
+        let time_to = |remaining: f32| {
+            let hours = remaining * self.model.capacity / fabsf(current);
+            core::time::Duration::try_from_secs_f32(hours * 3600.0).ok()
+        };
+
+        FuelGaugeState {
+            state_of_charge: soc * 100.0,
+            time_to_empty: if current <= -REST_CURRENT {
+                time_to(soc)
+            } else {
+                None
+            },
+            time_to_full: if current >= REST_CURRENT {
+                time_to(1.0 - soc)
+            } else {
+                None
+            },
+        }
+    }
+}
+
+#[maybe_async::maybe_async]
+impl<I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> crate::NPM1300<I2c, Delay> {
+    /// Measure the battery for the fuel gauge
+    ///
+    /// The battery temperature is measured with the NTC thermistor if one is configured with
+    /// [`Self::configure_ntc_resistance`], otherwise the die temperature is used.
+    ///
+    /// IBAT is only measured together with VBAT, so the IBAT measurement is enabled for the
+    /// VBAT measurement if needed and disabled again afterwards.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FuelGaugeInput)` - The battery voltage, current and temperature
+    /// * `Err(NPM1300Error)` - An error occurred while measuring
+    pub async fn measure_fuel_gauge_input(
+        &mut self,
+    ) -> Result<FuelGaugeInput, crate::NPM1300Error<I2c::Error>> {
+        let ibat_enabled = self.get_ibat_measurement_configuration().await?;
+        if !ibat_enabled {
+            self.configure_ibat_measurement(true).await?;
+        }
+        let measurement = self.measure_vbat_and_ibat().await;
+        if !ibat_enabled {
+            self.configure_ibat_measurement(false).await?;
+        }
+        let (voltage, current) = measurement?;
+
+        let temperature = if self.ntc_beta.is_some() {
+            self.measure_ntc().await?
+        } else {
+            self.measure_die_temperature().await?
+        };
+
+        Ok(FuelGaugeInput {
+            voltage,
+            current,
+            temperature,
+        })
+    }
+
+    /// Measure VBAT and the IBAT result of the same measurement, in V and mA
+    async fn measure_vbat_and_ibat(
+        &mut self,
+    ) -> Result<(f32, f32), crate::NPM1300Error<I2c::Error>> {
+        let voltage = self.measure_vbat().await?;
+        let current = self.measure_ibat().await?;
+        Ok((voltage, current))
+    }
+
+    /// Measure the battery and update a fuel gauge
+    ///
+    /// # Arguments
+    ///
+    /// * `fuel_gauge` - The fuel gauge to update, see [`FuelGauge`]
+    /// * `elapsed` - Time since the previous update
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FuelGaugeState)` - The new estimate
+    /// * `Err(NPM1300Error)` - An error occurred while measuring
+    pub async fn update_fuel_gauge(
+        &mut self,
+        fuel_gauge: &mut FuelGauge<'_>,
+        elapsed: core::time::Duration,
+    ) -> Result<FuelGaugeState, crate::NPM1300Error<I2c::Error>> {
+        let input = self.measure_fuel_gauge_input().await?;
+        Ok(fuel_gauge.update(input, elapsed))
+    }
+}
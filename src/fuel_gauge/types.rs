@@ -0,0 +1,25 @@
+/// Battery measurement fed to the [`super::FuelGauge`]
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct FuelGaugeInput {
+    /// Battery voltage in volts, as returned by [`crate::NPM1300::measure_vbat`]
+    pub voltage: f32,
+    /// Battery current in mA, positive when charging and negative when discharging, as
+    /// returned by [`crate::NPM1300::measure_ibat`]
+    pub current: f32,
+    /// Battery temperature in degrees Celsius, as returned by
+    /// [`crate::NPM1300::measure_ntc`]
+    pub temperature: f32,
+}
+
+/// Fuel gauge estimate
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct FuelGaugeState {
+    /// State of charge in percent, from 0.0 to 100.0
+    pub state_of_charge: f32,
+    /// Estimated time until the battery is empty, `None` when not discharging
+    pub time_to_empty: Option<core::time::Duration>,
+    /// Estimated time until the battery is full, `None` when not charging
+    pub time_to_full: Option<core::time::Duration>,
+}
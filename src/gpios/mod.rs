@@ -3,6 +3,9 @@ mod types;
 // Re-export everything in types.rs
 pub use types::*;
 
+#[cfg(feature = "blocking")]
+use crate::hal::prelude::*;
+
 /// Builder pattern for GPIO configuration
 pub struct GpioConfigBuilder {
     config: GpioConfig,
@@ -11,67 +14,73 @@ pub struct GpioConfigBuilder {
 /// GPIO Configuration structure
 ///
 /// Pull-down is prioritized if both pull-up and pull-down are activated on a GPIO pin at the same time.
-#[derive(Debug, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub struct GpioConfig {
-    mode: GpioMode,
-    drive_strength: GpioDriveStrength,
-    pull_up: GpioPullUp,
-    pull_down: GpioPullDown,
-    open_drain: GpioOpenDrain,
-    debounce: GpioDebounce,
+    pub(crate) mode: GpioMode,
+    pub(crate) drive_strength: GpioDriveStrength,
+    pub(crate) pull_up: GpioPullUp,
+    pub(crate) pull_down: GpioPullDown,
+    pub(crate) open_drain: GpioOpenDrain,
+    pub(crate) debounce: GpioDebounce,
+}
+
+impl GpioConfig {
+    /// Reset configuration of a GPIO
+    const RESET: Self = Self {
+        mode: GpioMode::GpiInput,
+        drive_strength: GpioDriveStrength::Drive1mA,
+        pull_up: GpioPullUp::Disable,
+        pull_down: GpioPullDown::Enable,
+        open_drain: GpioOpenDrain::Disable,
+        debounce: GpioDebounce::Disable,
+    };
 }
 
 impl Default for GpioConfig {
     fn default() -> Self {
-        Self {
-            mode: GpioMode::GpiInput,
-            drive_strength: GpioDriveStrength::Drive1mA,
-            pull_up: GpioPullUp::Disable,
-            pull_down: GpioPullDown::Enable,
-            open_drain: GpioOpenDrain::Disable,
-            debounce: GpioDebounce::Disable,
-        }
+        Self::RESET
     }
 }
 
 impl GpioConfigBuilder {
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Self {
-            config: GpioConfig::default(),
+            config: GpioConfig::RESET,
         }
     }
 
-    pub fn mode(mut self, mode: GpioMode) -> Self {
+    pub const fn mode(mut self, mode: GpioMode) -> Self {
         self.config.mode = mode;
         self
     }
 
-    pub fn drive_strength(mut self, strength: GpioDriveStrength) -> Self {
+    pub const fn drive_strength(mut self, strength: GpioDriveStrength) -> Self {
         self.config.drive_strength = strength;
         self
     }
 
-    pub fn pull_up(mut self, pull_up: GpioPullUp) -> Self {
+    pub const fn pull_up(mut self, pull_up: GpioPullUp) -> Self {
         self.config.pull_up = pull_up;
         self
     }
 
-    pub fn pull_down(mut self, pull_down: GpioPullDown) -> Self {
+    pub const fn pull_down(mut self, pull_down: GpioPullDown) -> Self {
         self.config.pull_down = pull_down;
         self
     }
 
-    pub fn open_drain(mut self, open_drain: GpioOpenDrain) -> Self {
+    pub const fn open_drain(mut self, open_drain: GpioOpenDrain) -> Self {
         self.config.open_drain = open_drain;
         self
     }
 
-    pub fn debounce(mut self, debounce: GpioDebounce) -> Self {
+    pub const fn debounce(mut self, debounce: GpioDebounce) -> Self {
         self.config.debounce = debounce;
         self
     }
 
-    pub fn build(self) -> GpioConfig {
+    pub const fn build(self) -> GpioConfig {
         self.config
     }
 }
@@ -82,17 +91,20 @@ impl Default for GpioConfigBuilder {
     }
 }
 
-impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
-    crate::NPM1300<I2c, Delay>
-{
+#[maybe_async::maybe_async]
+impl<I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> crate::NPM1300<I2c, Delay> {
+    /// Configure a GPIO
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - GPIO pin, see [`GpioPin`]
+    /// * `config` - GPIO configuration, see [`GpioConfigBuilder`]
     pub async fn configure_gpio(
         &mut self,
-        pin: usize,
+        pin: GpioPin,
         config: GpioConfig,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        if pin > 4 {
-            panic!("GPIO pin number must be between 0 and 4");
-        }
+        let pin = pin.index();
         // GPIO mode configuration
         self.device
             .gpios()
@@ -141,23 +153,20 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Arguments
     ///
-    /// * `pin` - GPIO pin number (0-4)
+    /// * `pin` - GPIO pin, see [`GpioPin`]
     pub async fn get_gpio_status(
         &mut self,
-        pin: usize,
+        pin: GpioPin,
     ) -> Result<GpioStatus, crate::NPM1300Error<I2c::Error>> {
-        if pin > 4 {
-            panic!("GPIO pin number must be between 0 and 4");
-        }
         let status = self.device.gpios().gpiostatus().read_async().await?;
 
-        Ok(match pin {
-            0 => status.gpio_0_status().unwrap(),
-            1 => status.gpio_1_status().unwrap(),
-            2 => status.gpio_2_status().unwrap(),
-            3 => status.gpio_3_status().unwrap(),
-            4 => status.gpio_4_status().unwrap(),
-            _ => unreachable!(), // We already checked pin range above
-        })
+        match pin {
+            GpioPin::Gpio0 => status.gpio_0_status(),
+            GpioPin::Gpio1 => status.gpio_1_status(),
+            GpioPin::Gpio2 => status.gpio_2_status(),
+            GpioPin::Gpio3 => status.gpio_3_status(),
+            GpioPin::Gpio4 => status.gpio_4_status(),
+        }
+        .map_err(|_| crate::NPM1300Error::UnexpectedRegisterValue)
     }
 }
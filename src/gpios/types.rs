@@ -1,5 +1,5 @@
 /// GPIOs available on the nPM1300
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum Gpio {
     None = 0,
@@ -34,8 +34,75 @@ impl From<Gpio> for u8 {
     }
 }
 
+impl Gpio {
+    /// Get the pin of the GPIO, `None` for [`Gpio::None`]
+    pub fn pin(self) -> Option<GpioPin> {
+        match self {
+            Self::None => None,
+            Self::Gpio0 => Some(GpioPin::Gpio0),
+            Self::Gpio1 => Some(GpioPin::Gpio1),
+            Self::Gpio2 => Some(GpioPin::Gpio2),
+            Self::Gpio3 => Some(GpioPin::Gpio3),
+            Self::Gpio4 => Some(GpioPin::Gpio4),
+        }
+    }
+}
+
+// Add conversion from GpioPin
+impl From<GpioPin> for Gpio {
+    fn from(pin: GpioPin) -> Self {
+        match pin {
+            GpioPin::Gpio0 => Self::Gpio0,
+            GpioPin::Gpio1 => Self::Gpio1,
+            GpioPin::Gpio2 => Self::Gpio2,
+            GpioPin::Gpio3 => Self::Gpio3,
+            GpioPin::Gpio4 => Self::Gpio4,
+        }
+    }
+}
+
+/// GPIO pins of the nPM1300
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum GpioPin {
+    Gpio0 = 0,
+    Gpio1 = 1,
+    Gpio2 = 2,
+    Gpio3 = 3,
+    Gpio4 = 4,
+}
+
+impl GpioPin {
+    /// Get the register index of the pin
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Error for a GPIO pin number that does not exist on the nPM1300
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[error("invalid GPIO pin {0}, it must be between 0 and 4")]
+pub struct InvalidGpioPin(pub usize);
+
+// Add conversion from a pin number
+impl TryFrom<usize> for GpioPin {
+    type Error = InvalidGpioPin;
+
+    fn try_from(pin: usize) -> Result<Self, Self::Error> {
+        match pin {
+            0 => Ok(Self::Gpio0),
+            1 => Ok(Self::Gpio1),
+            2 => Ok(Self::Gpio2),
+            3 => Ok(Self::Gpio3),
+            4 => Ok(Self::Gpio4),
+            _ => Err(InvalidGpioPin(pin)),
+        }
+    }
+}
+
 /// GPIOs polarity
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum GpioPolarity {
     NotInverted = 0,
@@ -63,7 +130,7 @@ impl From<GpioPolarity> for u8 {
 }
 
 /// GPIO mode configuration
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum GpioMode {
     /// GPI Input
@@ -117,7 +184,7 @@ impl From<GpioMode> for u8 {
 }
 
 /// GPIO Drive Strength Configuration
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum GpioDriveStrength {
     /// 1mA
@@ -145,7 +212,7 @@ impl From<GpioDriveStrength> for u8 {
 }
 
 /// GPIO Pull-up Configuration
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum GpioPullUp {
     Disable = 0,
@@ -171,7 +238,7 @@ impl From<GpioPullUp> for u8 {
 }
 
 /// GPIO Pull-down Configuration
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum GpioPullDown {
     Disable = 0,
@@ -198,7 +265,7 @@ impl From<GpioPullDown> for u8 {
 }
 
 /// GPIO Open Drain Configuration
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum GpioOpenDrain {
     Disable = 0,
@@ -224,7 +291,7 @@ impl From<GpioOpenDrain> for u8 {
 }
 
 /// GPIO Debounce Configuration
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum GpioDebounce {
     Disable = 0,
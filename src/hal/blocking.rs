@@ -0,0 +1,113 @@
+use device_driver::{
+    CommandInterface, CommandOperation, FieldSet, ReadCapability, RegisterInterface,
+    RegisterOperation, WriteCapability,
+};
+
+/// Blocking register read
+///
+/// The method keeps the name of its async counterpart, so the driver code shared with the
+/// async driver can call `read_async` once `maybe_async` has removed the `.await`.
+pub trait BlockingRead {
+    type Register;
+    type Error;
+
+    fn read_async(&mut self) -> Result<Self::Register, Self::Error>;
+}
+
+/// Blocking register write, under the method name of its async counterpart
+pub trait BlockingWrite {
+    type Register;
+    type Error;
+
+    fn write_async<R>(
+        &mut self,
+        f: impl FnOnce(&mut Self::Register) -> R,
+    ) -> Result<R, Self::Error>;
+}
+
+/// Blocking register modify, under the method name of its async counterpart
+pub trait BlockingModify {
+    type Register;
+    type Error;
+
+    fn modify_async<R>(
+        &mut self,
+        f: impl FnOnce(&mut Self::Register) -> R,
+    ) -> Result<R, Self::Error>;
+}
+
+/// Blocking command dispatch, under the method name of its async counterpart
+pub trait BlockingDispatch {
+    type InFieldSet;
+    type Error;
+
+    fn dispatch_async(self, f: impl FnOnce(&mut Self::InFieldSet)) -> Result<(), Self::Error>;
+}
+
+impl<Interface, AddressType: Copy, Register: FieldSet, Access> BlockingRead
+    for RegisterOperation<'_, Interface, AddressType, Register, Access>
+where
+    Interface: RegisterInterface<AddressType = AddressType>,
+    Access: ReadCapability,
+{
+    type Register = Register;
+    type Error = Interface::Error;
+
+    fn read_async(&mut self) -> Result<Register, Interface::Error> {
+        self.read()
+    }
+}
+
+impl<Interface, AddressType: Copy, Register: FieldSet, Access> BlockingWrite
+    for RegisterOperation<'_, Interface, AddressType, Register, Access>
+where
+    Interface: RegisterInterface<AddressType = AddressType>,
+    Access: WriteCapability,
+{
+    type Register = Register;
+    type Error = Interface::Error;
+
+    fn write_async<R>(
+        &mut self,
+        f: impl FnOnce(&mut Register) -> R,
+    ) -> Result<R, Interface::Error> {
+        self.write(f)
+    }
+}
+
+impl<Interface, AddressType: Copy, Register: FieldSet, Access> BlockingModify
+    for RegisterOperation<'_, Interface, AddressType, Register, Access>
+where
+    Interface: RegisterInterface<AddressType = AddressType>,
+    Access: ReadCapability + WriteCapability,
+{
+    type Register = Register;
+    type Error = Interface::Error;
+
+    fn modify_async<R>(
+        &mut self,
+        f: impl FnOnce(&mut Register) -> R,
+    ) -> Result<R, Interface::Error> {
+        self.modify(f)
+    }
+}
+
+impl<Interface, AddressType: Copy, InFieldSet: FieldSet> BlockingDispatch
+    for CommandOperation<'_, Interface, AddressType, InFieldSet, ()>
+where
+    Interface: CommandInterface<AddressType = AddressType>,
+{
+    type InFieldSet = InFieldSet;
+    type Error = Interface::Error;
+
+    fn dispatch_async(self, f: impl FnOnce(&mut InFieldSet)) -> Result<(), Interface::Error> {
+        self.dispatch(f)
+    }
+}
+
+/// Brings the blocking register and command operations into scope
+pub mod prelude {
+    pub use super::{
+        BlockingDispatch as _, BlockingModify as _, BlockingRead as _, BlockingWrite as _,
+    };
+}
@@ -0,0 +1,23 @@
+//! HAL traits used by the driver
+//!
+//! The driver is written once against the async traits. With the `blocking` feature,
+//! [`maybe_async`] turns every driver method into a blocking one and these aliases switch to
+//! the blocking traits of `embedded-hal` and `device-driver`.
+
+#[cfg(not(feature = "blocking"))]
+pub use device_driver::{
+    AsyncCommandInterface as CommandInterface, AsyncRegisterInterface as RegisterInterface,
+};
+#[cfg(not(feature = "blocking"))]
+pub use embedded_hal_async::{delay::DelayNs, digital::Wait, i2c::I2c};
+
+#[cfg(feature = "blocking")]
+pub use device_driver::{CommandInterface, RegisterInterface};
+#[cfg(feature = "blocking")]
+pub use embedded_hal::{delay::DelayNs, digital::InputPin, i2c::I2c};
+
+#[cfg(feature = "blocking")]
+mod blocking;
+
+#[cfg(feature = "blocking")]
+pub use blocking::prelude;
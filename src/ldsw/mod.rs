@@ -1,7 +1,10 @@
+#[cfg(feature = "blocking")]
+use crate::hal::prelude::*;
 use crate::{
     common::Task,
     field_sets::Ldswstatus,
-    //gpios::{Gpio, GpioPolarity},
+    gpios::{Gpio, GpioMode, GpioPolarity},
+    Ldsw1Activedischarge, Ldsw2Activedischarge,
 };
 
 mod types;
@@ -9,59 +12,31 @@ mod types;
 // Re-export everything in types.rs
 pub use types::*;
 
-/// Convert a GPIO enum value to its register index
-///
-/// GPIOs are 1-indexed in the nPM1300 so we subtract 1 from the GPIO number
-/// to get the register index
-/*
-fn gpio_to_register_index(gpio: Gpio) -> usize {
-    usize::from(u8::from(gpio) - 1)
-}
-
-pub struct Config {
-    /// GPIO to enable/disable LDSW regulators
-    pub gpio_ldsw_enable_control: Gpio,
-    /// GPIO enable/disable polarity
-    pub gpio_ldsw_enable_control_polarity: GpioPolarity,
-}
-
-
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            gpio_ldsw_enable_control: Gpio::None,
-            gpio_ldsw_enable_control_polarity: GpioPolarity::NotInverted,
-        }
-    }
-}
-*/
-
-impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
-    crate::NPM1300<I2c, Delay>
-{
+#[maybe_async::maybe_async]
+impl<I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> crate::NPM1300<I2c, Delay> {
     /// Enable or disable a LDSW regulator
     ///
     /// # Arguments
     ///
-    /// * `ldsw_index` - Index of the LDSW regulator (0 for LDSW1, 1 for LDSW2)
+    /// * `ldsw` - The load switch, see [`Ldsw`]
     /// * `enable` - true to enable the regulator, false to disable it
     async fn control_ldsw_power(
         &mut self,
-        ldsw_index: u8,
+        ldsw: Ldsw,
         enable: bool,
     ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
         if enable {
             // Enable the regulator
             self.device
                 .ldsw()
-                .ldswset(ldsw_index.into())
+                .ldswset(ldsw.index())
                 .dispatch_async(|command| command.set_taskldswset(Task::Trigger))
                 .await
         } else {
             // Disable the regulator
             self.device
                 .ldsw()
-                .ldswclr(ldsw_index.into())
+                .ldswclr(ldsw.index())
                 .dispatch_async(|command| command.set_taskldswclr(Task::Trigger))
                 .await
         }
@@ -69,22 +44,257 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
 
     /// Enable LDSW1
     pub async fn enable_ldsw1(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.control_ldsw_power(0, true).await
+        self.control_ldsw_power(Ldsw::Ldsw1, true).await
     }
 
     /// Disable LDSW1
     pub async fn disable_ldsw1(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.control_ldsw_power(0, false).await
+        self.control_ldsw_power(Ldsw::Ldsw1, false).await
     }
 
     /// Enable LDSW2
     pub async fn enable_ldsw2(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.control_ldsw_power(1, true).await
+        self.control_ldsw_power(Ldsw::Ldsw2, true).await
     }
 
     /// Disable LDSW2
     pub async fn disable_ldsw2(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
-        self.control_ldsw_power(1, false).await
+        self.control_ldsw_power(Ldsw::Ldsw2, false).await
+    }
+
+    /// Set LDSW1 operating mode
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - Load switch or LDO, see [`LdswMode`]
+    pub async fn set_ldsw1_mode(
+        &mut self,
+        mode: LdswMode,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.device
+            .ldsw()
+            .ldsw_1_ldosel()
+            .write_async(|reg| reg.set_value(mode))
+            .await
+    }
+
+    /// Set LDSW2 operating mode
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - Load switch or LDO, see [`LdswMode`]
+    pub async fn set_ldsw2_mode(
+        &mut self,
+        mode: LdswMode,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.device
+            .ldsw()
+            .ldsw_2_ldosel()
+            .write_async(|reg| reg.set_value(mode))
+            .await
+    }
+
+    /// Set LDO1 output voltage
+    ///
+    /// Only used when LDSW1 is in [`LdswMode::Ldo`].
+    ///
+    /// # Arguments
+    ///
+    /// * `voltage` - The voltage to set for LDO1, a [`LdswVoltage`] or [`Millivolts`](crate::units::Millivolts)
+    pub async fn set_ldo1_voltage<V>(
+        &mut self,
+        voltage: V,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>>
+    where
+        V: TryInto<LdswVoltage>,
+        crate::NPM1300Error<I2c::Error>: From<V::Error>,
+    {
+        let voltage = voltage.try_into()?;
+        self.device
+            .ldsw()
+            .ldsw_1_voutsel()
+            .write_async(|reg| reg.set_value(voltage))
+            .await
+    }
+
+    /// Set LDO2 output voltage
+    ///
+    /// Only used when LDSW2 is in [`LdswMode::Ldo`].
+    ///
+    /// # Arguments
+    ///
+    /// * `voltage` - The voltage to set for LDO2, a [`LdswVoltage`] or [`Millivolts`](crate::units::Millivolts)
+    pub async fn set_ldo2_voltage<V>(
+        &mut self,
+        voltage: V,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>>
+    where
+        V: TryInto<LdswVoltage>,
+        crate::NPM1300Error<I2c::Error>: From<V::Error>,
+    {
+        let voltage = voltage.try_into()?;
+        self.device
+            .ldsw()
+            .ldsw_2_voutsel()
+            .write_async(|reg| reg.set_value(voltage))
+            .await
+    }
+
+    /// Set LDSW1 soft-start current limit
+    ///
+    /// # Arguments
+    ///
+    /// * `current` - The soft-start current limit, see [`LdswSoftStartCurrent`]
+    pub async fn set_ldsw1_soft_start_current(
+        &mut self,
+        current: LdswSoftStartCurrent,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.device
+            .ldsw()
+            .ldswconfig()
+            .modify_async(|reg| reg.set_ldsw_1_softstartsel(current))
+            .await
+    }
+
+    /// Set LDSW2 soft-start current limit
+    ///
+    /// # Arguments
+    ///
+    /// * `current` - The soft-start current limit, see [`LdswSoftStartCurrent`]
+    pub async fn set_ldsw2_soft_start_current(
+        &mut self,
+        current: LdswSoftStartCurrent,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.device
+            .ldsw()
+            .ldswconfig()
+            .modify_async(|reg| reg.set_ldsw_2_softstartsel(current))
+            .await
+    }
+
+    /// Enable LDSW1 active discharge
+    pub async fn enable_ldsw1_active_discharge(
+        &mut self,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.device
+            .ldsw()
+            .ldswconfig()
+            .modify_async(|reg| reg.set_ldsw_1_activedischarge(Ldsw1Activedischarge::Enabled))
+            .await
+    }
+
+    /// Disable LDSW1 active discharge
+    pub async fn disable_ldsw1_active_discharge(
+        &mut self,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.device
+            .ldsw()
+            .ldswconfig()
+            .modify_async(|reg| reg.set_ldsw_1_activedischarge(Ldsw1Activedischarge::Disabled))
+            .await
+    }
+
+    /// Enable LDSW2 active discharge
+    pub async fn enable_ldsw2_active_discharge(
+        &mut self,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.device
+            .ldsw()
+            .ldswconfig()
+            .modify_async(|reg| reg.set_ldsw_2_activedischarge(Ldsw2Activedischarge::Enabled))
+            .await
+    }
+
+    /// Disable LDSW2 active discharge
+    pub async fn disable_ldsw2_active_discharge(
+        &mut self,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.device
+            .ldsw()
+            .ldswconfig()
+            .modify_async(|reg| reg.set_ldsw_2_activedischarge(Ldsw2Activedischarge::Disabled))
+            .await
+    }
+
+    /// Configure GPIO enable control for a LDSW regulator
+    ///
+    /// # Arguments
+    ///
+    /// * `ldsw` - The load switch, see [`Ldsw`]
+    /// * `gpio` - GPIO to control the regulator, [`Gpio::None`] to disable GPIO control
+    /// * `polarity` - Polarity of GPIO
+    async fn configure_ldsw_gpio_enable_control(
+        &mut self,
+        ldsw: Ldsw,
+        gpio: Gpio,
+        polarity: GpioPolarity,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        if let Some(pin) = gpio.pin() {
+            // Configure GPIO mode as input
+            self.device
+                .gpios()
+                .gpiomode(pin.index())
+                .write_async(|reg| reg.set_gpiomode(GpioMode::GpiInput))
+                .await?;
+        }
+
+        // Configure GPIO and its polarity
+        match ldsw {
+            Ldsw::Ldsw1 => {
+                self.device
+                    .ldsw()
+                    .ldsw_1_gpisel()
+                    .write_async(|reg| {
+                        reg.set_ldsw_1_gpisel(gpio);
+                        reg.set_ldsw_1_gpiinv(polarity);
+                    })
+                    .await
+            }
+            Ldsw::Ldsw2 => {
+                self.device
+                    .ldsw()
+                    .ldsw_2_gpisel()
+                    .write_async(|reg| {
+                        reg.set_ldsw_2_gpisel(gpio);
+                        reg.set_ldsw_2_gpiinv(polarity);
+                    })
+                    .await
+            }
+        }
+    }
+
+    /// Configure LDSW1 GPIO enable control
+    ///
+    /// The GPIO is configured as an input. While GPIO control is active, LDSW1 follows the
+    /// level of the GPIO.
+    ///
+    /// # Arguments
+    /// * `gpio` - GPIO to enable, [`Gpio::None`] to disable GPIO control
+    /// * `polarity` - Polarity of GPIO
+    pub async fn set_ldsw1_gpio_control(
+        &mut self,
+        gpio: Gpio,
+        polarity: GpioPolarity,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.configure_ldsw_gpio_enable_control(Ldsw::Ldsw1, gpio, polarity)
+            .await
+    }
+
+    /// Configure LDSW2 GPIO enable control
+    ///
+    /// The GPIO is configured as an input. While GPIO control is active, LDSW2 follows the
+    /// level of the GPIO.
+    ///
+    /// # Arguments
+    /// * `gpio` - GPIO to enable, [`Gpio::None`] to disable GPIO control
+    /// * `polarity` - Polarity of GPIO
+    pub async fn set_ldsw2_gpio_control(
+        &mut self,
+        gpio: Gpio,
+        polarity: GpioPolarity,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.configure_ldsw_gpio_enable_control(Ldsw::Ldsw2, gpio, polarity)
+            .await
     }
 
     /// Get LDSW status
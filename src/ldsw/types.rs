@@ -1,5 +1,22 @@
+use crate::units::{nearest_step_index, step_index, Millivolts, UnitError};
+
+/// Load switches of the nPM1300
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum Ldsw {
+    Ldsw1 = 0,
+    Ldsw2 = 1,
+}
+
+impl Ldsw {
+    /// Get the register index of the load switch
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
 /// LDO regulator voltages available on the nPM1300
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum LdswVoltage {
     V1_0 = 0,
@@ -69,3 +86,95 @@ impl From<LdswVoltage> for u8 {
         voltage as u8
     }
 }
+
+impl LdswVoltage {
+    /// Get the voltage nearest to `voltage`, clamped to the 1.0 V to 3.3 V range
+    pub fn nearest(voltage: Millivolts) -> Self {
+        Self::try_from(nearest_step_index(voltage.0 as i32, 1000, 3300, 100)).unwrap_or(Self::V3_3)
+    }
+}
+
+// Add conversion from Millivolts to LdswVoltage, rejecting voltages between steps
+impl TryFrom<Millivolts> for LdswVoltage {
+    type Error = UnitError;
+
+    fn try_from(voltage: Millivolts) -> Result<Self, Self::Error> {
+        let index = step_index(voltage.0 as i32, 1000, 3300, 100)?;
+        Self::try_from(index).map_err(|_| UnitError::OutOfRange)
+    }
+}
+
+// Add conversion from LdswVoltage to Millivolts
+impl From<LdswVoltage> for Millivolts {
+    fn from(voltage: LdswVoltage) -> Self {
+        Millivolts(1000 + 100 * voltage as u16)
+    }
+}
+
+/// LDSW operating mode
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum LdswMode {
+    /// Load switch, the output follows the input
+    LoadSwitch = 0,
+    /// LDO regulator, the output voltage is set by [`LdswVoltage`]
+    Ldo = 1,
+}
+
+// Add conversion from u8 to LdswMode
+impl TryFrom<u8> for LdswMode {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::LoadSwitch),
+            1 => Ok(Self::Ldo),
+            _ => Err(()),
+        }
+    }
+}
+
+// Add conversion from LdswMode to u8
+impl From<LdswMode> for u8 {
+    fn from(mode: LdswMode) -> Self {
+        mode as u8
+    }
+}
+
+/// LDSW soft-start current limit
+///
+/// Limits the inrush current when the load switch or LDO is enabled.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum LdswSoftStartCurrent {
+    /// 10 mA
+    MA10 = 0,
+    /// 20 mA
+    MA20 = 1,
+    /// 35 mA
+    MA35 = 2,
+    /// 50 mA
+    MA50 = 3,
+}
+
+// Add conversion from u8 to LdswSoftStartCurrent
+impl TryFrom<u8> for LdswSoftStartCurrent {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::MA10),
+            1 => Ok(Self::MA20),
+            2 => Ok(Self::MA35),
+            3 => Ok(Self::MA50),
+            _ => Err(()),
+        }
+    }
+}
+
+// Add conversion from LdswSoftStartCurrent to u8
+impl From<LdswSoftStartCurrent> for u8 {
+    fn from(current: LdswSoftStartCurrent) -> Self {
+        current as u8
+    }
+}
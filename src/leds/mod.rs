@@ -1,13 +1,14 @@
 use crate::common::Task;
+#[cfg(feature = "blocking")]
+use crate::hal::prelude::*;
 
 mod types;
 
 // Re-export everything in types.rs
 pub use types::*;
 
-impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
-    crate::NPM1300<I2c, Delay>
-{
+#[maybe_async::maybe_async]
+impl<I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> crate::NPM1300<I2c, Delay> {
     /// Configure LED driver
     ///
     /// # Arguments
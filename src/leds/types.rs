@@ -1,5 +1,5 @@
 /// LED mode configuration
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum LedMode {
     /// Error condition from charger
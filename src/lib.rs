@@ -1,28 +1,44 @@
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 
-use device_driver::AsyncRegisterInterface;
+use hal::RegisterInterface;
 
 pub mod common;
 
 pub mod adc;
+#[cfg(feature = "float")]
+pub mod battery;
 pub mod buck;
-pub mod ldsw;
 pub mod charger;
+pub mod config;
+#[cfg(feature = "decode")]
+pub mod decode;
+pub mod events;
+#[cfg(feature = "float")]
+pub mod fuel_gauge;
 pub mod gpios;
+mod hal;
+pub mod ldsw;
 pub mod leds;
 pub mod pof;
+pub mod reset;
+pub mod sequence;
 pub mod ship;
+#[cfg(feature = "sim")]
+pub mod sim;
+#[cfg(all(feature = "split", not(feature = "blocking")))]
+pub mod split;
 pub mod sysreg;
+pub mod timer;
+pub mod units;
 
-const ADDR: u8 = 0x6B;
+/// Default I2C address of the nPM1300
+pub const DEFAULT_ADDRESS: u8 = 0x6B;
 
 #[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum NPM1300Error<I2cError> {
     #[error("i2c error: {0:?}")]
     I2c(I2cError),
-    #[error("charger current {0:?} is too high")]
-    ChargerCurrentTooHigh(u16),
     #[error("invalid NTC threshold")]
     InvalidNtcThreshold,
     #[error("invalid die temperature stop/resume threshold")]
@@ -37,29 +53,88 @@ pub enum NPM1300Error<I2cError> {
     InvalidPofVsysThreshold,
     #[error("invalid discharge current value")]
     InvalidDischargeCurrentValue { msb: u8, lsb: u8 },
+    #[error("failed to wait on the interrupt pin")]
+    InterruptPin,
+    #[error("invalid timer mode")]
+    InvalidTimerMode,
+    #[error("invalid hibernate period, it must be between 2 ms and about 74.5 hours")]
+    InvalidHibernatePeriod,
+    #[error("invalid power cycle delay, it must be between 2 ms and about 74.5 hours")]
+    InvalidPowerCycleDelay,
+    #[cfg(feature = "float")]
+    #[error("invalid battery model: {0}")]
+    InvalidBatteryModel(battery::BatteryModelError),
+    #[error("unsupported value: {0}")]
+    UnsupportedValue(units::UnitError),
+    #[error("invalid GPIO pin {0}, it must be between 0 and 4")]
+    InvalidGpioPin(usize),
+    #[error("invalid index {0}")]
+    InvalidIndex(usize),
+    #[error("unexpected register value")]
+    UnexpectedRegisterValue,
+    #[error("timed out waiting for a status")]
+    Timeout,
+}
+
+impl<I2cError> From<gpios::InvalidGpioPin> for NPM1300Error<I2cError> {
+    fn from(error: gpios::InvalidGpioPin) -> Self {
+        NPM1300Error::InvalidGpioPin(error.0)
+    }
+}
+
+impl<I2cError> From<units::UnitError> for NPM1300Error<I2cError> {
+    fn from(error: units::UnitError) -> Self {
+        NPM1300Error::UnsupportedValue(error)
+    }
+}
+
+// Setters taking a register setting or a unit value convert an infallible setting
+impl<I2cError> From<core::convert::Infallible> for NPM1300Error<I2cError> {
+    fn from(error: core::convert::Infallible) -> Self {
+        match error {}
+    }
 }
 
 #[derive(Debug)]
-pub struct DeviceInterface<I2c: embedded_hal_async::i2c::I2c> {
+pub struct DeviceInterface<I2c: hal::I2c> {
     pub i2c: I2c,
+    /// 7-bit I2C address of the nPM1300
+    pub address: u8,
 }
 
-pub struct NPM1300<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs> {
+pub struct NPM1300<I2c: hal::I2c, Delay: hal::DelayNs> {
     device: Device<DeviceInterface<I2c>>,
     delay: Delay,
-    ntc_beta: Option<f32>,
+    ntc_beta: Option<u16>,
 }
 
-impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
-    NPM1300<I2c, Delay>
-{
+impl<I2c: hal::I2c, Delay: hal::DelayNs> NPM1300<I2c, Delay> {
+    /// Create a new driver using the default I2C address, see [`DEFAULT_ADDRESS`]
     pub fn new(i2c: I2c, delay: Delay) -> Self {
+        Self::new_with_address(i2c, delay, DEFAULT_ADDRESS)
+    }
+
+    /// Create a new driver for an nPM1300 at a custom I2C address
+    ///
+    /// Used when several nPM1300s share a bus, for example behind an address translator.
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C bus
+    /// * `delay` - The delay provider
+    /// * `address` - The 7-bit I2C address of the nPM1300
+    pub fn new_with_address(i2c: I2c, delay: Delay, address: u8) -> Self {
         Self {
-            device: Device::new(DeviceInterface { i2c }),
+            device: Device::new(DeviceInterface { i2c, address }),
             delay,
             ntc_beta: None,
         }
     }
+
+    /// Get the I2C address of the nPM1300
+    pub fn address(&self) -> u8 {
+        self.device.interface.address
+    }
 }
 
 device_driver::create_device!(
@@ -67,9 +142,8 @@ device_driver::create_device!(
     manifest: "device.yaml"
 );
 
-impl<I2c: embedded_hal_async::i2c::I2c> device_driver::AsyncRegisterInterface
-    for DeviceInterface<I2c>
-{
+#[maybe_async::maybe_async(AFIT)]
+impl<I2c: hal::I2c> RegisterInterface for DeviceInterface<I2c> {
     type AddressType = u16;
 
     type Error = NPM1300Error<I2c::Error>;
@@ -81,7 +155,10 @@ impl<I2c: embedded_hal_async::i2c::I2c> device_driver::AsyncRegisterInterface
         data: &[u8],
     ) -> Result<(), Self::Error> {
         let buf = [(address >> 8) as u8, address as u8, data[0]];
-        self.i2c.write(ADDR, &buf).await.map_err(NPM1300Error::I2c)
+        self.i2c
+            .write(self.address, &buf)
+            .await
+            .map_err(NPM1300Error::I2c)
     }
 
     async fn read_register(
@@ -91,15 +168,14 @@ impl<I2c: embedded_hal_async::i2c::I2c> device_driver::AsyncRegisterInterface
         data: &mut [u8],
     ) -> Result<(), Self::Error> {
         self.i2c
-            .write_read(ADDR, &[(address >> 8) as u8, address as u8], data)
+            .write_read(self.address, &[(address >> 8) as u8, address as u8], data)
             .await
             .map_err(NPM1300Error::I2c)
     }
 }
 
-impl<I2c: embedded_hal_async::i2c::I2c> device_driver::AsyncCommandInterface
-    for DeviceInterface<I2c>
-{
+#[maybe_async::maybe_async(AFIT)]
+impl<I2c: hal::I2c> hal::CommandInterface for DeviceInterface<I2c> {
     type AddressType = u16;
 
     type Error = NPM1300Error<I2c::Error>;
@@ -1,8 +1,74 @@
-use crate::{Pofena, Pofwarnpolarity, VsysThreshold};
+#[cfg(feature = "blocking")]
+use crate::hal::prelude::*;
+use crate::{
+    units::{nearest_step_index, step_index, Millivolts, UnitError},
+    Pofena, Pofwarnpolarity, VsysThreshold,
+};
 
-impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
-    crate::NPM1300<I2c, Delay>
-{
+impl VsysThreshold {
+    /// Get the threshold nearest to `voltage`, clamped to the 2.6 V to 3.5 V range
+    pub fn nearest(voltage: Millivolts) -> Self {
+        Self::from_index(nearest_step_index(voltage.0 as i32, 2600, 3500, 100))
+    }
+
+    /// Get the threshold from its index in the 2.6 V to 3.5 V range
+    fn from_index(index: u8) -> Self {
+        match index {
+            0 => Self::V26,
+            1 => Self::V27,
+            2 => Self::V28,
+            3 => Self::V29,
+            4 => Self::V30,
+            5 => Self::V31,
+            6 => Self::V32,
+            7 => Self::V33,
+            8 => Self::V34,
+            _ => Self::V35,
+        }
+    }
+}
+
+// Add conversion from Millivolts, rejecting voltages between steps
+impl TryFrom<Millivolts> for VsysThreshold {
+    type Error = UnitError;
+
+    fn try_from(voltage: Millivolts) -> Result<Self, Self::Error> {
+        Ok(Self::from_index(step_index(
+            voltage.0 as i32,
+            2600,
+            3500,
+            100,
+        )?))
+    }
+}
+
+// Add conversion to Millivolts
+impl From<VsysThreshold> for Millivolts {
+    fn from(threshold: VsysThreshold) -> Self {
+        Millivolts(match threshold {
+            VsysThreshold::V26 => 2600,
+            VsysThreshold::V27 => 2700,
+            VsysThreshold::V28 => 2800,
+            VsysThreshold::V29 => 2900,
+            VsysThreshold::V30 => 3000,
+            VsysThreshold::V31 => 3100,
+            VsysThreshold::V32 => 3200,
+            VsysThreshold::V33 => 3300,
+            VsysThreshold::V34 => 3400,
+            VsysThreshold::V35 => 3500,
+            // Unused values select 2.8 V
+            VsysThreshold::Unused10
+            | VsysThreshold::Unused11
+            | VsysThreshold::Unused12
+            | VsysThreshold::Unused13
+            | VsysThreshold::Unused14
+            | VsysThreshold::Unused15 => 2800,
+        })
+    }
+}
+
+#[maybe_async::maybe_async]
+impl<I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> crate::NPM1300<I2c, Delay> {
     /// Enable or disable power-failure detection
     ///
     /// # Arguments
@@ -68,7 +134,8 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Arguments
     ///
-    /// * `threshold` - The VSYS threshold voltage level to trigger power failure detection
+    /// * `threshold` - The VSYS threshold voltage level to trigger power failure detection, a
+    ///   [`VsysThreshold`] or [`Millivolts`]
     ///
     /// # Safety
     ///
@@ -84,30 +151,19 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     /// Returns `Ok(())` if the threshold was set successfully, or `Err(NPM1300Error::InvalidPofVsysThreshold)`
     /// if the requested threshold is higher than the current VSYS voltage, or
     /// Err(NPM1300Error::I2c) if there was an error communicating with the device.
-    pub async fn set_vsys_threshold(
+    pub async fn set_vsys_threshold<T>(
         &mut self,
-        threshold: VsysThreshold,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        threshold: T,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>>
+    where
+        T: TryInto<VsysThreshold>,
+        crate::NPM1300Error<I2c::Error>: From<T::Error>,
+    {
+        let threshold = threshold.try_into()?;
+
         // Check if the threshold is safe
-        let vsys = self.measure_vsys().await?;
-        let threshold_voltage = match threshold {
-            VsysThreshold::V26 => 2.6,
-            VsysThreshold::V27 => 2.7,
-            VsysThreshold::V28 => 2.8,
-            VsysThreshold::V29 => 2.9,
-            VsysThreshold::V30 => 3.0,
-            VsysThreshold::V31 => 3.1,
-            VsysThreshold::V32 => 3.2,
-            VsysThreshold::V33 => 3.3,
-            VsysThreshold::V34 => 3.4,
-            VsysThreshold::V35 => 3.5,
-            VsysThreshold::Unused10 => 2.8,
-            VsysThreshold::Unused11 => 2.8,
-            VsysThreshold::Unused12 => 2.8,
-            VsysThreshold::Unused13 => 2.8,
-            VsysThreshold::Unused14 => 2.8,
-            VsysThreshold::Unused15 => 2.8,
-        };
+        let vsys = self.measure_vsys_millivolts().await?;
+        let threshold_voltage = Millivolts::from(threshold);
         if vsys < threshold_voltage {
             return Err(crate::NPM1300Error::InvalidPofVsysThreshold);
         }
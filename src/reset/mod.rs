@@ -0,0 +1,131 @@
+#[cfg(feature = "blocking")]
+use crate::hal::prelude::*;
+use crate::{
+    charger::{ChargerErrorReason, ChargerSensorValueDuringError},
+    common::Task,
+    timer::{TimerPeriod, WatchdogAction},
+};
+
+mod types;
+
+// Re-export everything in types.rs
+pub use types::*;
+
+#[maybe_async::maybe_async]
+impl<I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> crate::NPM1300<I2c, Delay> {
+    /// Get the cause of the last reset
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ResetReason)` - The latched reset causes
+    /// * `Err(NPM1300Error)` - An error occurred while reading the error log
+    pub async fn get_reset_reason(
+        &mut self,
+    ) -> Result<ResetReason, crate::NPM1300Error<I2c::Error>> {
+        let cause = self.device.errlog().rstcause().read_async().await?;
+
+        Ok(ResetReason {
+            ship_mode_exit: cause.shipmodeexit() == 1,
+            boot_monitor_timeout: cause.bootmonitortimeout() == 1,
+            watchdog_timeout: cause.watchdogtimeout() == 1,
+            long_press_timeout: cause.longpresstimeout() == 1,
+            thermal_shutdown: cause.thermalshutdown() == 1,
+            vsys_low: cause.vsyslow() == 1,
+            software_reset: cause.swreset() == 1,
+        })
+    }
+
+    /// Get the error log
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ErrorLog)` - The reset cause and the charger errors latched at reset
+    /// * `Err(NPM1300Error)` - An error occurred while reading the error log
+    pub async fn get_error_log(&mut self) -> Result<ErrorLog, crate::NPM1300Error<I2c::Error>> {
+        let reset_reason = self.get_reset_reason().await?;
+        let status = self.device.errlog().chargererrreason().read_async().await?;
+        let sensor = self.device.errlog().chargererrsensor().read_async().await?;
+
+        Ok(ErrorLog {
+            reset_reason,
+            charger_error_reason: ChargerErrorReason {
+                ntc_sensor_error: status.ntcsensorerror() == 1,
+                vbat_sensor_error: status.vbatsensorerror() == 1,
+                vbat_low_error: status.vbatlow() == 1,
+                vtrickle_error: status.vtrickle() == 1,
+                measurement_timeout_error: status.meastimeout() == 1,
+                charge_timeout_error: status.chargetimeout() == 1,
+                trickle_timeout_error: status.trickletimeout() == 1,
+            },
+            charger_sensor_value: ChargerSensorValueDuringError {
+                sensor_ntc_cold: sensor.sensorntccold() == 1,
+                sensor_ntc_cool: sensor.sensorntccool() == 1,
+                sensor_ntc_warm: sensor.sensorntcwarm() == 1,
+                sensor_ntc_hot: sensor.sensorntchot() == 1,
+                sensor_vterm: sensor.sensorvterm() == 1,
+                sensor_recharge: sensor.sensorrecharge() == 1,
+                sensor_vtrickle: sensor.sensorvtrickle() == 1,
+                sensor_vbat_low: sensor.sensorvbatlow() == 1,
+            },
+        })
+    }
+
+    /// Clear the error log
+    ///
+    /// Clears the reset cause and the charger errors latched at reset, so the next boot only
+    /// reports causes of resets that happen from now on.
+    pub async fn clear_error_log(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.device
+            .errlog()
+            .taskclrerrlog()
+            .dispatch_async(|command| command.set_taskclrerrlog(Task::Trigger))
+            .await
+    }
+
+    /// Reset the nPM1300
+    ///
+    /// The nPM1300 resets immediately, power cycling BUCK1, BUCK2 and the load switches and
+    /// asserting any GPIO configured as [`crate::gpios::GpioMode::GpoReset`]. If the host is
+    /// supplied by the nPM1300 it loses power, so anything that must survive, such as pending
+    /// flash writes, must be completed before calling this.
+    ///
+    /// The reset task is the last transaction sent to the nPM1300. Depending on how fast the
+    /// rails drop, the host may not see this function return.
+    pub async fn software_reset(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.device
+            .main()
+            .taskswreset()
+            .dispatch_async(|command| command.set_taskswreset(Task::Trigger))
+            .await
+    }
+
+    /// Power cycle the system after a delay
+    ///
+    /// Arms the watchdog in reset mode without ever kicking it, so the nPM1300 resets and
+    /// power cycles all rails when `delay` elapses. The timer configuration is fully written
+    /// and strobed before the timer starts, so the host is free to finish its own work,
+    /// such as flushing flash writes, while the delay runs.
+    ///
+    /// The power cycle can be cancelled with [`Self::stop_timer`]. Calling
+    /// [`Self::kick_watchdog`] postpones it by `delay`.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - Time until the power cycle, between [`TimerPeriod::MIN`] and
+    ///   [`TimerPeriod::MAX`]. Rounded up to the next timer tick.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The power cycle is scheduled
+    /// * `Err(NPM1300Error::InvalidPowerCycleDelay)` - `delay` is outside the timer range
+    /// * `Err(NPM1300Error)` - An error occurred while configuring the timer
+    pub async fn power_cycle_after(
+        &mut self,
+        delay: core::time::Duration,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        let period = TimerPeriod::try_from(delay)
+            .map_err(|_| crate::NPM1300Error::InvalidPowerCycleDelay)?;
+
+        self.enable_watchdog(period, WatchdogAction::Reset).await
+    }
+}
@@ -0,0 +1,52 @@
+use crate::charger::{ChargerErrorReason, ChargerSensorValueDuringError};
+
+/// Cause of the last nPM1300 reset
+///
+/// Several causes can be latched at once if the log was not cleared between resets. If no
+/// cause is set, the last reset was a power-on reset.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct ResetReason {
+    /// Device exited ship or hibernate mode
+    pub ship_mode_exit: bool,
+    /// Boot monitor timed out
+    pub boot_monitor_timeout: bool,
+    /// Watchdog timed out
+    pub watchdog_timeout: bool,
+    /// SHPHLD button was held long enough to trigger a reset
+    pub long_press_timeout: bool,
+    /// Die temperature exceeded the thermal shutdown threshold
+    pub thermal_shutdown: bool,
+    /// VSYS dropped below the power-fail threshold
+    pub vsys_low: bool,
+    /// Software reset was requested
+    pub software_reset: bool,
+}
+
+impl ResetReason {
+    /// Returns `true` if no reset cause is latched, i.e. the last reset was a power-on reset
+    pub fn is_power_on_reset(&self) -> bool {
+        !(self.ship_mode_exit
+            || self.boot_monitor_timeout
+            || self.watchdog_timeout
+            || self.long_press_timeout
+            || self.thermal_shutdown
+            || self.vsys_low
+            || self.software_reset)
+    }
+}
+
+/// Contents of the nPM1300 error log
+///
+/// The error log survives resets and is only cleared by
+/// [`crate::NPM1300::clear_error_log`] or a power-on reset.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct ErrorLog {
+    /// Cause of the last reset
+    pub reset_reason: ResetReason,
+    /// Charger errors latched when the reset occurred
+    pub charger_error_reason: ChargerErrorReason,
+    /// Charger sensor values latched when the reset occurred
+    pub charger_sensor_value: ChargerSensorValueDuringError,
+}
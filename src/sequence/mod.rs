@@ -0,0 +1,157 @@
+#[cfg(feature = "blocking")]
+use crate::hal::prelude::*;
+use crate::{buck::Buck, ldsw::Ldsw, Ldsw1Pwrupldo, Ldsw1Pwrupldsw, Ldsw2Pwrupldo, Ldsw2Pwrupldsw};
+
+mod types;
+
+// Re-export everything in types.rs
+pub use types::*;
+
+/// Interval between two status reads while waiting, in microseconds
+const POLL_INTERVAL_US: u32 = 100;
+
+#[maybe_async::maybe_async]
+impl<I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> crate::NPM1300<I2c, Delay> {
+    /// Run a power sequence
+    ///
+    /// The steps are run in order. If a step fails, the steps already run are undone in
+    /// reverse order, as in [`Self::run_power_down_sequence`], before returning the error.
+    /// Undoing is best effort, errors while undoing are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `steps` - The power sequence, see [`Step`]
+    ///
+    /// # Errors
+    ///
+    /// * `NPM1300Error::Timeout` - A wait step timed out
+    /// * `NPM1300Error` - An error occurred while running a step
+    pub async fn run_sequence(
+        &mut self,
+        steps: &[Step],
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        for (index, step) in steps.iter().enumerate() {
+            if let Err(error) = self.run_step(step).await {
+                // Roll back the steps that completed
+                for step in steps[..index].iter().rev().filter_map(Step::reverse) {
+                    let _ = self.run_step(&step).await;
+                }
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
+    /// Run the power-down sequence of a power sequence
+    ///
+    /// The reverse of each step is run, last step first, see [`Step::reverse`]. Stops at the
+    /// first error.
+    ///
+    /// # Arguments
+    ///
+    /// * `steps` - The power sequence to reverse, see [`Step`]
+    pub async fn run_power_down_sequence(
+        &mut self,
+        steps: &[Step],
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        for step in steps.iter().rev().filter_map(Step::reverse) {
+            self.run_step(&step).await?;
+        }
+        Ok(())
+    }
+
+    /// Run a single step of a power sequence
+    async fn run_step(&mut self, step: &Step) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        match *step {
+            Step::EnableBuck(Buck::Buck1) => self.enable_buck1().await,
+            Step::EnableBuck(Buck::Buck2) => self.enable_buck2().await,
+            Step::DisableBuck(Buck::Buck1) => self.disable_buck1().await,
+            Step::DisableBuck(Buck::Buck2) => self.disable_buck2().await,
+            Step::SetBuckVoltage(Buck::Buck1, voltage) => {
+                self.set_buck1_normal_voltage(voltage).await
+            }
+            Step::SetBuckVoltage(Buck::Buck2, voltage) => {
+                self.set_buck2_normal_voltage(voltage).await
+            }
+            Step::EnableLdsw(Ldsw::Ldsw1) => self.enable_ldsw1().await,
+            Step::EnableLdsw(Ldsw::Ldsw2) => self.enable_ldsw2().await,
+            Step::DisableLdsw(Ldsw::Ldsw1) => self.disable_ldsw1().await,
+            Step::DisableLdsw(Ldsw::Ldsw2) => self.disable_ldsw2().await,
+            Step::SetLdoVoltage(Ldsw::Ldsw1, voltage) => self.set_ldo1_voltage(voltage).await,
+            Step::SetLdoVoltage(Ldsw::Ldsw2, voltage) => self.set_ldo2_voltage(voltage).await,
+            Step::Delay { us } => {
+                self.delay.delay_us(us).await;
+                Ok(())
+            }
+            Step::WaitBuckVoltage {
+                buck,
+                voltage,
+                timeout_us,
+            } => {
+                let mut waited_us = 0;
+                loop {
+                    let status = self
+                        .device
+                        .buck()
+                        .buckvoutstatus(buck.index())
+                        .read_async()
+                        .await?;
+                    if status.buckvoutstatus() == Ok(voltage) {
+                        return Ok(());
+                    }
+                    self.wait_poll_interval(&mut waited_us, timeout_us).await?;
+                }
+            }
+            Step::WaitLdswPowered { ldsw, timeout_us } => {
+                let mut waited_us = 0;
+                loop {
+                    let status = self.get_ldsw_status().await?;
+                    let powered = match ldsw {
+                        Ldsw::Ldsw1 => {
+                            status.ldsw_1_pwrupldsw() == Ldsw1Pwrupldsw::Ldswpowered
+                                || status.ldsw_1_pwrupldo() == Ldsw1Pwrupldo::Ldopowered
+                        }
+                        Ldsw::Ldsw2 => {
+                            status.ldsw_2_pwrupldsw() == Ldsw2Pwrupldsw::Ldswpowered
+                                || status.ldsw_2_pwrupldo() == Ldsw2Pwrupldo::Ldopowered
+                        }
+                    };
+                    if powered {
+                        return Ok(());
+                    }
+                    self.wait_poll_interval(&mut waited_us, timeout_us).await?;
+                }
+            }
+            Step::SetGpio { pin, mode } => {
+                self.device
+                    .gpios()
+                    .gpiomode(pin.index())
+                    .write_async(|reg| reg.set_gpiomode(mode))
+                    .await
+            }
+        }
+    }
+
+    /// Wait before polling a status again
+    ///
+    /// # Arguments
+    ///
+    /// * `waited_us` - The time already waited, updated by this function
+    /// * `timeout_us` - The maximum time to wait
+    ///
+    /// # Errors
+    ///
+    /// Returns `NPM1300Error::Timeout` if the timeout has been reached
+    async fn wait_poll_interval(
+        &mut self,
+        waited_us: &mut u32,
+        timeout_us: u32,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        if *waited_us >= timeout_us {
+            return Err(crate::NPM1300Error::Timeout);
+        }
+        self.delay.delay_us(POLL_INTERVAL_US).await;
+        *waited_us = waited_us.saturating_add(POLL_INTERVAL_US);
+        Ok(())
+    }
+}
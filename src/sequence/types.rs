@@ -0,0 +1,96 @@
+use crate::{
+    buck::{Buck, BuckVoltage},
+    gpios::{GpioMode, GpioPin},
+    ldsw::{Ldsw, LdswVoltage},
+};
+
+/// Step of a power sequence
+///
+/// Sequences are plain slices of steps, so they can be declared as constants:
+///
+/// ```ignore
+/// const POWER_UP: &[Step] = &[
+///     Step::EnableBuck(Buck::Buck1),
+///     Step::WaitBuckVoltage { buck: Buck::Buck1, voltage: BuckVoltage::V1_8, timeout_us: 10_000 },
+///     Step::SetBuckVoltage(Buck::Buck2, BuckVoltage::V1_8),
+///     Step::EnableBuck(Buck::Buck2),
+///     Step::Delay { us: 500 },
+///     Step::EnableLdsw(Ldsw::Ldsw1),
+///     Step::WaitLdswPowered { ldsw: Ldsw::Ldsw1, timeout_us: 10_000 },
+///     Step::SetGpio { pin: GpioPin::Gpio2, mode: GpioMode::GpiInput },
+/// ];
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum Step {
+    /// Enable a BUCK regulator
+    EnableBuck(Buck),
+    /// Disable a BUCK regulator
+    DisableBuck(Buck),
+    /// Set the normal mode output voltage of a BUCK regulator
+    SetBuckVoltage(Buck, BuckVoltage),
+    /// Enable a load switch or LDO
+    EnableLdsw(Ldsw),
+    /// Disable a load switch or LDO
+    DisableLdsw(Ldsw),
+    /// Set the output voltage of a load switch in LDO mode
+    SetLdoVoltage(Ldsw, LdswVoltage),
+    /// Wait for a number of microseconds
+    Delay { us: u32 },
+    /// Wait until the output voltage of a BUCK regulator reaches a setting, see
+    /// [`crate::NPM1300::get_buck1_vout_status`]
+    WaitBuckVoltage {
+        buck: Buck,
+        voltage: BuckVoltage,
+        timeout_us: u32,
+    },
+    /// Wait until a load switch or LDO is powered, see [`crate::NPM1300::get_ldsw_status`]
+    WaitLdswPowered { ldsw: Ldsw, timeout_us: u32 },
+    /// Set the mode of a GPIO, an input mode releases a GPIO driven by the nPM1300
+    SetGpio { pin: GpioPin, mode: GpioMode },
+}
+
+impl Step {
+    /// Get the step undoing this step in a power-down sequence
+    ///
+    /// Enabling and disabling are swapped, delays are kept, and GPIOs driven high are driven
+    /// low and the other way around. Voltage settings, waits and the other GPIO modes, including
+    /// releasing a GPIO with an input mode, have no reverse step. A power-down sequence that
+    /// drives a released GPIO, such as an active-low reset line, states that step itself.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Step)` - The reverse step
+    /// * `None` - Nothing to undo
+    pub fn reverse(&self) -> Option<Step> {
+        match *self {
+            Step::EnableBuck(buck) => Some(Step::DisableBuck(buck)),
+            Step::DisableBuck(buck) => Some(Step::EnableBuck(buck)),
+            Step::EnableLdsw(ldsw) => Some(Step::DisableLdsw(ldsw)),
+            Step::DisableLdsw(ldsw) => Some(Step::EnableLdsw(ldsw)),
+            Step::Delay { us } => Some(Step::Delay { us }),
+            Step::SetGpio { pin, mode } => match mode {
+                GpioMode::GpoLogic1 => Some(Step::SetGpio {
+                    pin,
+                    mode: GpioMode::GpoLogic0,
+                }),
+                GpioMode::GpoLogic0 => Some(Step::SetGpio {
+                    pin,
+                    mode: GpioMode::GpoLogic1,
+                }),
+                GpioMode::GpiInput
+                | GpioMode::GpiLogic1
+                | GpioMode::GpiLogic0
+                | GpioMode::GpiEventRise
+                | GpioMode::GpiEventFall
+                | GpioMode::GpoIrq
+                | GpioMode::GpoReset
+                | GpioMode::GpoPowerLossWarning => None,
+            },
+            Step::SetBuckVoltage(..)
+            | Step::SetLdoVoltage(..)
+            | Step::WaitBuckVoltage { .. }
+            | Step::WaitLdswPowered { .. } => None,
+        }
+    }
+}
@@ -1,8 +1,9 @@
-use crate::{common::Task, field_sets::Shphldstatus, Shphldtim};
+#[cfg(feature = "blocking")]
+use crate::hal::prelude::*;
+use crate::{common::Task, field_sets::Shphldstatus, timer::TimerPeriod, Shphldtim};
 
-impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
-    crate::NPM1300<I2c, Delay>
-{
+#[maybe_async::maybe_async]
+impl<I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> crate::NPM1300<I2c, Delay> {
     /// Enter hibernation mode
     pub async fn enter_hibernate_mode(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
         self.device
@@ -12,6 +13,32 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
             .await
     }
 
+    /// Enter hibernation mode for a limited time
+    ///
+    /// Configures the wake-up timer and enters hibernation. The device wakes up when
+    /// `duration` elapses, or earlier on SHPHLD or VBUS.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - The time to spend in hibernation, between [`TimerPeriod::MIN`] and
+    ///   [`TimerPeriod::MAX`]. Rounded up to the next timer tick.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The device is entering hibernation
+    /// * `Err(NPM1300Error::InvalidHibernatePeriod)` - `duration` is outside the timer range
+    /// * `Err(NPM1300Error)` - An error occurred while configuring the timer
+    pub async fn enter_hibernate_for(
+        &mut self,
+        duration: core::time::Duration,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        let period = TimerPeriod::try_from(duration)
+            .map_err(|_| crate::NPM1300Error::InvalidHibernatePeriod)?;
+
+        self.configure_wake_up_timer(period).await?;
+        self.enter_hibernate_mode().await
+    }
+
     /// Enter ship mode
     pub async fn enter_ship_mode(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
         self.device
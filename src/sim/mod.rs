@@ -0,0 +1,617 @@
+//! In-memory simulator of the nPM1300
+//!
+//! [`Sim`] implements the I2C and delay traits of the driver, so the high-level API can be run
+//! without hardware. It models the register map of `device.yaml` with its reset values, the
+//! set and clear registers, the regulator status registers updated by the BUCK and LDSW tasks,
+//! and the ADC results of the measurement tasks, computed from configurable inputs.
+//!
+//! A `Sim` is a handle to a shared state, the clone given to the driver and the one kept by the
+//! test see the same registers:
+//!
+//! ```ignore
+//! let sim = Sim::new();
+//! let mut npm1300 = NPM1300::new(sim.clone(), sim.clone());
+//!
+//! sim.set_vbat(Millivolts(3700));
+//! assert_eq!(npm1300.measure_vbat_millivolts().await?, Millivolts(3700));
+//!
+//! npm1300.enable_buck1().await?;
+//! assert_eq!(sim.register("BUCK.BUCKSTATUS")?, 0x04);
+//! ```
+//!
+//! The register accesses of the driver can be recorded as a [`Transcript`], for example to
+//! compare them to a golden transcript:
+//!
+//! ```ignore
+//! sim.start_recording();
+//! npm1300.enable_buck1().await?;
+//! assert_eq!(
+//!     sim.take_transcript().to_string(),
+//!     "write BUCK.BUCKENASET[0] = 0x01 { TASKBUCKENASET: 1 }\n"
+//! );
+//! ```
+//!
+//! The register map is shared with the decoder, see [`crate::decode`].
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use embedded_hal_async::i2c::{ErrorType, Operation};
+
+use crate::{
+    adc::IbatStatuscodes,
+    decode::{RegisterKind, RegisterMap, WriteMode},
+    events::AdcEvents,
+    units::{CentiCelsius, Milliamps, Millivolts},
+};
+
+mod types;
+
+// Re-export everything in types.rs
+pub use types::*;
+
+/// BUCK1PWRGOOD in BUCKSTATUS, the BUCK2 fields are 4 bits higher
+const BUCKSTATUS_PWRGOOD: u8 = 1 << 2;
+/// BUCK1MODE and BUCK1PWMOK in BUCKSTATUS
+const BUCKSTATUS_PWM_MASK: u8 = 0b1011;
+/// BUCK1MODE set to forced PWM and BUCK1PWMOK set
+const BUCKSTATUS_PWM: u8 = 0b1010;
+/// LDSW1PWRUPLDSW in LDSWSTATUS, the LDSW2 fields are 2 bits higher
+const LDSWSTATUS_LDSW: u8 = 1 << 0;
+/// LDSW1PWRUPLDO in LDSWSTATUS
+const LDSWSTATUS_LDO: u8 = 1 << 1;
+
+/// Full scale voltage of the VBAT and VSYS measurements, in millivolts
+const VFS_VBAT: f64 = 5000.0;
+/// Full scale voltage of the VBUS measurement, in millivolts
+const VFS_VBUS: f64 = 7500.0;
+
+/// Simulated nPM1300
+///
+/// Implements the I2C bus of the driver, answering at a single address, and its delay
+/// provider, which only counts the time waited, see [`Sim::elapsed_us`]. Both the async and the
+/// blocking traits are implemented, whether or not the `blocking` feature is enabled.
+#[derive(Clone, Debug)]
+pub struct Sim {
+    state: Arc<Mutex<State>>,
+}
+
+impl Default for Sim {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sim {
+    /// Create a simulated nPM1300 at the default I2C address, see [`crate::DEFAULT_ADDRESS`]
+    pub fn new() -> Self {
+        Self::new_with_address(crate::DEFAULT_ADDRESS)
+    }
+
+    /// Create a simulated nPM1300 at a custom I2C address
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The 7-bit I2C address of the nPM1300
+    pub fn new_with_address(address: u8) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                address,
+                values: reset_values(),
+                pointer: 0,
+                inputs: Inputs::default(),
+                elapsed_ns: 0,
+                transcript: None,
+            })),
+        }
+    }
+
+    /// Lock the shared state
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Reset all registers to their reset value, as `MAIN.TASKSWRESET` does
+    ///
+    /// The inputs and the elapsed time are kept.
+    pub fn reset(&self) {
+        self.state().values = reset_values();
+    }
+
+    /// Read a register
+    ///
+    /// The read has no side effect, a command reads as 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the register, see [`crate::decode::RegisterInfo::name`]
+    ///
+    /// # Errors
+    ///
+    /// * `SimError::UnknownRegisterName` - There is no register with this name
+    pub fn register(&self, name: &str) -> Result<u8, SimError> {
+        let index = index_of_name(name)?;
+        Ok(self.state().value_at(index))
+    }
+
+    /// Set the value of a register
+    ///
+    /// Unlike a write over I2C, read-only registers can be set, for example to simulate a
+    /// status, and no task is triggered.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the register, see [`crate::decode::RegisterInfo::name`]
+    /// * `value` - The new value
+    ///
+    /// # Errors
+    ///
+    /// * `SimError::UnknownRegisterName` - There is no register with this name
+    pub fn set_register(&self, name: &str, value: u8) -> Result<(), SimError> {
+        let index = index_of_name(name)?;
+        self.state().set_value_at(index, value);
+        Ok(())
+    }
+
+    /// Set the battery voltage measured by the VBAT tasks
+    pub fn set_vbat(&self, vbat: Millivolts) {
+        self.state().inputs.vbat = vbat;
+    }
+
+    /// Set the system voltage measured by the VSYS task
+    pub fn set_vsys(&self, vsys: Millivolts) {
+        self.state().inputs.vsys = vsys;
+    }
+
+    /// Set the VBUS voltage measured by the VBUS task
+    pub fn set_vbus(&self, vbus: Millivolts) {
+        self.state().inputs.vbus = vbus;
+    }
+
+    /// Set the battery temperature measured by the NTC task
+    ///
+    /// # Arguments
+    ///
+    /// * `temperature` - The battery temperature
+    /// * `ntc_beta` - The beta value of the NTC thermistor, to compute its resistance
+    pub fn set_ntc_temperature(&self, temperature: CentiCelsius, ntc_beta: u16) {
+        // R / R25 = exp(beta * (1 / T - 1 / T25)), and the ADC measures R / (R + R25)
+        let kelvin = temperature.0 as f64 / 100.0 + 273.15;
+        let ratio = (ntc_beta as f64 * (1.0 / kelvin - 1.0 / 298.15)).exp();
+        self.state().inputs.ntc = adc_code(1024.0 * ratio / (ratio + 1.0)).max(1);
+    }
+
+    /// Set the die temperature measured by the die temperature task
+    pub fn set_die_temperature(&self, temperature: CentiCelsius) {
+        self.state().inputs.die_temperature = temperature;
+    }
+
+    /// Set the battery current measured by the IBAT measurement
+    ///
+    /// # Arguments
+    ///
+    /// * `ibat` - The battery current, positive when charging and negative when discharging
+    pub fn set_ibat(&self, ibat: Milliamps) {
+        self.state().inputs.ibat = ibat;
+    }
+
+    /// Start recording the register accesses over I2C, discarding the ones recorded before
+    ///
+    /// A read of several registers in one transaction is recorded as one access per register.
+    pub fn start_recording(&self) {
+        self.state().transcript = Some(Vec::new());
+    }
+
+    /// Stop recording and get the register accesses recorded, see [`Sim::start_recording`]
+    pub fn take_transcript(&self) -> Transcript {
+        Transcript(self.state().transcript.take().unwrap_or_default())
+    }
+
+    /// Get the time waited on the delay provider, in microseconds
+    pub fn elapsed_us(&self) -> u64 {
+        self.state().elapsed_ns / 1000
+    }
+}
+
+impl ErrorType for Sim {
+    type Error = SimError;
+}
+
+impl embedded_hal_async::i2c::I2c for Sim {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.state().transaction(address, operations)
+    }
+}
+
+impl embedded_hal_async::delay::DelayNs for Sim {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.state().elapsed_ns += ns as u64;
+    }
+}
+
+impl embedded_hal::i2c::I2c for Sim {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.state().transaction(address, operations)
+    }
+}
+
+impl embedded_hal::delay::DelayNs for Sim {
+    fn delay_ns(&mut self, ns: u32) {
+        self.state().elapsed_ns += ns as u64;
+    }
+}
+
+/// Analog values measured by the ADC tasks
+#[derive(Copy, Clone, Debug)]
+struct Inputs {
+    vbat: Millivolts,
+    vsys: Millivolts,
+    vbus: Millivolts,
+    /// Raw NTC result, see [`Sim::set_ntc_temperature`]
+    ntc: u16,
+    die_temperature: CentiCelsius,
+    ibat: Milliamps,
+}
+
+impl Default for Inputs {
+    fn default() -> Self {
+        Self {
+            vbat: Millivolts(3700),
+            vsys: Millivolts(3700),
+            vbus: Millivolts(5000),
+            // The NTC resistance equals the reference resistance at 25 °C
+            ntc: 512,
+            die_temperature: CentiCelsius(2500),
+            ibat: Milliamps(0),
+        }
+    }
+}
+
+/// State shared by the handles of a [`Sim`]
+#[derive(Debug)]
+struct State {
+    /// 7-bit I2C address
+    address: u8,
+    /// Register values, indexed like [`crate::decode::registers`]
+    values: Vec<u8>,
+    /// Register address of the next read or write
+    pointer: u16,
+    inputs: Inputs,
+    elapsed_ns: u64,
+    /// Accesses recorded since recording started, `None` when not recording
+    transcript: Option<Vec<Access>>,
+}
+
+impl State {
+    /// Run an I2C transaction
+    ///
+    /// A write starts with the 2 byte big-endian register address, the following bytes are
+    /// written from that address on. Reads continue from the last address. The address is
+    /// incremented after every byte.
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), SimError> {
+        if address != self.address {
+            return Err(SimError::NoAcknowledge(address));
+        }
+        for operation in operations {
+            match operation {
+                Operation::Write([]) => {}
+                Operation::Write([address_hi, address_lo, data @ ..]) => {
+                    self.pointer = u16::from_be_bytes([*address_hi, *address_lo]);
+                    for &byte in data.iter() {
+                        self.write(self.pointer, byte)?;
+                        self.pointer = self.pointer.wrapping_add(1);
+                    }
+                }
+                Operation::Write(_) => return Err(SimError::InvalidTransaction),
+                Operation::Read(buffer) => {
+                    for byte in buffer.iter_mut() {
+                        *byte = self.read(self.pointer)?;
+                        self.pointer = self.pointer.wrapping_add(1);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Read a register over I2C
+    fn read(&mut self, address: u16) -> Result<u8, SimError> {
+        let map = RegisterMap::get();
+        let index = map
+            .index_of_address(address)
+            .ok_or(SimError::UnknownRegister(address))?;
+        let value = self.value_at(index);
+        if let Some(transcript) = &mut self.transcript {
+            transcript.push(Access::Read(&map.registers[index], value));
+        }
+        Ok(value)
+    }
+
+    /// Write a register over I2C
+    fn write(&mut self, address: u16, value: u8) -> Result<(), SimError> {
+        let map = RegisterMap::get();
+        let index = map
+            .index_of_address(address)
+            .ok_or(SimError::UnknownRegister(address))?;
+        if let Some(transcript) = &mut self.transcript {
+            transcript.push(Access::Write(&map.registers[index], value));
+        }
+        match map.registers[index].kind {
+            RegisterKind::Command => {
+                if value & 1 == 1 {
+                    self.run_task(&map.registers[index].name);
+                }
+            }
+            RegisterKind::Register {
+                read_only: true, ..
+            } => {}
+            RegisterKind::Register { mask, .. } => {
+                let storage = map.storage_of(index);
+                self.values[storage] = match map.write_mode(index) {
+                    WriteMode::Value => value & mask,
+                    WriteMode::Set => self.values[storage] | (value & mask),
+                    WriteMode::Clear(_) => self.values[storage] & !(value & mask),
+                };
+            }
+        }
+        self.update_buck_vout_status();
+        Ok(())
+    }
+
+    /// Get the value of a register, a command reads as 0
+    fn value_at(&self, index: usize) -> u8 {
+        let map = RegisterMap::get();
+        match map.registers[index].kind {
+            RegisterKind::Command => 0,
+            RegisterKind::Register { .. } => self.values[map.storage_of(index)],
+        }
+    }
+
+    /// Set the value of a register, ignoring its access
+    fn set_value_at(&mut self, index: usize, value: u8) {
+        let map = RegisterMap::get();
+        if let RegisterKind::Register { mask, .. } = map.registers[index].kind {
+            self.values[map.storage_of(index)] = value & mask;
+        }
+    }
+
+    /// Get the value of a register modeled by the simulator
+    fn value(&self, name: &str) -> u8 {
+        self.value_at(modeled_register(name))
+    }
+
+    /// Set bits of a register modeled by the simulator
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the register
+    /// * `mask` - The bits to change
+    /// * `bits` - The new value of the bits, only the bits in `mask` are used
+    fn set_bits(&mut self, name: &str, mask: u8, bits: u8) {
+        let index = modeled_register(name);
+        let value = (self.value_at(index) & !mask) | (bits & mask);
+        self.set_value_at(index, value);
+    }
+
+    /// Run a task
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the command, see [`crate::decode::RegisterInfo::name`]
+    fn run_task(&mut self, name: &str) {
+        let (object, index) = match name.split_once('[') {
+            Some((object, index)) => (object, index.trim_end_matches(']').parse().unwrap_or(0)),
+            None => (name, 0),
+        };
+        let buck_shift = 4 * index;
+        let ldsw_shift = 2 * index;
+
+        match object {
+            "MAIN.TASKSWRESET" => self.values = reset_values(),
+            "BUCK.BUCKENASET" => self.set_bits(
+                "BUCK.BUCKSTATUS",
+                BUCKSTATUS_PWRGOOD << buck_shift,
+                BUCKSTATUS_PWRGOOD << buck_shift,
+            ),
+            "BUCK.BUCKENACLR" => {
+                self.set_bits("BUCK.BUCKSTATUS", BUCKSTATUS_PWRGOOD << buck_shift, 0)
+            }
+            "BUCK.BUCKPWMSET" => self.set_bits(
+                "BUCK.BUCKSTATUS",
+                BUCKSTATUS_PWM_MASK << buck_shift,
+                BUCKSTATUS_PWM << buck_shift,
+            ),
+            "BUCK.BUCKPWMCLR" => {
+                self.set_bits("BUCK.BUCKSTATUS", BUCKSTATUS_PWM_MASK << buck_shift, 0)
+            }
+            "LDSW.LDSWSET" => {
+                // The status follows the mode selected in LDSWxLDOSEL
+                let ldo = self.value(&format!("LDSW.LDSW{}LDOSEL", index + 1)) & 1 == 1;
+                let powered = if ldo { LDSWSTATUS_LDO } else { LDSWSTATUS_LDSW };
+                self.set_bits(
+                    "LDSW.LDSWSTATUS",
+                    (LDSWSTATUS_LDSW | LDSWSTATUS_LDO) << ldsw_shift,
+                    powered << ldsw_shift,
+                );
+            }
+            "LDSW.LDSWCLR" => self.set_bits(
+                "LDSW.LDSWSTATUS",
+                (LDSWSTATUS_LDSW | LDSWSTATUS_LDO) << ldsw_shift,
+                0,
+            ),
+            "ADC.TASKVBATMEASURE" | "ADC.TASKDELAYEDVBATMEASURE" => self.measure_vbat(),
+            "ADC.TASKNTCMEASURE" => {
+                self.store_result(
+                    "ADC.ADCNTCRESULTMSB",
+                    "ADC.ADCGP0RESULTLSBS",
+                    1,
+                    self.inputs.ntc,
+                );
+                self.set_adc_event(AdcEvents::NTC_READY);
+            }
+            "ADC.TASKTEMPMEASURE" => {
+                // T = 394.67 - 0.7926 * result, in degrees Celsius
+                let temperature = self.inputs.die_temperature.0 as f64 / 100.0;
+                let result = adc_code((394.67 - temperature) / 0.7926);
+                self.store_result("ADC.ADCTEMPRESULTMSB", "ADC.ADCGP0RESULTLSBS", 2, result);
+                self.set_adc_event(AdcEvents::DIE_TEMPERATURE_READY);
+            }
+            "ADC.TASKVSYSMEASURE" => {
+                let result = voltage_code(self.inputs.vsys, VFS_VBAT);
+                self.store_result("ADC.ADCVSYSRESULTMSB", "ADC.ADCGP0RESULTLSBS", 3, result);
+                self.set_adc_event(AdcEvents::VSYS_READY);
+            }
+            "ADC.TASKIBATMEASURE" => self.measure_ibat(),
+            "ADC.TASKVBUS7MEASURE" => {
+                // The VBUS result is stored in the last burst slot
+                let result = voltage_code(self.inputs.vbus, VFS_VBUS);
+                self.store_burst_result(3, result);
+                self.set_adc_event(AdcEvents::VBUS_READY);
+            }
+            _ => {}
+        }
+    }
+
+    /// Measure VBAT, as a single measurement or a burst of 4 depending on ADCCONFIG
+    fn measure_vbat(&mut self) {
+        let result = voltage_code(self.inputs.vbat, VFS_VBAT);
+        // VBATBURSTENABLE in ADCCONFIG
+        if self.value("ADC.ADCCONFIG") & (1 << 1) != 0 {
+            for slot in 0..4 {
+                self.store_burst_result(slot, result);
+            }
+        } else {
+            self.store_result("ADC.ADCVBATRESULTMSB", "ADC.ADCGP0RESULTLSBS", 0, result);
+        }
+        self.set_adc_event(AdcEvents::VBAT_READY);
+
+        // An IBAT measurement follows the VBAT measurement if enabled in ADCIBATMEASEN
+        if self.value("ADC.ADCIBATMEASEN") & 1 != 0 {
+            self.measure_ibat();
+        }
+    }
+
+    /// Measure IBAT
+    ///
+    /// The result is scaled to the charge current when charging, or to the discharge current
+    /// limit when discharging, as configured in the charger registers.
+    fn measure_ibat(&mut self) {
+        let ibat = self.inputs.ibat.0;
+        let (status, full_scale) = if ibat > 0 {
+            let msb = self.value("CHARGER.BCHGISETMSB") as u16;
+            let lsb = self.value("CHARGER.BCHGISETLSB") as u16 & 1;
+            (
+                IbatStatuscodes::IbatStatChargeNormal,
+                (msb << 2) | (lsb << 1),
+            )
+        } else {
+            let limit = match (
+                self.value("CHARGER.BCHGISETDISCHARGEMSB"),
+                self.value("CHARGER.BCHGISETDISCHARGELSB"),
+            ) {
+                (42, 0) => 200,
+                _ => 1000,
+            };
+            (IbatStatuscodes::IbatStatDischarge, limit)
+        };
+        let result = adc_code(ibat.unsigned_abs() as f64 * 1024.0 / full_scale.max(1) as f64);
+
+        self.set_bits("ADC.ADCIBATMEASSTATUS", 0x0F, status.into());
+        // The IBAT result is stored in the third burst slot
+        self.store_burst_result(2, result);
+        self.set_adc_event(AdcEvents::IBAT_READY);
+    }
+
+    /// Store a 10-bit ADC result in its MSB register and its 2 LSBs in a shared LSB register
+    ///
+    /// # Arguments
+    ///
+    /// * `msb` - The name of the MSB register
+    /// * `lsbs` - The name of the shared LSB register
+    /// * `position` - The position of the 2 LSBs in the shared LSB register
+    /// * `result` - The 10-bit result
+    fn store_result(&mut self, msb: &str, lsbs: &str, position: u32, result: u16) {
+        self.set_bits(msb, 0xFF, (result >> 2) as u8);
+        let shift = 2 * position;
+        self.set_bits(lsbs, 0b11 << shift, ((result & 0b11) as u8) << shift);
+    }
+
+    /// Store a 10-bit ADC result in a burst slot
+    fn store_burst_result(&mut self, slot: u32, result: u16) {
+        self.store_result(
+            &format!("ADC.ADCVBATBURSTRESULTMSB[{slot}]"),
+            "ADC.ADCGP1RESULTLSBS",
+            slot,
+            result,
+        );
+    }
+
+    /// Set an ADC event, readable in EVENTSADCSET and EVENTSADCCLR
+    fn set_adc_event(&mut self, event: AdcEvents) {
+        self.set_bits("MAIN.EVENTSADCSET", event.bits(), event.bits());
+    }
+
+    /// Update BUCKVOUTSTATUS from the normal mode voltage of the powered BUCKs
+    fn update_buck_vout_status(&mut self) {
+        for index in 0..2 {
+            let powered = self.value("BUCK.BUCKSTATUS") & (BUCKSTATUS_PWRGOOD << (4 * index)) != 0;
+            let vout = if powered {
+                self.value(&format!("BUCK.BUCK{}NORMVOUT", index + 1))
+            } else {
+                0
+            };
+            self.set_bits(&format!("BUCK.BUCKVOUTSTATUS[{index}]"), 0xFF, vout);
+        }
+    }
+}
+
+/// Get the reset values of all registers
+fn reset_values() -> Vec<u8> {
+    crate::decode::registers()
+        .iter()
+        .map(|register| match register.kind {
+            RegisterKind::Register { reset_value, .. } => reset_value,
+            RegisterKind::Command => 0,
+        })
+        .collect()
+}
+
+/// Get the index of a register by name
+fn index_of_name(name: &str) -> Result<usize, SimError> {
+    RegisterMap::get()
+        .index_of_name(name)
+        .ok_or_else(|| SimError::UnknownRegisterName(name.into()))
+}
+
+/// Get the index of a register the simulator models, which is in the manifest
+fn modeled_register(name: &str) -> usize {
+    RegisterMap::get()
+        .index_of_name(name)
+        .expect("registers modeled by the simulator are in device.yaml")
+}
+
+/// Round and clamp a value to the 10-bit ADC range
+fn adc_code(value: f64) -> u16 {
+    value.round().clamp(0.0, 1023.0) as u16
+}
+
+/// Get the ADC result of a voltage measurement
+///
+/// # Arguments
+///
+/// * `voltage` - The measured voltage
+/// * `full_scale` - The full scale voltage of the measurement in millivolts
+fn voltage_code(voltage: Millivolts, full_scale: f64) -> u16 {
+    adc_code(voltage.0 as f64 * 1023.0 / full_scale)
+}
@@ -0,0 +1,71 @@
+use embedded_hal_async::i2c::{ErrorKind, NoAcknowledgeSource};
+
+use crate::decode::RegisterInfo;
+
+/// Register access performed over I2C, recorded in a [`Transcript`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Access {
+    /// Register read, with the value read
+    Read(&'static RegisterInfo, u8),
+    /// Register write or task trigger, with the value written
+    Write(&'static RegisterInfo, u8),
+}
+
+impl core::fmt::Display for Access {
+    /// Format as `write BUCK.BUCK1NORMVOUT = 0x08 { value: 8 }`
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (operation, register, value) = match *self {
+            Access::Read(register, value) => ("read", register, value),
+            Access::Write(register, value) => ("write", register, value),
+        };
+        write!(f, "{operation} {} = {value:#04x}", register.name)?;
+        if !register.fields.is_empty() {
+            let fields = register
+                .fields
+                .iter()
+                .map(|field| format!("{}: {}", field.name, field.extract(value)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            write!(f, " {{ {fields} }}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Register accesses recorded by a [`super::Sim`], see [`super::Sim::start_recording`]
+///
+/// Displayed with one access per line, to compare against a golden transcript.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Transcript(pub Vec<Access>);
+
+impl core::fmt::Display for Transcript {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for access in &self.0 {
+            writeln!(f, "{access}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Error of the simulated nPM1300
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum SimError {
+    #[error("no device at address {0:#04x}")]
+    NoAcknowledge(u8),
+    #[error("no register at address {0:#06x}")]
+    UnknownRegister(u16),
+    #[error("no register named {0}")]
+    UnknownRegisterName(String),
+    #[error("a write must start with the 2 byte register address")]
+    InvalidTransaction,
+}
+
+impl embedded_hal_async::i2c::Error for SimError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            SimError::NoAcknowledge(_) => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+            SimError::UnknownRegister(_) => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+            SimError::UnknownRegisterName(_) | SimError::InvalidTransaction => ErrorKind::Other,
+        }
+    }
+}
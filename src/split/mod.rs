@@ -0,0 +1,544 @@
+use embassy_sync::blocking_mutex::raw::RawMutex;
+
+use crate::{
+    adc::{AdcSnapshot, IbatStatuscodes},
+    buck::BuckVoltage,
+    charger::{
+        ChargeCurrent, ChargerErrorReason, ChargerSensorValueDuringError, ChargerStatus,
+        ChargerTerminationCurrentLevelSelect, ChargerTerminationVoltage, ChargerTrickleLevelSelect,
+        DieTemperatureThresholdType, DischargeCurrentLimit, NtcThresholdRegion,
+    },
+    events,
+    field_sets::{Buckstatus, Buckvoutstatus, Ldswstatus, Shphldstatus},
+    gpios::{Gpio, GpioConfig, GpioPin, GpioPolarity, GpioStatus},
+    ldsw::{LdswMode, LdswSoftStartCurrent, LdswVoltage},
+    leds::LedMode,
+    reset::{ErrorLog, ResetReason},
+    sysreg::{VbusCcStatus, VbusInCurrentLimit, VbusInStatus},
+    timer::{TimerPeriod, WatchdogAction},
+    units::{CentiCelsius, Milliamps, Millivolts},
+    Buck1Autoctrlsel, Buck2Autoctrlsel, NPM1300Error, NtcThermistorType, Ntcautotim,
+    Pofwarnpolarity, Shphldtim, Tempautotim, TimerMode, Vbatautoenable, Vbatburstenable,
+    VsysThreshold,
+};
+
+mod types;
+
+// Re-export everything in types.rs
+pub use types::*;
+
+impl<I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> crate::NPM1300<I2c, Delay> {
+    /// Split a shared driver into one handle per block
+    ///
+    /// Every handle only exposes the methods of its block and locks the driver for the
+    /// duration of each call, so the handles can be moved to different tasks. The driver is
+    /// borrowed mutably for as long as the handles live, so each block has a single handle.
+    /// A regulator is put under GPIO control with the handle of the GPIO, see [`GpioHandle`]:
+    ///
+    /// ```ignore
+    /// static NPM1300: StaticCell<SharedNPM1300<CriticalSectionRawMutex, I2c, Delay>> =
+    ///     StaticCell::new();
+    ///
+    /// let npm1300 = NPM1300.init(Mutex::new(NPM1300::new(i2c, Delay)));
+    /// let parts = NPM1300::split(npm1300);
+    /// spawner.spawn(charger_task(parts.charger)).unwrap();
+    /// spawner.spawn(led_task(parts.leds)).unwrap();
+    /// parts.buck2.set_gpio_control(&parts.gpio1, GpioPolarity::NotInverted).await?;
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `npm1300` - The driver behind an async mutex
+    pub fn split<M: RawMutex>(
+        npm1300: &mut SharedNPM1300<M, I2c, Delay>,
+    ) -> Parts<'_, M, I2c, Delay> {
+        let npm1300 = &*npm1300;
+        Parts {
+            buck1: Buck1 { npm1300 },
+            buck2: Buck2 { npm1300 },
+            ldsw1: Ldsw1 { npm1300 },
+            ldsw2: Ldsw2 { npm1300 },
+            charger: Charger { npm1300 },
+            adc: Adc { npm1300 },
+            gpio0: Gpio0 { npm1300 },
+            gpio1: Gpio1 { npm1300 },
+            gpio2: Gpio2 { npm1300 },
+            gpio3: Gpio3 { npm1300 },
+            gpio4: Gpio4 { npm1300 },
+            leds: Leds { npm1300 },
+            ship: Ship { npm1300 },
+            pof: Pof { npm1300 },
+            events: Events { npm1300 },
+            timer: Timer { npm1300 },
+            vbus: Vbus { npm1300 },
+            reset: Reset { npm1300 },
+        }
+    }
+}
+
+/// Implement the methods of a handle, each locking the driver and calling a driver method
+///
+/// Generic methods list their bounds between brackets after `where`.
+macro_rules! forward {
+    (impl $handle:ident {
+        $(
+            $(#[$attr:meta])*
+            fn $name:ident $(<$($generic:ident),*>)? ($($arg:ident: $ty:ty),*) -> $output:ty
+                $(where [$($bound:tt)*])? => $method:ident;
+        )*
+    }) => {
+        impl<M: RawMutex, I2c: crate::hal::I2c, Delay: crate::hal::DelayNs>
+            $handle<'_, M, I2c, Delay>
+        {
+            $(
+                $(#[$attr])*
+                #[doc = concat!(
+                    "See [`NPM1300::", stringify!($method), "`](crate::NPM1300::",
+                    stringify!($method), ")"
+                )]
+                pub async fn $name $(<$($generic),*>)? (
+                    &self,
+                    $($arg: $ty),*
+                ) -> Result<$output, NPM1300Error<I2c::Error>>
+                $(where $($bound)*)?
+                {
+                    self.npm1300.lock().await.$method($($arg),*).await
+                }
+            )*
+        }
+    };
+}
+
+/// Implement the GPIO control methods of a regulator handle, taking the handle of the GPIO
+///
+/// Each entry generates a method putting the regulator under control of a GPIO, and
+/// optionally one disabling the GPIO control for the driver methods taking [`Gpio::None`].
+macro_rules! forward_gpio_control {
+    (impl $handle:ident {
+        $(fn $name:ident $(, $disable:ident)? => $method:ident;)*
+    }) => {
+        impl<M: RawMutex, I2c: crate::hal::I2c, Delay: crate::hal::DelayNs>
+            $handle<'_, M, I2c, Delay>
+        {
+            $(
+                #[doc = concat!(
+                    "See [`NPM1300::", stringify!($method), "`](crate::NPM1300::",
+                    stringify!($method), ")"
+                )]
+                ///
+                /// The GPIO is configured as an input, so it is given by its handle.
+                pub async fn $name(
+                    &self,
+                    gpio: &impl GpioHandle,
+                    polarity: GpioPolarity,
+                ) -> Result<(), NPM1300Error<I2c::Error>> {
+                    self.npm1300.lock().await.$method(gpio.gpio(), polarity).await
+                }
+
+                $(
+                    #[doc = concat!(
+                        "Disable the GPIO control set with [`Self::", stringify!($name), "`]"
+                    )]
+                    pub async fn $disable(&self) -> Result<(), NPM1300Error<I2c::Error>> {
+                        self.npm1300
+                            .lock()
+                            .await
+                            .$method(Gpio::None, GpioPolarity::NotInverted)
+                            .await
+                    }
+                )?
+            )*
+        }
+    };
+}
+
+/// Implement the retention mode method of a BUCK handle, taking the handle of the GPIO
+macro_rules! forward_retention {
+    ($($handle:ident => $method:ident),*) => {
+        $(
+            impl<M: RawMutex, I2c: crate::hal::I2c, Delay: crate::hal::DelayNs>
+                $handle<'_, M, I2c, Delay>
+            {
+                #[doc = concat!(
+                    "See [`NPM1300::", stringify!($method), "`](crate::NPM1300::",
+                    stringify!($method), ")"
+                )]
+                ///
+                /// The GPIO is configured as an input, so it is given by its handle.
+                pub async fn configure_retention_mode<V>(
+                    &self,
+                    voltage: V,
+                    gpio: &impl GpioHandle,
+                    polarity: GpioPolarity,
+                ) -> Result<(), NPM1300Error<I2c::Error>>
+                where
+                    V: TryInto<BuckVoltage>,
+                    NPM1300Error<I2c::Error>: From<V::Error>,
+                {
+                    self.npm1300
+                        .lock()
+                        .await
+                        .$method(voltage, gpio.gpio(), polarity)
+                        .await
+                }
+            }
+        )*
+    };
+}
+
+forward!(impl Buck1 {
+    fn enable() -> () => enable_buck1;
+    fn disable() -> () => disable_buck1;
+    fn enable_forced_pwm_mode() -> () => enable_buck1_forced_pwm_mode;
+    fn disable_forced_pwm_mode() -> () => disable_buck1_forced_pwm_mode;
+    fn set_normal_voltage<V>(voltage: V) -> ()
+        where [V: TryInto<BuckVoltage>, NPM1300Error<I2c::Error>: From<V::Error>]
+        => set_buck1_normal_voltage;
+    fn enable_vset_voltage() -> () => enable_buck1_vset_voltage;
+    fn disable_retention() -> () => disable_buck1_retention;
+    fn get_vout_status() -> Buckvoutstatus => get_buck1_vout_status;
+    fn configure_operating_mode(mode: Buck1Autoctrlsel) -> () => configure_buck1_operating_mode;
+    fn enable_pull_down() -> () => enable_buck1_pull_down;
+    fn disable_pull_down() -> () => disable_buck1_pull_down;
+    fn get_status() -> Buckstatus => get_buck_status;
+});
+
+forward_gpio_control!(impl Buck1 {
+    fn set_gpio_control => set_buck1_gpio_control;
+    fn set_gpio_forced_pwm_mode_control => set_buck1_gpio_forced_pwm_mode_control;
+});
+
+forward!(impl Buck2 {
+    fn enable() -> () => enable_buck2;
+    fn disable() -> () => disable_buck2;
+    fn enable_forced_pwm_mode() -> () => enable_buck2_forced_pwm_mode;
+    fn disable_forced_pwm_mode() -> () => disable_buck2_forced_pwm_mode;
+    fn set_normal_voltage<V>(voltage: V) -> ()
+        where [V: TryInto<BuckVoltage>, NPM1300Error<I2c::Error>: From<V::Error>]
+        => set_buck2_normal_voltage;
+    fn enable_vset_voltage() -> () => enable_buck2_vset_voltage;
+    fn disable_retention() -> () => disable_buck2_retention;
+    fn get_vout_status() -> Buckvoutstatus => get_buck2_vout_status;
+    fn configure_operating_mode(mode: Buck2Autoctrlsel) -> () => configure_buck2_operating_mode;
+    fn enable_pull_down() -> () => enable_buck2_pull_down;
+    fn disable_pull_down() -> () => disable_buck2_pull_down;
+    fn get_status() -> Buckstatus => get_buck_status;
+});
+
+forward_gpio_control!(impl Buck2 {
+    fn set_gpio_control => set_buck2_gpio_control;
+    fn set_gpio_forced_pwm_mode_control => set_buck2_gpio_forced_pwm_mode_control;
+});
+
+forward_retention!(
+    Buck1 => configure_buck1_retention_mode,
+    Buck2 => configure_buck2_retention_mode
+);
+
+forward!(impl Ldsw1 {
+    fn enable() -> () => enable_ldsw1;
+    fn disable() -> () => disable_ldsw1;
+    fn set_mode(mode: LdswMode) -> () => set_ldsw1_mode;
+    fn set_ldo_voltage<V>(voltage: V) -> ()
+        where [V: TryInto<LdswVoltage>, NPM1300Error<I2c::Error>: From<V::Error>]
+        => set_ldo1_voltage;
+    fn set_soft_start_current(current: LdswSoftStartCurrent) -> ()
+        => set_ldsw1_soft_start_current;
+    fn enable_active_discharge() -> () => enable_ldsw1_active_discharge;
+    fn disable_active_discharge() -> () => disable_ldsw1_active_discharge;
+    fn get_status() -> Ldswstatus => get_ldsw_status;
+});
+
+forward_gpio_control!(impl Ldsw1 {
+    fn set_gpio_control, disable_gpio_control => set_ldsw1_gpio_control;
+});
+
+forward!(impl Ldsw2 {
+    fn enable() -> () => enable_ldsw2;
+    fn disable() -> () => disable_ldsw2;
+    fn set_mode(mode: LdswMode) -> () => set_ldsw2_mode;
+    fn set_ldo_voltage<V>(voltage: V) -> ()
+        where [V: TryInto<LdswVoltage>, NPM1300Error<I2c::Error>: From<V::Error>]
+        => set_ldo2_voltage;
+    fn set_soft_start_current(current: LdswSoftStartCurrent) -> ()
+        => set_ldsw2_soft_start_current;
+    fn enable_active_discharge() -> () => enable_ldsw2_active_discharge;
+    fn disable_active_discharge() -> () => disable_ldsw2_active_discharge;
+    fn get_status() -> Ldswstatus => get_ldsw_status;
+});
+
+forward_gpio_control!(impl Ldsw2 {
+    fn set_gpio_control, disable_gpio_control => set_ldsw2_gpio_control;
+});
+
+forward!(impl Charger {
+    fn clear_charger_errors() -> () => clear_charger_errors;
+    fn clear_charger_safety_timer() -> () => clear_charger_safety_timer;
+    fn enable_battery_charging() -> () => enable_battery_charging;
+    fn disable_battery_charging() -> () => disable_battery_charging;
+    fn is_charging_enabled() -> bool => is_charging_enabled;
+    fn enable_battery_charger_full_charge_in_cool_temp() -> ()
+        => enable_battery_charger_full_charge_in_cool_temp;
+    fn disable_battery_charger_full_charge_in_cool_temp() -> ()
+        => disable_battery_charger_full_charge_in_cool_temp;
+    fn is_full_charge_current_in_cool_temp_enabled() -> bool
+        => is_full_charge_current_in_cool_temp_enabled;
+    fn enable_battery_recharge() -> () => enable_battery_recharge;
+    fn disable_battery_recharge() -> () => disable_battery_recharge;
+    fn is_battery_recharge_enabled() -> bool => is_battery_recharge_enabled;
+    fn ignore_ntc_measurements() -> () => ignore_ntc_measurements;
+    fn use_ntc_measurements() -> () => use_ntc_measurements;
+    fn is_ntc_measurement_ignored() -> bool => is_ntc_measurement_ignored;
+    fn set_charger_current<C>(current: C) -> ()
+        where [C: TryInto<ChargeCurrent>, NPM1300Error<I2c::Error>: From<C::Error>]
+        => set_charger_current;
+    fn get_charger_config_current() -> u16 => get_charger_config_current;
+    fn set_discharge_current_limit(limit: DischargeCurrentLimit) -> ()
+        => set_discharge_current_limit;
+    fn get_discharge_current_limit() -> DischargeCurrentLimit => get_discharge_current_limit;
+    fn get_discharge_current_limit_active() -> bool => get_discharge_current_limit_active;
+    fn set_normal_temperature_termination_voltage<V>(termination_voltage: V) -> ()
+        where [
+            V: TryInto<ChargerTerminationVoltage>,
+            NPM1300Error<I2c::Error>: From<V::Error>
+        ]
+        => set_normal_temperature_termination_voltage;
+    fn set_warm_temperature_termination_voltage<V>(termination_voltage: V) -> ()
+        where [
+            V: TryInto<ChargerTerminationVoltage>,
+            NPM1300Error<I2c::Error>: From<V::Error>
+        ]
+        => set_warm_temperature_termination_voltage;
+    #[cfg(feature = "float")]
+    fn configure_charger_for_battery(model: &crate::battery::BatteryModel) -> ()
+        => configure_charger_for_battery;
+    fn set_trickle_level(trickle_level: ChargerTrickleLevelSelect) -> () => set_trickle_level;
+    fn set_termination_current_level(
+        termination_current_level: ChargerTerminationCurrentLevelSelect
+    ) -> () => set_termination_current_level;
+    fn set_ntc_threshold(
+        region: NtcThresholdRegion,
+        desired_resistance: u32,
+        reference_resistance_25c: u32
+    ) -> () => set_ntc_threshold;
+    fn get_ntc_threshold(region: NtcThresholdRegion) -> u16 => get_ntc_threshold;
+    fn get_ntc_current_region() -> Option<NtcThresholdRegion> => get_ntc_current_region;
+    fn set_die_temperature_threshold(
+        threshold_type: DieTemperatureThresholdType,
+        temperature_celsius: u16
+    ) -> () => set_die_temperature_threshold;
+    fn is_die_temperature_above_charging_threshold() -> bool
+        => is_die_temperature_above_charging_threshold;
+    fn set_charge_if_battery_warm(enable: bool) -> () => set_charge_if_battery_warm;
+    fn get_charger_status() -> ChargerStatus => get_charger_status;
+    fn get_charger_error_reason_and_sensor_value()
+        -> (ChargerErrorReason, ChargerSensorValueDuringError)
+        => get_charger_error_reason_and_sensor_value;
+});
+
+forward!(impl Adc {
+    fn measure_vbat_millivolts() -> Millivolts => measure_vbat_millivolts;
+    fn measure_vbat_burst_millivolts() -> [Millivolts; 4] => measure_vbat_burst_millivolts;
+    fn get_vbat_measurement_result_millivolts() -> Millivolts
+        => get_vbat_measurement_result_millivolts;
+    fn get_vbat_burst_measurement_result_millivolts(vbat_index: u8) -> Millivolts
+        => get_vbat_burst_measurement_result_millivolts;
+    fn measure_delayed_vbat_millivolts(delay: u16) -> Millivolts
+        => measure_delayed_vbat_millivolts;
+    fn get_vbat_delay_configuration() -> u8 => get_vbat_delay_configuration;
+    fn configure_auto_vbat_measurement(enable: bool) -> () => configure_auto_vbat_measurement;
+    fn get_vbat_auto_measurement_configuration() -> Vbatautoenable
+        => get_vbat_auto_measurement_configuration;
+    fn configure_vbat_burst_measurement(enable: bool) -> () => configure_vbat_burst_measurement;
+    fn get_vbat_burst_measurement_configuration() -> Vbatburstenable
+        => get_vbat_burst_measurement_configuration;
+    fn measure_ntc_centi_celsius() -> CentiCelsius => measure_ntc_centi_celsius;
+    fn get_ntc_measurement_result_centi_celsius() -> CentiCelsius
+        => get_ntc_measurement_result_centi_celsius;
+    fn configure_ntc_resistance_kelvin(
+        ntc_resistance: NtcThermistorType,
+        ntc_beta: Option<u16>
+    ) -> () => configure_ntc_resistance_kelvin;
+    fn get_ntc_resistance_configuration() -> NtcThermistorType
+        => get_ntc_resistance_configuration;
+    fn configure_auto_ntc_measurement(ntc_auto_measurement_interval: Ntcautotim) -> ()
+        => configure_auto_ntc_measurement;
+    fn get_ntc_auto_measurement_configuration() -> Ntcautotim
+        => get_ntc_auto_measurement_configuration;
+    fn measure_die_temperature_centi_celsius() -> CentiCelsius
+        => measure_die_temperature_centi_celsius;
+    fn get_die_temperature_measurement_result_centi_celsius() -> CentiCelsius
+        => get_die_temperature_measurement_result_centi_celsius;
+    fn configure_die_temperature_auto_measurement_interval(
+        die_temperature_auto_measurement_interval: Tempautotim
+    ) -> () => configure_die_temperature_auto_measurement_interval;
+    fn get_die_temperature_auto_measurement_configuration() -> Tempautotim
+        => get_die_temperature_auto_measurement_configuration;
+    fn measure_vsys_millivolts() -> Millivolts => measure_vsys_millivolts;
+    fn measure_vbus_millivolts() -> Millivolts => measure_vbus_millivolts;
+    fn configure_ibat_measurement(enable: bool) -> () => configure_ibat_measurement;
+    fn get_ibat_measurement_configuration() -> bool => get_ibat_measurement_configuration;
+    fn get_ibat_status() -> IbatStatuscodes => get_ibat_status;
+    fn measure_ibat_raw() -> u16 => measure_ibat_raw;
+    fn measure_ibat_milliamps() -> Milliamps => measure_ibat_milliamps;
+    fn get_adc_snapshot() -> AdcSnapshot => get_adc_snapshot;
+    #[cfg(feature = "float")]
+    fn measure_vbat() -> f32 => measure_vbat;
+    #[cfg(feature = "float")]
+    fn measure_vbat_burst() -> (f32, f32, f32, f32) => measure_vbat_burst;
+    #[cfg(feature = "float")]
+    fn get_vbat_measurement_result() -> f32 => get_vbat_measurement_result;
+    #[cfg(feature = "float")]
+    fn get_vbat_burst_measurement_result(vbat_index: u8) -> f32
+        => get_vbat_burst_measurement_result;
+    #[cfg(feature = "float")]
+    fn measure_delayed_vbat(delay: u16) -> f32 => measure_delayed_vbat;
+    #[cfg(feature = "float")]
+    fn configure_ntc_resistance(ntc_resistance: NtcThermistorType, ntc_beta: Option<f32>) -> ()
+        => configure_ntc_resistance;
+    #[cfg(feature = "float")]
+    fn measure_ntc() -> f32 => measure_ntc;
+    #[cfg(feature = "float")]
+    fn get_ntc_measurement_result() -> f32 => get_ntc_measurement_result;
+    #[cfg(feature = "float")]
+    fn measure_die_temperature() -> f32 => measure_die_temperature;
+    #[cfg(feature = "float")]
+    fn get_die_temperature_measurement_result() -> f32
+        => get_die_temperature_measurement_result;
+    #[cfg(feature = "float")]
+    fn measure_vsys() -> f32 => measure_vsys;
+    #[cfg(feature = "float")]
+    fn measure_vbus() -> f32 => measure_vbus;
+    #[cfg(feature = "float")]
+    fn measure_ibat() -> f32 => measure_ibat;
+});
+
+/// Implement the methods of the handle of a GPIO, calling the driver methods with its pin
+macro_rules! forward_gpio {
+    ($($handle:ident => $pin:ident),*) => {
+        $(
+            impl<M: RawMutex, I2c: crate::hal::I2c, Delay: crate::hal::DelayNs>
+                $handle<'_, M, I2c, Delay>
+            {
+                /// See [`NPM1300::configure_gpio`](crate::NPM1300::configure_gpio)
+                pub async fn configure(
+                    &self,
+                    config: GpioConfig,
+                ) -> Result<(), NPM1300Error<I2c::Error>> {
+                    self.npm1300
+                        .lock()
+                        .await
+                        .configure_gpio(GpioPin::$pin, config)
+                        .await
+                }
+
+                /// See [`NPM1300::get_gpio_status`](crate::NPM1300::get_gpio_status)
+                pub async fn get_status(&self) -> Result<GpioStatus, NPM1300Error<I2c::Error>> {
+                    self.npm1300.lock().await.get_gpio_status(GpioPin::$pin).await
+                }
+            }
+        )*
+    };
+}
+
+forward_gpio!(Gpio0 => Gpio0, Gpio1 => Gpio1, Gpio2 => Gpio2, Gpio3 => Gpio3, Gpio4 => Gpio4);
+
+forward!(impl Leds {
+    fn configure_led0_mode(mode: LedMode) -> () => configure_led0_mode;
+    fn configure_led1_mode(mode: LedMode) -> () => configure_led1_mode;
+    fn configure_led2_mode(mode: LedMode) -> () => configure_led2_mode;
+    fn enable_led0() -> () => enable_led0;
+    fn disable_led0() -> () => disable_led0;
+    fn enable_led1() -> () => enable_led1;
+    fn disable_led1() -> () => disable_led1;
+    fn enable_led2() -> () => enable_led2;
+    fn disable_led2() -> () => disable_led2;
+});
+
+forward!(impl Ship {
+    fn enter_hibernate_mode() -> () => enter_hibernate_mode;
+    fn enter_hibernate_for(duration: core::time::Duration) -> () => enter_hibernate_for;
+    fn enter_ship_mode() -> () => enter_ship_mode;
+    fn reset_ship_hold_config() -> () => reset_ship_hold_config;
+    fn set_ship_hold_press_timer(time: Shphldtim) -> () => set_ship_hold_press_timer;
+    fn get_ship_hold_status() -> Shphldstatus => get_ship_hold_status;
+    fn enable_long_press_reset() -> () => enable_long_press_reset;
+    fn disable_long_press_reset() -> () => disable_long_press_reset;
+    fn use_ship_hold_button_only() -> () => use_ship_hold_button_only;
+    fn use_ship_hold_button_and_gpio0() -> () => use_ship_hold_button_and_gpio0;
+});
+
+forward!(impl Pof {
+    fn enable_power_failure_detection(enable: bool) -> () => enable_power_failure_detection;
+    fn is_power_failure_detection_enabled() -> bool => is_power_failure_detection_enabled;
+    fn set_power_failure_warning_gpio_polarity(polarity: Pofwarnpolarity) -> ()
+        => set_power_failure_warning_gpio_polarity;
+    fn get_power_failure_warning_gpio_polarity() -> Pofwarnpolarity
+        => get_power_failure_warning_gpio_polarity;
+    fn set_vsys_threshold<T>(threshold: T) -> ()
+        where [T: TryInto<VsysThreshold>, NPM1300Error<I2c::Error>: From<T::Error>]
+        => set_vsys_threshold;
+    fn get_vsys_threshold() -> VsysThreshold => get_vsys_threshold;
+});
+
+forward!(impl Events {
+    fn enable_events(events: events::Events) -> () => enable_events;
+    fn disable_events(events: events::Events) -> () => disable_events;
+    fn get_enabled_events() -> events::Events => get_enabled_events;
+    fn get_events() -> events::Events => get_events;
+    fn clear_events(events: events::Events) -> () => clear_events;
+});
+
+impl<M: RawMutex, I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> Events<'_, M, I2c, Delay> {
+    /// See [`NPM1300::wait_for_events`](crate::NPM1300::wait_for_events)
+    ///
+    /// The driver is only locked once the interrupt line is asserted, so the other handles
+    /// can be used while waiting.
+    pub async fn wait_for_events<Irq: crate::hal::Wait>(
+        &self,
+        irq: &mut Irq,
+    ) -> Result<events::Events, NPM1300Error<I2c::Error>> {
+        irq.wait_for_high()
+            .await
+            .map_err(|_| NPM1300Error::InterruptPin)?;
+
+        let mut npm1300 = self.npm1300.lock().await;
+        let events = npm1300.get_events().await?;
+        npm1300.clear_events(events).await?;
+
+        Ok(events)
+    }
+}
+
+forward!(impl Timer {
+    fn start_timer() -> () => start_timer;
+    fn stop_timer() -> () => stop_timer;
+    fn enable_watchdog(period: TimerPeriod, action: WatchdogAction) -> () => enable_watchdog;
+    fn kick_watchdog() -> () => kick_watchdog;
+    fn disable_watchdog() -> () => disable_watchdog;
+    fn start_general_purpose_timer(period: TimerPeriod) -> () => start_general_purpose_timer;
+    fn configure_wake_up_timer(period: TimerPeriod) -> () => configure_wake_up_timer;
+    fn enable_boot_monitor(period: TimerPeriod) -> () => enable_boot_monitor;
+    fn get_timer_mode() -> TimerMode => get_timer_mode;
+    fn get_timer_period() -> Option<TimerPeriod> => get_timer_period;
+});
+
+forward!(impl Vbus {
+    fn set_vbus_in_current_limit<C>(current_limit: C) -> ()
+        where [C: TryInto<VbusInCurrentLimit>, NPM1300Error<I2c::Error>: From<C::Error>]
+        => set_vbus_in_current_limit;
+    fn set_vbus_in_startup_current_limit<C>(current_limit: C) -> ()
+        where [C: TryInto<VbusInCurrentLimit>, NPM1300Error<I2c::Error>: From<C::Error>]
+        => set_vbus_in_startup_current_limit;
+    fn set_vbus_mode(suspend: bool) -> () => set_vbus_mode;
+    fn get_vbus_cc_status() -> VbusCcStatus => get_vbus_cc_status;
+    fn get_vbus_in_status() -> VbusInStatus => get_vbus_in_status;
+});
+
+forward!(impl Reset {
+    fn get_reset_reason() -> ResetReason => get_reset_reason;
+    fn get_error_log() -> ErrorLog => get_error_log;
+    fn clear_error_log() -> () => clear_error_log;
+    fn software_reset() -> () => software_reset;
+    fn power_cycle_after(delay: core::time::Duration) -> () => power_cycle_after;
+});
@@ -0,0 +1,149 @@
+use embassy_sync::{blocking_mutex::raw::RawMutex, mutex::Mutex};
+
+use crate::gpios::Gpio;
+
+/// Driver shared by the handles of [`crate::NPM1300::split`]
+///
+/// Methods spanning several blocks, like [`crate::NPM1300::apply_config`] or
+/// [`crate::NPM1300::run_sequence`], have no handle and are called on the driver before it is
+/// split.
+pub type SharedNPM1300<M, I2c, Delay> = Mutex<M, crate::NPM1300<I2c, Delay>>;
+
+/// Declare a handle to one block of a [`SharedNPM1300`]
+macro_rules! handle {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        pub struct $name<'a, M: RawMutex, I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> {
+            pub(super) npm1300: &'a SharedNPM1300<M, I2c, Delay>,
+        }
+    };
+}
+
+handle!(
+    /// Handle to BUCK1
+    Buck1
+);
+handle!(
+    /// Handle to BUCK2
+    Buck2
+);
+handle!(
+    /// Handle to load switch 1 and LDO1
+    Ldsw1
+);
+handle!(
+    /// Handle to load switch 2 and LDO2
+    Ldsw2
+);
+handle!(
+    /// Handle to the battery charger
+    Charger
+);
+handle!(
+    /// Handle to the ADC
+    Adc
+);
+handle!(
+    /// Handle to GPIO0
+    Gpio0
+);
+handle!(
+    /// Handle to GPIO1
+    Gpio1
+);
+handle!(
+    /// Handle to GPIO2
+    Gpio2
+);
+handle!(
+    /// Handle to GPIO3
+    Gpio3
+);
+handle!(
+    /// Handle to GPIO4
+    Gpio4
+);
+handle!(
+    /// Handle to the LED drivers
+    Leds
+);
+handle!(
+    /// Handle to the ship and hibernate modes and the ship hold button
+    Ship
+);
+handle!(
+    /// Handle to the power-fail comparator
+    Pof
+);
+handle!(
+    /// Handle to the event and interrupt registers
+    Events
+);
+handle!(
+    /// Handle to the timer, the watchdog and the boot monitor
+    Timer
+);
+handle!(
+    /// Handle to the VBUS input regulator
+    Vbus
+);
+handle!(
+    /// Handle to the reset reason, the error log and the software reset
+    Reset
+);
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Handle to one GPIO, see [`Gpio0`] to [`Gpio4`]
+///
+/// A regulator can only be put under GPIO control with the handle of the GPIO, as doing so
+/// configures the GPIO as an input.
+pub trait GpioHandle: sealed::Sealed {
+    /// The GPIO of the handle
+    fn gpio(&self) -> Gpio;
+}
+
+macro_rules! gpio_handle {
+    ($($handle:ident),*) => {
+        $(
+            impl<M: RawMutex, I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> sealed::Sealed
+                for $handle<'_, M, I2c, Delay>
+            {
+            }
+
+            impl<M: RawMutex, I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> GpioHandle
+                for $handle<'_, M, I2c, Delay>
+            {
+                fn gpio(&self) -> Gpio {
+                    Gpio::$handle
+                }
+            }
+        )*
+    };
+}
+
+gpio_handle!(Gpio0, Gpio1, Gpio2, Gpio3, Gpio4);
+
+/// Handles to the blocks of an nPM1300, see [`crate::NPM1300::split`]
+pub struct Parts<'a, M: RawMutex, I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> {
+    pub buck1: Buck1<'a, M, I2c, Delay>,
+    pub buck2: Buck2<'a, M, I2c, Delay>,
+    pub ldsw1: Ldsw1<'a, M, I2c, Delay>,
+    pub ldsw2: Ldsw2<'a, M, I2c, Delay>,
+    pub charger: Charger<'a, M, I2c, Delay>,
+    pub adc: Adc<'a, M, I2c, Delay>,
+    pub gpio0: Gpio0<'a, M, I2c, Delay>,
+    pub gpio1: Gpio1<'a, M, I2c, Delay>,
+    pub gpio2: Gpio2<'a, M, I2c, Delay>,
+    pub gpio3: Gpio3<'a, M, I2c, Delay>,
+    pub gpio4: Gpio4<'a, M, I2c, Delay>,
+    pub leds: Leds<'a, M, I2c, Delay>,
+    pub ship: Ship<'a, M, I2c, Delay>,
+    pub pof: Pof<'a, M, I2c, Delay>,
+    pub events: Events<'a, M, I2c, Delay>,
+    pub timer: Timer<'a, M, I2c, Delay>,
+    pub vbus: Vbus<'a, M, I2c, Delay>,
+    pub reset: Reset<'a, M, I2c, Delay>,
+}
@@ -3,11 +3,12 @@ mod types;
 // Re-export everything in types.rs
 pub use types::*;
 
+#[cfg(feature = "blocking")]
+use crate::hal::prelude::*;
 use crate::{common::Task, Vbussuspendena};
 
-impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayNs>
-    crate::NPM1300<I2c, Delay>
-{
+#[maybe_async::maybe_async]
+impl<I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> crate::NPM1300<I2c, Delay> {
     /// Set VBUS input current limit
     ///
     /// This function sets the input current limit and triggers a switch from VBUSINILIMSTARTUP to VBUSINILIM0.
@@ -18,11 +19,17 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Arguments
     ///
-    /// * `current_limit` - The desired input current limit setting
-    pub async fn set_vbus_in_current_limit(
+    /// * `current_limit` - The desired input current limit, a [`VbusInCurrentLimit`] or
+    ///   [`Milliamps`](crate::units::Milliamps)
+    pub async fn set_vbus_in_current_limit<C>(
         &mut self,
-        current_limit: VbusInCurrentLimit,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        current_limit: C,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>>
+    where
+        C: TryInto<VbusInCurrentLimit>,
+        crate::NPM1300Error<I2c::Error>: From<C::Error>,
+    {
+        let current_limit = current_limit.try_into()?;
         // Set the input current limit
         self.device
             .vbusin()
@@ -41,11 +48,17 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
     ///
     /// # Arguments
     ///
-    /// * `current_limit` - The desired input startup current limit setting
-    pub async fn set_vbus_in_startup_current_limit(
+    /// * `current_limit` - The desired input startup current limit, a [`VbusInCurrentLimit`] or
+    ///   [`Milliamps`](crate::units::Milliamps)
+    pub async fn set_vbus_in_startup_current_limit<C>(
         &mut self,
-        current_limit: VbusInCurrentLimit,
-    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        current_limit: C,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>>
+    where
+        C: TryInto<VbusInCurrentLimit>,
+        crate::NPM1300Error<I2c::Error>: From<C::Error>,
+    {
+        let current_limit = current_limit.try_into()?;
         // Set the input current limit
         self.device
             .vbusin()
@@ -86,10 +99,13 @@ impl<I2c: embedded_hal_async::i2c::I2c, Delay: embedded_hal_async::delay::DelayN
         &mut self,
     ) -> Result<VbusCcStatus, crate::NPM1300Error<I2c::Error>> {
         let status = self.device.vbusin().usbcdetectstatus().read_async().await?;
-        // Since we successfully read the register, we can safely unwrap the CC1/CC2 status values
         Ok(VbusCcStatus {
-            vbusin_cc1_status: status.vbusincc_1_cmp().unwrap(),
-            vbusin_cc2_status: status.vbusincc_2_cmp().unwrap(),
+            vbusin_cc1_status: status
+                .vbusincc_1_cmp()
+                .map_err(|_| crate::NPM1300Error::UnexpectedRegisterValue)?,
+            vbusin_cc2_status: status
+                .vbusincc_2_cmp()
+                .map_err(|_| crate::NPM1300Error::UnexpectedRegisterValue)?,
         })
     }
 
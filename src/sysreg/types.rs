@@ -1,5 +1,7 @@
+use crate::units::{nearest_step_index, step_index, Milliamps, UnitError};
+
 /// Input current limit for VBUS
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum VbusInCurrentLimit {
     // 500 mA
@@ -55,8 +57,7 @@ impl From<u8> for VbusInCurrentLimit {
             12 => VbusInCurrentLimit::MA1200,
             13 => VbusInCurrentLimit::MA1300,
             14 => VbusInCurrentLimit::MA1400,
-            15 => VbusInCurrentLimit::MA1500,
-            _ => unreachable!(),
+            _ => VbusInCurrentLimit::MA1500,
         }
     }
 }
@@ -68,6 +69,46 @@ impl From<VbusInCurrentLimit> for u8 {
     }
 }
 
+impl VbusInCurrentLimit {
+    /// Get the current limit nearest to `current`, clamped to the 100 mA to 1500 mA range
+    pub fn nearest(current: Milliamps) -> Self {
+        Self::from_index(nearest_step_index(current.0 as i32, 100, 1500, 100))
+    }
+
+    /// Get the current limit from its index in the 100 mA to 1500 mA range
+    fn from_index(index: u8) -> Self {
+        match index + 1 {
+            // Prefer the default 500 mA setting over the alternate one
+            5 => Self::MA500,
+            value => Self::from(value),
+        }
+    }
+}
+
+// Add conversion from Milliamps, rejecting currents between steps
+impl TryFrom<Milliamps> for VbusInCurrentLimit {
+    type Error = UnitError;
+
+    fn try_from(current: Milliamps) -> Result<Self, Self::Error> {
+        Ok(Self::from_index(step_index(
+            current.0 as i32,
+            100,
+            1500,
+            100,
+        )?))
+    }
+}
+
+// Add conversion to Milliamps
+impl From<VbusInCurrentLimit> for Milliamps {
+    fn from(current_limit: VbusInCurrentLimit) -> Self {
+        match current_limit {
+            VbusInCurrentLimit::MA500 => Milliamps(500),
+            current_limit => Milliamps(100 * current_limit as i16),
+        }
+    }
+}
+
 /// Input current limit for VBUS
 #[derive(Copy, Clone, Debug)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
@@ -89,8 +130,7 @@ impl From<u8> for VbusInCcCmp {
             0 => VbusInCcCmp::NoConnection,
             1 => VbusInCcCmp::DefaultUsb,
             2 => VbusInCcCmp::MA1500HighPower,
-            3 => VbusInCcCmp::MA3000HighPower,
-            _ => unreachable!(),
+            _ => VbusInCcCmp::MA3000HighPower,
         }
     }
 }
@@ -0,0 +1,227 @@
+mod types;
+
+// Re-export everything in types.rs
+pub use types::*;
+
+#[cfg(feature = "blocking")]
+use crate::hal::prelude::*;
+use crate::{common::Task, TimerMode, TimerPrescaler};
+
+#[maybe_async::maybe_async]
+impl<I2c: crate::hal::I2c, Delay: crate::hal::DelayNs> crate::NPM1300<I2c, Delay> {
+    /// Configure the timer mode and period
+    ///
+    /// The timer must be started separately, except for the wake-up timer which is started
+    /// when entering hibernate.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The timer mode, see [`TimerMode`]
+    /// * `period` - The timer period, see [`TimerPeriod`]
+    async fn configure_timer(
+        &mut self,
+        mode: TimerMode,
+        period: TimerPeriod,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        // Select mode and prescaler
+        self.device
+            .timer()
+            .timerconfig()
+            .write_async(|reg| {
+                reg.set_timermodesel(mode);
+                reg.set_timerprescaler(period.prescaler());
+            })
+            .await?;
+
+        // Write the 24-bit timer target, most significant byte first
+        let ticks = period.ticks();
+        self.device
+            .timer()
+            .timerhibyte()
+            .write_async(|reg| reg.set_timerhibyte((ticks >> 16) as u8))
+            .await?;
+        self.device
+            .timer()
+            .timermidbyte()
+            .write_async(|reg| reg.set_timermidbyte((ticks >> 8) as u8))
+            .await?;
+        self.device
+            .timer()
+            .timerlobyte()
+            .write_async(|reg| reg.set_timerlobyte(ticks as u8))
+            .await?;
+
+        // Load the new target
+        self.device
+            .timer()
+            .timertargetstrobe()
+            .dispatch_async(|command| command.set_tasktimertargetstrobe(Task::Trigger))
+            .await
+    }
+
+    /// Start the timer in its configured mode
+    pub async fn start_timer(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.device
+            .timer()
+            .timerset()
+            .dispatch_async(|command| command.set_tasktimeren(Task::Trigger))
+            .await
+    }
+
+    /// Stop the timer
+    ///
+    /// Also disables the watchdog and the boot monitor.
+    pub async fn stop_timer(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.device
+            .timer()
+            .timerclr()
+            .dispatch_async(|command| command.set_tasktimerdis(Task::Trigger))
+            .await
+    }
+
+    /// Enable the watchdog
+    ///
+    /// The watchdog must be kicked with [`Self::kick_watchdog`] before `period` elapses,
+    /// otherwise `action` is taken.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The watchdog timeout, see [`TimerPeriod`]
+    /// * `action` - The action to take on timeout, see [`WatchdogAction`]
+    pub async fn enable_watchdog(
+        &mut self,
+        period: TimerPeriod,
+        action: WatchdogAction,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        let mode = match action {
+            WatchdogAction::Warning => TimerMode::Watchdogwarning,
+            WatchdogAction::Reset => TimerMode::Watchdogreset,
+        };
+        self.stop_timer().await?;
+        self.configure_timer(mode, period).await?;
+        self.start_timer().await
+    }
+
+    /// Kick the watchdog
+    ///
+    /// Restarts the watchdog period.
+    pub async fn kick_watchdog(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.device
+            .timer()
+            .watchdogkick()
+            .dispatch_async(|command| command.set_taskwatchdogkick(Task::Trigger))
+            .await
+    }
+
+    /// Disable the watchdog
+    pub async fn disable_watchdog(&mut self) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.stop_timer().await
+    }
+
+    /// Start the general purpose timer
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The timer period, see [`TimerPeriod`]
+    pub async fn start_general_purpose_timer(
+        &mut self,
+        period: TimerPeriod,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.stop_timer().await?;
+        self.configure_timer(TimerMode::Generalpurpose, period)
+            .await?;
+        self.start_timer().await
+    }
+
+    /// Configure the wake-up timer
+    ///
+    /// The wake-up timer starts when entering hibernate and wakes the device up when `period`
+    /// elapses.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The time to spend in hibernate, see [`TimerPeriod`]
+    pub async fn configure_wake_up_timer(
+        &mut self,
+        period: TimerPeriod,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.stop_timer().await?;
+        self.configure_timer(TimerMode::Wakeup, period).await
+    }
+
+    /// Enable the boot monitor
+    ///
+    /// The host must stop the timer with [`Self::stop_timer`] once it has booted, otherwise
+    /// the nPM1300 resets when `period` elapses.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The time allowed for the host to boot, see [`TimerPeriod`]
+    pub async fn enable_boot_monitor(
+        &mut self,
+        period: TimerPeriod,
+    ) -> Result<(), crate::NPM1300Error<I2c::Error>> {
+        self.stop_timer().await?;
+        self.configure_timer(TimerMode::Bootmonitor, period).await?;
+        self.start_timer().await
+    }
+
+    /// Get the timer mode
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TimerMode)` - The configured timer mode
+    /// * `Err(NPM1300Error)` - An error occurred while reading the timer configuration
+    pub async fn get_timer_mode(&mut self) -> Result<TimerMode, crate::NPM1300Error<I2c::Error>> {
+        self.device
+            .timer()
+            .timerconfig()
+            .read_async()
+            .await?
+            .timermodesel()
+            .map_err(|_| crate::NPM1300Error::InvalidTimerMode)
+    }
+
+    /// Get the timer period
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(TimerPeriod))` - The configured timer period
+    /// * `Ok(None)` - No timer period is configured
+    /// * `Err(NPM1300Error)` - An error occurred while reading the timer registers
+    pub async fn get_timer_period(
+        &mut self,
+    ) -> Result<Option<TimerPeriod>, crate::NPM1300Error<I2c::Error>> {
+        let prescaler: TimerPrescaler = self
+            .device
+            .timer()
+            .timerconfig()
+            .read_async()
+            .await?
+            .timerprescaler();
+        let hi = self
+            .device
+            .timer()
+            .timerhibyte()
+            .read_async()
+            .await?
+            .timerhibyte();
+        let mid = self
+            .device
+            .timer()
+            .timermidbyte()
+            .read_async()
+            .await?
+            .timermidbyte();
+        let lo = self
+            .device
+            .timer()
+            .timerlobyte()
+            .read_async()
+            .await?
+            .timerlobyte();
+
+        let ticks = (hi as u32) << 16 | (mid as u32) << 8 | lo as u32;
+        Ok(TimerPeriod::from_ticks(ticks, prescaler))
+    }
+}
@@ -0,0 +1,125 @@
+use crate::TimerPrescaler;
+
+/// Period of the nPM1300 timer
+///
+/// The timer counts up to a 24-bit target in ticks of 2 ms (fast prescaler) or 16 ms (slow
+/// prescaler), giving a range of 2 ms to roughly 74.5 hours.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct TimerPeriod {
+    ticks: u32,
+    prescaler: TimerPrescaler,
+}
+
+impl TimerPeriod {
+    /// Largest timer target, the target is 24 bits wide
+    pub const MAX_TICKS: u32 = 0x00FF_FFFF;
+
+    /// Shortest period supported by the timer, one fast tick
+    pub const MIN: Self = Self {
+        ticks: 1,
+        prescaler: TimerPrescaler::Fast,
+    };
+
+    /// Longest period supported by the timer, all slow ticks
+    pub const MAX: Self = Self {
+        ticks: Self::MAX_TICKS,
+        prescaler: TimerPrescaler::Slow,
+    };
+
+    /// Create a period from a number of ticks and a prescaler
+    ///
+    /// Returns `None` if `ticks` is zero or does not fit in 24 bits.
+    pub const fn from_ticks(ticks: u32, prescaler: TimerPrescaler) -> Option<Self> {
+        if ticks == 0 || ticks > Self::MAX_TICKS {
+            return None;
+        }
+        Some(Self { ticks, prescaler })
+    }
+
+    /// Create a period from milliseconds
+    ///
+    /// The fast prescaler is used when the period fits, otherwise the slow prescaler. The
+    /// period is rounded up to the next tick.
+    ///
+    /// Returns `None` if `millis` is zero or longer than [`Self::MAX`].
+    pub const fn from_millis(millis: u32) -> Option<Self> {
+        let fast_ticks = millis.div_ceil(TimerPrescaler::Fast.tick_millis());
+        if fast_ticks <= Self::MAX_TICKS {
+            return Self::from_ticks(fast_ticks, TimerPrescaler::Fast);
+        }
+        Self::from_ticks(
+            millis.div_ceil(TimerPrescaler::Slow.tick_millis()),
+            TimerPrescaler::Slow,
+        )
+    }
+
+    /// Create a period from seconds
+    ///
+    /// Returns `None` if `secs` is zero or longer than [`Self::MAX`].
+    pub const fn from_secs(secs: u32) -> Option<Self> {
+        match secs.checked_mul(1000) {
+            Some(millis) => Self::from_millis(millis),
+            None => None,
+        }
+    }
+
+    /// Number of timer ticks
+    pub const fn ticks(&self) -> u32 {
+        self.ticks
+    }
+
+    /// Timer prescaler
+    pub const fn prescaler(&self) -> TimerPrescaler {
+        self.prescaler
+    }
+
+    /// Period in milliseconds
+    pub const fn as_millis(&self) -> u32 {
+        self.ticks * self.prescaler.tick_millis()
+    }
+}
+
+impl TryFrom<core::time::Duration> for TimerPeriod {
+    type Error = ();
+
+    fn try_from(duration: core::time::Duration) -> Result<Self, Self::Error> {
+        // Round a sub-millisecond remainder up, as the ticks are
+        let partial = !duration.subsec_nanos().is_multiple_of(1_000_000);
+        let millis = u32::try_from(duration.as_millis() + u128::from(partial)).map_err(|_| ())?;
+        Self::from_millis(millis).ok_or(())
+    }
+}
+
+impl From<TimerPeriod> for core::time::Duration {
+    fn from(period: TimerPeriod) -> Self {
+        core::time::Duration::from_millis(period.as_millis() as u64)
+    }
+}
+
+impl TimerPrescaler {
+    /// Duration of one timer tick in milliseconds
+    pub const fn tick_millis(&self) -> u32 {
+        match self {
+            TimerPrescaler::Slow => 16,
+            TimerPrescaler::Fast => 2,
+        }
+    }
+}
+
+/// Action taken by the nPM1300 when the watchdog times out
+///
+/// The watchdog is kicked with [`crate::NPM1300::kick_watchdog`]. The nPM1300 has no reset
+/// that keeps the rails up, so [`Self::Reset`] is also the power cycle option.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum WatchdogAction {
+    /// Only generate a [`crate::events::ShipHoldEvents::WATCHDOG_WARNING`] event
+    Warning,
+    /// Generate a warning event, then reset and power cycle the nPM1300
+    ///
+    /// The reset turns BUCK1, BUCK2 and the load switches off and back on, and asserts any GPIO
+    /// configured as [`crate::gpios::GpioMode::GpoReset`]. The reset reason is recorded as a
+    /// watchdog timeout.
+    Reset,
+}
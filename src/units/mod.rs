@@ -0,0 +1,81 @@
+mod types;
+
+// Re-export everything in types.rs
+pub use types::*;
+
+/// Get the index of a value in a linear range of settings
+///
+/// # Arguments
+///
+/// * `value` - The value to convert
+/// * `min` - The value of the first setting
+/// * `max` - The value of the last setting
+/// * `step` - The difference between two settings
+///
+/// # Returns
+///
+/// * `Ok(u8)` - The index of the setting, 0 for `min`
+/// * `Err(UnitError)` - `value` is outside of the range or between two settings
+pub(crate) fn step_index(value: i32, min: i32, max: i32, step: i32) -> Result<u8, UnitError> {
+    if !(min..=max).contains(&value) {
+        return Err(UnitError::OutOfRange);
+    }
+    if (value - min) % step != 0 {
+        return Err(UnitError::OutOfStep);
+    }
+    Ok(((value - min) / step) as u8)
+}
+
+/// Get the index of the setting nearest to a value in a linear range of settings
+///
+/// Values outside of the range are clamped to it, values halfway between two settings are
+/// rounded up.
+///
+/// # Arguments
+///
+/// * `value` - The value to convert
+/// * `min` - The value of the first setting
+/// * `max` - The value of the last setting
+/// * `step` - The difference between two settings
+pub(crate) fn nearest_step_index(value: i32, min: i32, max: i32, step: i32) -> u8 {
+    ((value.clamp(min, max) - min + step / 2) / step) as u8
+}
+
+#[cfg(feature = "float")]
+impl Millivolts {
+    /// Convert a voltage in volts, rounded to the nearest millivolt
+    pub fn from_volts(volts: f32) -> Self {
+        Self(libm::roundf(volts * 1000.0) as u16)
+    }
+
+    /// Voltage in volts
+    pub fn as_volts(self) -> f32 {
+        self.0 as f32 / 1000.0
+    }
+}
+
+#[cfg(feature = "float")]
+impl Milliamps {
+    /// Convert a current in amperes, rounded to the nearest milliampere
+    pub fn from_amps(amps: f32) -> Self {
+        Self(libm::roundf(amps * 1000.0) as i16)
+    }
+
+    /// Current in amperes
+    pub fn as_amps(self) -> f32 {
+        self.0 as f32 / 1000.0
+    }
+}
+
+#[cfg(feature = "float")]
+impl CentiCelsius {
+    /// Convert a temperature in degrees Celsius, rounded to the nearest hundredth of a degree
+    pub fn from_celsius_f32(celsius: f32) -> Self {
+        Self(libm::roundf(celsius * 100.0) as i16)
+    }
+
+    /// Temperature in degrees Celsius
+    pub fn as_celsius(self) -> f32 {
+        self.0 as f32 / 100.0
+    }
+}
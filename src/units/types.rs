@@ -0,0 +1,43 @@
+/// Voltage in millivolts
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Millivolts(pub u16);
+
+/// Current in milliamperes
+///
+/// Positive when the battery is charging, negative when it is discharging.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Milliamps(pub i16);
+
+/// Temperature in hundredths of a degree Celsius
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CentiCelsius(pub i16);
+
+impl CentiCelsius {
+    /// Convert a temperature in whole degrees Celsius
+    ///
+    /// Saturates below -327 °C and above 327 °C, the range of [`CentiCelsius`].
+    pub const fn from_celsius(celsius: i16) -> Self {
+        Self(celsius.saturating_mul(100))
+    }
+
+    /// Temperature in whole degrees Celsius, rounded towards zero
+    pub const fn whole_celsius(self) -> i16 {
+        self.0 / 100
+    }
+}
+
+/// Errors when converting a unit value to a register setting
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub enum UnitError {
+    #[error("value is outside of the supported range")]
+    OutOfRange,
+    #[error("value is between two supported steps")]
+    OutOfStep,
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "float")]
+
+use npm1300_rs::battery::{BatteryModel, BatteryModelError};
+
+#[test]
+fn binary_models_round_trip() {
+    let model = BatteryModel::lp803448();
+    let mut buffer = [0; 256];
+    let len = model.to_bytes(&mut buffer).unwrap();
+    assert_eq!(len, model.encoded_len());
+
+    // Values are quantized, the loaded model writes the same bytes
+    let loaded = BatteryModel::from_bytes(&buffer[..len]).unwrap();
+    assert_eq!(loaded.capacity, model.capacity);
+    let mut reencoded = [0; 256];
+    assert_eq!(loaded.to_bytes(&mut reencoded), Ok(len));
+    assert_eq!(reencoded[..len], buffer[..len]);
+}
+
+#[test]
+fn binary_models_reject_trailing_bytes() {
+    let model = BatteryModel::lp502540();
+    let mut buffer = [0; 256];
+    let len = model.to_bytes(&mut buffer).unwrap();
+    assert_eq!(
+        BatteryModel::from_bytes(&buffer[..len + 1]),
+        Err(BatteryModelError::TrailingBytes)
+    );
+    assert_eq!(
+        BatteryModel::from_bytes(&buffer[..len - 1]),
+        Err(BatteryModelError::Truncated)
+    );
+}
+
+#[test]
+fn capacities_must_fit_the_binary_format() {
+    let mut model = BatteryModel::li_ion_18650();
+    model.capacity = 65535.0;
+    assert_eq!(model.validate(), Ok(()));
+
+    model.capacity = 70000.0;
+    assert_eq!(model.validate(), Err(BatteryModelError::InvalidCapacity));
+    assert_eq!(
+        model.to_bytes(&mut [0; 256]),
+        Err(BatteryModelError::InvalidCapacity)
+    );
+}
@@ -0,0 +1,56 @@
+//! Smoke tests of the npm1300-cli binary on the simulator
+
+#![cfg(feature = "cli")]
+
+use std::process::{Command, Output};
+
+/// Run the CLI on the simulator
+fn cli(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_npm1300-cli"))
+        .arg("--sim")
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Run the CLI on the simulator and get its output, asserting it succeeds
+fn stdout(args: &[&str]) -> String {
+    let output = cli(args);
+    assert!(
+        output.status.success(),
+        "{args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn measure() {
+    assert_eq!(stdout(&["measure", "vbat"]), "3700 mV\n");
+    assert_eq!(stdout(&["measure", "ibat"]), "0 mA\n");
+    assert_eq!(stdout(&["measure", "ntc"]), "25.00 °C\n");
+}
+
+#[test]
+fn watch() {
+    let output = stdout(&["watch", "--count", "2", "--interval", "1"]);
+    let lines: Vec<_> = output.lines().collect();
+    assert_eq!(lines.len(), 3, "{output}");
+    assert!(lines[0].starts_with("vbat_mv"), "{output}");
+}
+
+#[test]
+fn dump() {
+    let output = stdout(&["dump"]);
+    assert!(output.contains("BUCK.BUCK1NORMVOUT = 0x02"), "{output}");
+}
+
+#[test]
+fn rail_and_charger() {
+    stdout(&["rail", "buck1", "voltage", "1800"]);
+    stdout(&["charger", "--current", "200", "--enable"]);
+
+    let output = cli(&["charger", "--current", "10"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unsupported value"));
+}
@@ -0,0 +1,37 @@
+#[cfg(not(feature = "blocking"))]
+use std::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use npm1300_rs::{sim::Sim, NPM1300};
+
+/// Run a future to completion
+///
+/// The simulator never blocks, so the futures of the driver complete without being woken.
+#[cfg(not(feature = "blocking"))]
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+/// Return the result of a call of the blocking driver
+///
+/// Lets the tests be shared with the async driver, whose calls return futures.
+#[cfg(feature = "blocking")]
+pub fn block_on<T>(output: T) -> T {
+    output
+}
+
+/// Create a driver on a simulated nPM1300, returning the simulator handle too
+pub fn simulated() -> (Sim, NPM1300<Sim, Sim>) {
+    let sim = Sim::new();
+    let npm1300 = NPM1300::new(sim.clone(), sim.clone());
+    (sim, npm1300)
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "sim")]
+
+mod common;
+
+use common::{block_on, simulated};
+use npm1300_rs::{
+    buck::{Buck, BuckVoltage},
+    charger::ChargeCurrent,
+    config::{diff, ConfigRegister, PmicConfig},
+    gpios::{GpioConfigBuilder, GpioMode, GpioPin},
+};
+
+/// A configuration changing the charge current, GPIO2 and the voltage of BUCK1
+fn changed_config() -> PmicConfig {
+    let mut config = PmicConfig::default();
+    config.charger.charge_current = ChargeCurrent::from_milliamps(400).unwrap();
+    config.gpios[2] = GpioConfigBuilder::new().mode(GpioMode::GpoLogic1).build();
+    config.buck1.normal_voltage = BuckVoltage::V3_3;
+    config
+}
+
+#[test]
+fn default_config_is_the_reset_configuration() {
+    let (_sim, mut npm1300) = simulated();
+    let config = block_on(npm1300.read_config()).unwrap();
+    assert_eq!(diff(&PmicConfig::default(), &config), []);
+    assert_eq!(config, PmicConfig::default());
+}
+
+#[test]
+fn applied_config_reads_back() {
+    let (_sim, mut npm1300) = simulated();
+    let config = changed_config();
+    block_on(npm1300.apply_config(&config)).unwrap();
+    assert_eq!(block_on(npm1300.read_config()).unwrap(), config);
+}
+
+#[test]
+fn update_config_writes_the_changed_registers_in_order() {
+    let (_sim, mut npm1300) = simulated();
+    let written = block_on(npm1300.update_config(&changed_config())).unwrap();
+    assert_eq!(
+        written,
+        [
+            ConfigRegister::ChargerCurrent,
+            ConfigRegister::GpioMode(GpioPin::Gpio2),
+            ConfigRegister::BuckNormalVoltage(Buck::Buck1),
+        ]
+    );
+}
+
+#[test]
+fn update_config_is_empty_after_apply() {
+    let (_sim, mut npm1300) = simulated();
+    let config = changed_config();
+    block_on(npm1300.apply_config(&config)).unwrap();
+    assert_eq!(block_on(npm1300.update_config(&config)).unwrap(), []);
+}
+
+#[test]
+fn update_config_keeps_the_other_charger_bits() {
+    let (_sim, mut npm1300) = simulated();
+    let mut config = PmicConfig::default();
+    config.charger.full_charge_current_in_cool_temp = true;
+    block_on(npm1300.apply_config(&config)).unwrap();
+
+    config.charger.charge_current = ChargeCurrent::from_milliamps(400).unwrap();
+    assert_eq!(
+        block_on(npm1300.update_config(&config)).unwrap(),
+        [ConfigRegister::ChargerCurrent]
+    );
+    let read = block_on(npm1300.read_config()).unwrap();
+    assert_eq!(diff(&read, &config), []);
+}
+
+#[test]
+fn update_config_keeps_ignore_ntc_when_changing_recharge() {
+    let (_sim, mut npm1300) = simulated();
+    let mut config = PmicConfig::default();
+    config.charger.recharge = false;
+    config.charger.ignore_ntc = true;
+    block_on(npm1300.apply_config(&config)).unwrap();
+
+    config.charger.recharge = true;
+    assert_eq!(
+        block_on(npm1300.update_config(&config)).unwrap(),
+        [ConfigRegister::ChargerRecharge]
+    );
+    let read = block_on(npm1300.read_config()).unwrap();
+    assert!(read.charger.ignore_ntc);
+    assert_eq!(diff(&read, &config), []);
+}
+
+#[test]
+fn apply_config_keeps_the_charger_bits_while_charging_is_disabled() {
+    let (_sim, mut npm1300) = simulated();
+    let mut config = PmicConfig::default();
+    config.charger.enabled = true;
+    config.charger.full_charge_current_in_cool_temp = true;
+    config.charger.recharge = false;
+    config.charger.ignore_ntc = true;
+    block_on(npm1300.apply_config(&config)).unwrap();
+    assert_eq!(block_on(npm1300.read_config()).unwrap(), config);
+
+    // Charging is disabled around the new charge current and enabled again
+    config.charger.charge_current = ChargeCurrent::from_milliamps(400).unwrap();
+    block_on(npm1300.apply_config(&config)).unwrap();
+    assert_eq!(block_on(npm1300.read_config()).unwrap(), config);
+}
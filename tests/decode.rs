@@ -0,0 +1,156 @@
+#![cfg(feature = "decode")]
+
+use npm1300_rs::decode::{
+    decode, decode_dump, register_at, register_named, registers, DecodedRegister, DumpError,
+    FieldConversion, FieldValue, RegisterKind, UnknownRegister,
+};
+
+/// Decode a value of a register by name
+fn decode_named(name: &str, value: u8) -> DecodedRegister {
+    decode(register_named(name).unwrap().address, value).unwrap()
+}
+
+/// Get the values of the fields of a decoded register
+fn field_values(name: &str, value: u8) -> Vec<FieldValue> {
+    decode_named(name, value)
+        .fields
+        .into_iter()
+        .map(|field| field.value)
+        .collect()
+}
+
+#[test]
+fn register_map_follows_manifest() {
+    assert_eq!(registers().len(), 191);
+
+    let buckenaset = register_at(0x0402).unwrap();
+    assert_eq!(buckenaset.name, "BUCK.BUCKENASET[1]");
+    assert_eq!(buckenaset.kind, RegisterKind::Command);
+
+    let buckstatus = register_named("BUCK.BUCKSTATUS").unwrap();
+    assert_eq!(buckstatus.address, 0x0434);
+    assert!(matches!(
+        buckstatus.kind,
+        RegisterKind::Register {
+            read_only: true,
+            ..
+        }
+    ));
+
+    assert!(register_at(0xFFFF).is_none());
+}
+
+#[test]
+fn fields_decode_to_enum_values() {
+    let register = decode_named("BUCK.BUCKSWCTRLSEL", 0x01);
+    assert!(register.is_valid());
+    assert_eq!(
+        register.to_string(),
+        "BUCK.BUCKSWCTRLSEL = 0x01 { BUCK1SWCTRLSEL: 1 Buck1Swctrlsel::Swctrl, \
+         BUCK2SWCTRLSEL: 0 Buck2Swctrlsel::Vsetandswctrl }"
+    );
+
+    assert_eq!(
+        field_values("VBUSIN.VBUSINILIM0", 0x0A),
+        [FieldValue::Variant("VbusInCurrentLimit::MA1000".into())]
+    );
+    assert_eq!(
+        field_values("SHIP.SHPHLDCONFIG", 0x03),
+        [FieldValue::Variant("Shphldtim::Ms96".into())]
+    );
+    assert_eq!(
+        field_values("MAIN.EVENTSADCSET", 0x09),
+        [FieldValue::Variant(
+            "AdcEvents(VBAT_READY | VSYS_READY)".into()
+        )]
+    );
+
+    let errors = decode_named("CHARGER.BCHGERRREASON", 0x0A);
+    let set: Vec<_> = errors
+        .fields
+        .iter()
+        .filter(|field| field.raw == 1)
+        .map(|field| field.field.name.as_str())
+        .collect();
+    assert_eq!(set, ["VBATSENSORERROR", "VTRICKLE"]);
+}
+
+#[test]
+fn reserved_and_invalid_values_are_flagged() {
+    // Termination voltages above 4.45 V are reserved and select 3.60 V
+    let register = decode_named("CHARGER.BCHGVTERM", 0x0E);
+    assert!(!register.is_valid());
+    assert_eq!(
+        register.fields[0].value,
+        FieldValue::Reserved(Some("ChargerTerminationVoltage::V3_60".into()))
+    );
+
+    assert_eq!(
+        field_values("POF.POFCONFIG", 0x3C)[2],
+        FieldValue::Reserved(Some("VsysThreshold::Unused15".into()))
+    );
+    assert_eq!(
+        field_values("TIMER.TIMERCONFIG", 0x06)[0],
+        FieldValue::Invalid
+    );
+
+    let register = decode_named("CHARGER.BCHGERRREASON", 0x81);
+    assert_eq!(register.reserved_bits, 0x80);
+    assert!(!register.is_valid());
+    assert!(register.to_string().ends_with(" reserved bits 0x80 set"));
+}
+
+#[test]
+fn all_driver_types_are_decoded() {
+    for register in registers() {
+        let decoded = decode(register.address, 0).unwrap();
+        for field in decoded.fields {
+            if let FieldConversion::External { path, .. } = &field.field.conversion {
+                assert_ne!(
+                    field.value,
+                    FieldValue::Integer,
+                    "{path} of {} is not decoded",
+                    register.name
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn dumps_decode_line_by_line() {
+    let dump = "\
+        # BUCKSWCTRLSEL and BCHGERRREASON\n\
+        0x040F 0x01\n\
+        \n\
+        0336, 04  # VBATLOW\n\
+        0x0336=0x00\n";
+    let registers = decode_dump(dump).unwrap();
+    assert_eq!(registers.len(), 3);
+    assert_eq!(registers[0].register.name, "BUCK.BUCKSWCTRLSEL");
+    assert_eq!(registers[1].register.name, "CHARGER.BCHGERRREASON");
+    assert_eq!(registers[1].value, 0x04);
+
+    assert_eq!(
+        decode_dump("0x040F 0x01\n0x040F\n"),
+        Err(DumpError::InvalidLine {
+            line: 2,
+            text: "0x040F".into()
+        })
+    );
+    assert_eq!(
+        decode_dump("0x040F 0x100\n"),
+        Err(DumpError::InvalidLine {
+            line: 1,
+            text: "0x040F 0x100".into()
+        })
+    );
+    assert_eq!(
+        decode_dump("0xFFFF 0x00\n"),
+        Err(DumpError::UnknownRegister {
+            line: 1,
+            address: 0xFFFF
+        })
+    );
+    assert_eq!(decode(0xFFFF, 0), Err(UnknownRegister(0xFFFF)));
+}
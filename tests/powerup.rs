@@ -0,0 +1,119 @@
+//! Import of nPM PowerUP exports
+//!
+//! `tests/powerup/npm1300.rs` is the output of `PmicConfig::to_rust_const` for
+//! `tests/powerup/npm1300.json`. After an intended change, regenerate it with
+//! `UPDATE_TRANSCRIPTS=1 cargo test --features std` and review its diff.
+
+#![cfg(feature = "std")]
+
+use std::fs;
+
+use npm1300_rs::{
+    buck::BuckVoltage,
+    charger::ChargeCurrent,
+    config::{PmicConfig, PowerUpError},
+    gpios::{Gpio, GpioConfigBuilder, GpioDriveStrength, GpioMode, GpioPullDown},
+    ldsw::LdswMode,
+    leds::LedMode,
+    NtcThermistorType,
+};
+
+// The generated constant, so the test fails to build if the generated source does not compile
+include!("powerup/npm1300.rs");
+
+const EXPORT: &str = include_str!("powerup/npm1300.json");
+
+/// Replace the value of a key of the charger in the export
+fn with_charger_value(key: &str, value: &str) -> String {
+    let mut export: serde_json::Value = serde_json::from_str(EXPORT).unwrap();
+    export["charger"][key] = serde_json::from_str(value).unwrap();
+    export.to_string()
+}
+
+#[test]
+fn import_export() {
+    let config = PmicConfig::from_powerup_json(EXPORT).unwrap();
+
+    assert!(config.charger.enabled);
+    assert_eq!(
+        config.charger.charge_current,
+        ChargeCurrent::from_milliamps(400).unwrap()
+    );
+    assert_eq!(config.adc.ntc_thermistor, NtcThermistorType::Ntc10K);
+    assert_eq!(config.adc.ntc_beta, Some(3380));
+    assert_eq!(config.buck1.normal_voltage, BuckVoltage::V1_8);
+    assert!(config.buck1.software_voltage_control);
+    assert_eq!(config.buck2.normal_voltage, BuckVoltage::V3_3);
+    assert_eq!(config.buck2.forced_pwm_gpio.gpio, Gpio::Gpio1);
+    assert_eq!(config.ldsw1.mode, LdswMode::Ldo);
+    assert_eq!(config.ldsw2.enable_gpio.gpio, Gpio::Gpio2);
+    assert_eq!(
+        config.leds,
+        [LedMode::ChargingError, LedMode::Charging, LedMode::Host]
+    );
+    assert!(config.pof.enabled);
+    assert!(config.ship.long_press_reset);
+
+    assert_eq!(
+        config.gpios[0],
+        GpioConfigBuilder::new()
+            .mode(GpioMode::GpoIrq)
+            .drive_strength(GpioDriveStrength::Drive6mA)
+            .pull_down(GpioPullDown::Disable)
+            .build()
+    );
+}
+
+#[test]
+fn generated_const() {
+    let config = PmicConfig::from_powerup_json(EXPORT).unwrap();
+    assert_eq!(NPM1300, config);
+
+    let path = [env!("CARGO_MANIFEST_DIR"), "tests", "powerup", "npm1300.rs"]
+        .iter()
+        .collect::<std::path::PathBuf>();
+    let actual = config.to_rust_const("NPM1300");
+    if std::env::var_os("UPDATE_TRANSCRIPTS").is_some() {
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+    assert!(
+        actual == fs::read_to_string(&path).unwrap(),
+        "generated const changed, run with UPDATE_TRANSCRIPTS=1 to update it"
+    );
+}
+
+#[test]
+fn device_type_is_required() {
+    let mut export: serde_json::Value = serde_json::from_str(EXPORT).unwrap();
+    export.as_object_mut().unwrap().remove("deviceType");
+    assert_eq!(
+        PmicConfig::from_powerup_json(&export.to_string()),
+        Err(PowerUpError::InvalidValue("deviceType"))
+    );
+
+    export["deviceType"] = "npm2100".into();
+    assert_eq!(
+        PmicConfig::from_powerup_json(&export.to_string()),
+        Err(PowerUpError::UnsupportedDevice)
+    );
+}
+
+#[test]
+fn invalid_charge_current() {
+    for current in ["401", "30", "802", "400.5"] {
+        assert_eq!(
+            PmicConfig::from_powerup_json(&with_charger_value("iChg", current)),
+            Err(PowerUpError::InvalidValue("iChg")),
+            "iChg {current}"
+        );
+    }
+}
+
+#[test]
+fn fractional_integer() {
+    assert_eq!(
+        PmicConfig::from_powerup_json(&with_charger_value("ntcBeta", "3380.4")),
+        Err(PowerUpError::InvalidValue("ntcBeta"))
+    );
+}
@@ -0,0 +1,125 @@
+pub const NPM1300: npm1300_rs::config::PmicConfig = npm1300_rs::config::PmicConfig {
+    vbusin: npm1300_rs::config::VbusInConfig {
+        current_limit: npm1300_rs::sysreg::VbusInCurrentLimit::MA500,
+        startup_current_limit: npm1300_rs::sysreg::VbusInCurrentLimit::MA500,
+        suspended: false,
+    },
+    charger: npm1300_rs::config::ChargerConfig {
+        enabled: true,
+        charge_current: npm1300_rs::charger::ChargeCurrent::from_milliamps(400).unwrap(),
+        discharge_current_limit: npm1300_rs::charger::DischargeCurrentLimit::High,
+        termination_voltage: npm1300_rs::charger::ChargerTerminationVoltage::V4_20,
+        warm_termination_voltage: npm1300_rs::charger::ChargerTerminationVoltage::V4_00,
+        trickle_level: npm1300_rs::charger::ChargerTrickleLevelSelect::V2_9,
+        termination_current_level: npm1300_rs::charger::ChargerTerminationCurrentLevelSelect::SEL10,
+        full_charge_current_in_cool_temp: false,
+        recharge: true,
+        ignore_ntc: false,
+        charge_if_warm: true,
+        ntc_thresholds: npm1300_rs::config::NtcThresholds { cold: 756, cool: 661, warm: 337, hot: 337 },
+        die_temperature_thresholds: npm1300_rs::config::DieTemperatureThresholds { stop: 359, resume: 372 },
+    },
+    buck1: npm1300_rs::config::BuckConfig {
+        enabled: true,
+        normal_voltage: npm1300_rs::buck::BuckVoltage::V1_8,
+        retention_voltage: npm1300_rs::buck::BuckVoltage::V1_0,
+        software_voltage_control: true,
+        forced_pwm: false,
+        pfm_only: false,
+        pull_down: false,
+        enable_gpio: npm1300_rs::config::GpioControl { gpio: npm1300_rs::gpios::Gpio::None, polarity: npm1300_rs::gpios::GpioPolarity::NotInverted },
+        retention_gpio: npm1300_rs::config::GpioControl { gpio: npm1300_rs::gpios::Gpio::None, polarity: npm1300_rs::gpios::GpioPolarity::NotInverted },
+        forced_pwm_gpio: npm1300_rs::config::GpioControl { gpio: npm1300_rs::gpios::Gpio::None, polarity: npm1300_rs::gpios::GpioPolarity::NotInverted },
+    },
+    buck2: npm1300_rs::config::BuckConfig {
+        enabled: true,
+        normal_voltage: npm1300_rs::buck::BuckVoltage::V3_3,
+        retention_voltage: npm1300_rs::buck::BuckVoltage::V1_0,
+        software_voltage_control: false,
+        forced_pwm: false,
+        pfm_only: false,
+        pull_down: true,
+        enable_gpio: npm1300_rs::config::GpioControl { gpio: npm1300_rs::gpios::Gpio::None, polarity: npm1300_rs::gpios::GpioPolarity::NotInverted },
+        retention_gpio: npm1300_rs::config::GpioControl { gpio: npm1300_rs::gpios::Gpio::None, polarity: npm1300_rs::gpios::GpioPolarity::NotInverted },
+        forced_pwm_gpio: npm1300_rs::config::GpioControl { gpio: npm1300_rs::gpios::Gpio::Gpio1, polarity: npm1300_rs::gpios::GpioPolarity::NotInverted },
+    },
+    ldsw1: npm1300_rs::config::LdswConfig {
+        enabled: true,
+        mode: npm1300_rs::ldsw::LdswMode::Ldo,
+        ldo_voltage: npm1300_rs::ldsw::LdswVoltage::V3_3,
+        soft_start_current: npm1300_rs::ldsw::LdswSoftStartCurrent::MA20,
+        active_discharge: false,
+        enable_gpio: npm1300_rs::config::GpioControl { gpio: npm1300_rs::gpios::Gpio::None, polarity: npm1300_rs::gpios::GpioPolarity::NotInverted },
+    },
+    ldsw2: npm1300_rs::config::LdswConfig {
+        enabled: false,
+        mode: npm1300_rs::ldsw::LdswMode::LoadSwitch,
+        ldo_voltage: npm1300_rs::ldsw::LdswVoltage::V1_0,
+        soft_start_current: npm1300_rs::ldsw::LdswSoftStartCurrent::MA20,
+        active_discharge: false,
+        enable_gpio: npm1300_rs::config::GpioControl { gpio: npm1300_rs::gpios::Gpio::Gpio2, polarity: npm1300_rs::gpios::GpioPolarity::NotInverted },
+    },
+    gpios: [
+        npm1300_rs::gpios::GpioConfigBuilder::new()
+            .mode(npm1300_rs::gpios::GpioMode::GpoIrq)
+            .drive_strength(npm1300_rs::gpios::GpioDriveStrength::Drive6mA)
+            .pull_up(npm1300_rs::gpios::GpioPullUp::Disable)
+            .pull_down(npm1300_rs::gpios::GpioPullDown::Disable)
+            .open_drain(npm1300_rs::gpios::GpioOpenDrain::Disable)
+            .debounce(npm1300_rs::gpios::GpioDebounce::Disable)
+            .build(),
+        npm1300_rs::gpios::GpioConfigBuilder::new()
+            .mode(npm1300_rs::gpios::GpioMode::GpiInput)
+            .drive_strength(npm1300_rs::gpios::GpioDriveStrength::Drive1mA)
+            .pull_up(npm1300_rs::gpios::GpioPullUp::Disable)
+            .pull_down(npm1300_rs::gpios::GpioPullDown::Enable)
+            .open_drain(npm1300_rs::gpios::GpioOpenDrain::Disable)
+            .debounce(npm1300_rs::gpios::GpioDebounce::Disable)
+            .build(),
+        npm1300_rs::gpios::GpioConfigBuilder::new()
+            .mode(npm1300_rs::gpios::GpioMode::GpiInput)
+            .drive_strength(npm1300_rs::gpios::GpioDriveStrength::Drive1mA)
+            .pull_up(npm1300_rs::gpios::GpioPullUp::Disable)
+            .pull_down(npm1300_rs::gpios::GpioPullDown::Enable)
+            .open_drain(npm1300_rs::gpios::GpioOpenDrain::Disable)
+            .debounce(npm1300_rs::gpios::GpioDebounce::Disable)
+            .build(),
+        npm1300_rs::gpios::GpioConfigBuilder::new()
+            .mode(npm1300_rs::gpios::GpioMode::GpoPowerLossWarning)
+            .drive_strength(npm1300_rs::gpios::GpioDriveStrength::Drive1mA)
+            .pull_up(npm1300_rs::gpios::GpioPullUp::Disable)
+            .pull_down(npm1300_rs::gpios::GpioPullDown::Disable)
+            .open_drain(npm1300_rs::gpios::GpioOpenDrain::Enable)
+            .debounce(npm1300_rs::gpios::GpioDebounce::Disable)
+            .build(),
+        npm1300_rs::gpios::GpioConfigBuilder::new()
+            .mode(npm1300_rs::gpios::GpioMode::GpiInput)
+            .drive_strength(npm1300_rs::gpios::GpioDriveStrength::Drive1mA)
+            .pull_up(npm1300_rs::gpios::GpioPullUp::Disable)
+            .pull_down(npm1300_rs::gpios::GpioPullDown::Enable)
+            .open_drain(npm1300_rs::gpios::GpioOpenDrain::Disable)
+            .debounce(npm1300_rs::gpios::GpioDebounce::Enable)
+            .build(),
+    ],
+    leds: [npm1300_rs::leds::LedMode::ChargingError, npm1300_rs::leds::LedMode::Charging, npm1300_rs::leds::LedMode::Host, ],
+    pof: npm1300_rs::config::PofConfig {
+        enabled: true,
+        warning_polarity: npm1300_rs::Pofwarnpolarity::High,
+        vsys_threshold: npm1300_rs::VsysThreshold::V30,
+    },
+    ship: npm1300_rs::config::ShipConfig {
+        press_timer: npm1300_rs::Shphldtim::Ms96,
+        long_press_reset: true,
+        long_press_reset_buttons: npm1300_rs::Longtimtwobuttonsel::Shphld,
+    },
+    adc: npm1300_rs::config::AdcConfig {
+        vbat_auto_measurement: false,
+        vbat_burst_measurement: false,
+        ntc_thermistor: npm1300_rs::NtcThermistorType::Ntc10K,
+        ntc_beta: Some(3380),
+        ntc_auto_measurement_interval: npm1300_rs::Ntcautotim::Ms1024,
+        die_temperature_auto_measurement_interval: npm1300_rs::Tempautotim::Ms4,
+        vbat_delay: 0,
+        ibat_measurement: false,
+    },
+};
@@ -0,0 +1,425 @@
+#![cfg(feature = "sim")]
+
+mod common;
+
+use std::time::Duration;
+
+use common::{block_on, simulated};
+#[cfg(feature = "blocking")]
+use embedded_hal::i2c::I2c;
+#[cfg(not(feature = "blocking"))]
+use embedded_hal_async::i2c::I2c;
+use npm1300_rs::{
+    adc::IbatStatuscodes,
+    buck::{Buck, BuckVoltage},
+    charger::{ChargeCurrent, DischargeCurrentLimit},
+    events::{AdcEvents, Events},
+    gpios::{GpioMode, GpioPin},
+    ldsw::{Ldsw, LdswMode},
+    sequence::Step,
+    sim::{Access, Sim, SimError, Transcript},
+    units::{CentiCelsius, Milliamps, Millivolts, UnitError},
+    Buck1Pwrgood, Buck2Pwrgood, Ldsw1Pwrupldo, Ldsw1Pwrupldsw, Ldsw2Pwrupldo, Ldsw2Pwrupldsw,
+    NPM1300Error, NtcThermistorType,
+};
+#[cfg(feature = "float")]
+use npm1300_rs::{battery::BatteryModel, fuel_gauge::FuelGauge};
+
+#[test]
+fn registers_start_at_reset_value() {
+    let sim = Sim::new();
+    assert_eq!(sim.register("BUCK.BUCK1NORMVOUT"), Ok(2));
+    assert_eq!(sim.register("CHARGER.BCHGISETDISCHARGEMSB"), Ok(207));
+    assert_eq!(sim.register("ADC.ADCNTCRSEL"), Ok(1));
+    assert_eq!(
+        sim.register("NOPE.NOPE"),
+        Err(SimError::UnknownRegisterName("NOPE.NOPE".into()))
+    );
+
+    sim.set_register("BUCK.BUCK1NORMVOUT", 10).unwrap();
+    sim.reset();
+    assert_eq!(sim.register("BUCK.BUCK1NORMVOUT"), Ok(2));
+}
+
+#[test]
+fn bus_errors() {
+    let mut sim = Sim::new();
+    let mut data = [0];
+    assert_eq!(
+        block_on(sim.write_read(0x6A, &[0x04, 0x08], &mut data)),
+        Err(SimError::NoAcknowledge(0x6A))
+    );
+    assert_eq!(
+        block_on(sim.write_read(0x6B, &[0xFF, 0xFF], &mut data)),
+        Err(SimError::UnknownRegister(0xFFFF))
+    );
+    assert_eq!(
+        block_on(sim.write(0x6B, &[0x04])),
+        Err(SimError::InvalidTransaction)
+    );
+}
+
+#[test]
+fn read_only_registers_ignore_writes() {
+    let mut sim = Sim::new();
+    block_on(sim.write(0x6B, &[0x04, 0x34, 0xFF])).unwrap();
+    assert_eq!(sim.register("BUCK.BUCKSTATUS"), Ok(0));
+}
+
+#[test]
+fn custom_address() {
+    let sim = Sim::new_with_address(0x6A);
+    let mut npm1300 = npm1300_rs::NPM1300::new_with_address(sim.clone(), sim.clone(), 0x6A);
+    block_on(npm1300.enable_buck1()).unwrap();
+    assert_eq!(sim.register("BUCK.BUCKSTATUS"), Ok(0x04));
+}
+
+#[test]
+fn buck_tasks_update_status() {
+    let (sim, mut npm1300) = simulated();
+
+    block_on(npm1300.set_buck2_normal_voltage(BuckVoltage::V3_3)).unwrap();
+    block_on(npm1300.enable_buck2()).unwrap();
+    let status = block_on(npm1300.get_buck_status()).unwrap();
+    assert_eq!(status.buck_1_pwrgood(), Buck1Pwrgood::Buckdisabled);
+    assert_eq!(status.buck_2_pwrgood(), Buck2Pwrgood::Buckpowered);
+    assert_eq!(
+        sim.register("BUCK.BUCKVOUTSTATUS[1]"),
+        Ok(BuckVoltage::V3_3 as u8)
+    );
+
+    block_on(npm1300.enable_buck2_forced_pwm_mode()).unwrap();
+    assert_eq!(sim.register("BUCK.BUCKSTATUS"), Ok(0xE0));
+    block_on(npm1300.disable_buck2_forced_pwm_mode()).unwrap();
+    assert_eq!(sim.register("BUCK.BUCKSTATUS"), Ok(0x40));
+
+    block_on(npm1300.disable_buck2()).unwrap();
+    let status = block_on(npm1300.get_buck_status()).unwrap();
+    assert_eq!(status.buck_2_pwrgood(), Buck2Pwrgood::Buckdisabled);
+    assert_eq!(sim.register("BUCK.BUCKVOUTSTATUS[1]"), Ok(0));
+}
+
+#[test]
+fn ldsw_tasks_follow_mode() {
+    let (_sim, mut npm1300) = simulated();
+
+    block_on(npm1300.set_ldsw2_mode(LdswMode::Ldo)).unwrap();
+    block_on(npm1300.enable_ldsw1()).unwrap();
+    block_on(npm1300.enable_ldsw2()).unwrap();
+    let status = block_on(npm1300.get_ldsw_status()).unwrap();
+    assert_eq!(status.ldsw_1_pwrupldsw(), Ldsw1Pwrupldsw::Ldswpowered);
+    assert_eq!(status.ldsw_1_pwrupldo(), Ldsw1Pwrupldo::Ldodisabled);
+    assert_eq!(status.ldsw_2_pwrupldsw(), Ldsw2Pwrupldsw::Ldswdisabled);
+    assert_eq!(status.ldsw_2_pwrupldo(), Ldsw2Pwrupldo::Ldopowered);
+
+    block_on(npm1300.disable_ldsw1()).unwrap();
+    let status = block_on(npm1300.get_ldsw_status()).unwrap();
+    assert_eq!(status.ldsw_1_pwrupldsw(), Ldsw1Pwrupldsw::Ldswdisabled);
+    assert_eq!(status.ldsw_2_pwrupldo(), Ldsw2Pwrupldo::Ldopowered);
+}
+
+#[test]
+fn set_and_clear_registers_share_a_value() {
+    let (sim, mut npm1300) = simulated();
+
+    assert!(!block_on(npm1300.is_charging_enabled()).unwrap());
+    block_on(npm1300.enable_battery_charging()).unwrap();
+    assert!(block_on(npm1300.is_charging_enabled()).unwrap());
+    assert_eq!(sim.register("CHARGER.BCHGENABLECLR"), Ok(1));
+    block_on(npm1300.disable_battery_charging()).unwrap();
+    assert!(!block_on(npm1300.is_charging_enabled()).unwrap());
+}
+
+#[test]
+fn measurements_set_adc_events() {
+    let (_sim, mut npm1300) = simulated();
+
+    block_on(npm1300.measure_vsys_millivolts()).unwrap();
+    let events = block_on(npm1300.get_events()).unwrap();
+    assert_eq!(events.adc, AdcEvents::VSYS_READY);
+
+    block_on(npm1300.clear_events(events)).unwrap();
+    let events = block_on(npm1300.get_events()).unwrap();
+    assert_eq!(events.adc, Events::empty().adc);
+}
+
+#[test]
+fn voltage_measurements() {
+    let (sim, mut npm1300) = simulated();
+    sim.set_vbat(Millivolts(3850));
+    sim.set_vsys(Millivolts(4500));
+    sim.set_vbus(Millivolts(5100));
+
+    // The ADC resolution is about 5 mV for VBAT and VSYS, and 7 mV for VBUS
+    let vbat = block_on(npm1300.measure_vbat_millivolts()).unwrap();
+    assert!(vbat.0.abs_diff(3850) <= 3, "{vbat:?}");
+    let vsys = block_on(npm1300.measure_vsys_millivolts()).unwrap();
+    assert!(vsys.0.abs_diff(4500) <= 3, "{vsys:?}");
+    let vbus = block_on(npm1300.measure_vbus_millivolts()).unwrap();
+    assert!(vbus.0.abs_diff(5100) <= 4, "{vbus:?}");
+
+    sim.set_vbat(Millivolts(3000));
+    let burst = block_on(npm1300.measure_vbat_burst_millivolts()).unwrap();
+    for vbat in burst {
+        assert!(vbat.0.abs_diff(3000) <= 3, "{vbat:?}");
+    }
+
+    assert_eq!(sim.elapsed_us(), 4 * 250);
+}
+
+#[test]
+fn temperature_measurements() {
+    let (sim, mut npm1300) = simulated();
+    sim.set_die_temperature(CentiCelsius(4200));
+    sim.set_ntc_temperature(CentiCelsius(3500), 3380);
+
+    // The die temperature resolution is about 0.8 °C
+    let die = block_on(npm1300.measure_die_temperature_centi_celsius()).unwrap();
+    assert!(die.0.abs_diff(4200) <= 40, "{die:?}");
+
+    assert_eq!(
+        block_on(npm1300.measure_ntc_centi_celsius()),
+        Err(NPM1300Error::InvalidNtcBeta)
+    );
+    block_on(npm1300.configure_ntc_resistance_kelvin(NtcThermistorType::Ntc10K, Some(3380)))
+        .unwrap();
+    let ntc = block_on(npm1300.measure_ntc_centi_celsius()).unwrap();
+    assert!(ntc.0.abs_diff(3500) <= 20, "{ntc:?}");
+
+    assert_eq!(CentiCelsius::from_celsius(35), CentiCelsius(3500));
+    assert_eq!(CentiCelsius::from_celsius(400), CentiCelsius(i16::MAX));
+    assert_eq!(CentiCelsius::from_celsius(-400), CentiCelsius(i16::MIN));
+}
+
+#[cfg(feature = "float")]
+#[test]
+fn float_ntc_beta() {
+    let (sim, mut npm1300) = simulated();
+    sim.set_ntc_temperature(CentiCelsius(3500), 3380);
+
+    assert_eq!(
+        block_on(npm1300.configure_ntc_resistance(NtcThermistorType::Ntc10K, Some(-1.0))),
+        Err(NPM1300Error::InvalidNtcBeta)
+    );
+    block_on(npm1300.configure_ntc_resistance(NtcThermistorType::Ntc10K, Some(3380.4))).unwrap();
+    let ntc = block_on(npm1300.measure_ntc()).unwrap();
+    assert!((ntc - 35.0).abs() <= 0.2, "{ntc}");
+}
+
+#[test]
+fn ibat_measurement() {
+    let (sim, mut npm1300) = simulated();
+    block_on(npm1300.configure_ibat_measurement(true)).unwrap();
+
+    block_on(npm1300.set_charger_current(Milliamps(400))).unwrap();
+    sim.set_ibat(Milliamps(300));
+    block_on(npm1300.measure_vbat_millivolts()).unwrap();
+    assert!(matches!(
+        block_on(npm1300.get_ibat_status()),
+        Ok(IbatStatuscodes::IbatStatChargeNormal)
+    ));
+    let ibat = block_on(npm1300.measure_ibat_milliamps()).unwrap();
+    assert!(ibat.0.abs_diff(300) <= 1, "{ibat:?}");
+
+    block_on(npm1300.set_discharge_current_limit(DischargeCurrentLimit::Low)).unwrap();
+    sim.set_ibat(Milliamps(-150));
+    block_on(npm1300.measure_vbat_millivolts()).unwrap();
+    let ibat = block_on(npm1300.measure_ibat_milliamps()).unwrap();
+    assert!(ibat.0.abs_diff(-150) <= 1, "{ibat:?}");
+}
+
+#[cfg(feature = "float")]
+#[test]
+fn ibat_measurement_keeps_the_fraction() {
+    let (sim, mut npm1300) = simulated();
+    block_on(npm1300.configure_ibat_measurement(true)).unwrap();
+    block_on(npm1300.set_discharge_current_limit(DischargeCurrentLimit::Low)).unwrap();
+
+    sim.set_ibat(Milliamps(-151));
+    block_on(npm1300.measure_vbat_millivolts()).unwrap();
+    let raw = block_on(npm1300.measure_ibat_raw()).unwrap();
+    let ibat = block_on(npm1300.measure_ibat()).unwrap();
+    assert_eq!(ibat, raw as f32 * -200.0 / 1024.0);
+    // The integer measurement truncates toward zero
+    assert_eq!(
+        block_on(npm1300.measure_ibat_milliamps()),
+        Ok(Milliamps(-150))
+    );
+    assert!((ibat + 151.0).abs() < 0.5, "{ibat}");
+}
+
+#[cfg(feature = "float")]
+#[test]
+fn update_fuel_gauge_measures_ibat() {
+    let (sim, mut npm1300) = simulated();
+    let model = BatteryModel::lp803448();
+    let mut fuel_gauge = FuelGauge::new(&model);
+
+    sim.set_ibat(Milliamps(-100));
+    let input = block_on(npm1300.measure_fuel_gauge_input()).unwrap();
+    assert!((input.current + 100.0).abs() < 1.0, "{input:?}");
+
+    let state =
+        block_on(npm1300.update_fuel_gauge(&mut fuel_gauge, Duration::from_secs(10))).unwrap();
+    assert!(state.time_to_empty.is_some(), "{state:?}");
+    assert_eq!(state.time_to_full, None);
+    // The IBAT measurement is left disabled as it was
+    assert_eq!(sim.register("ADC.ADCIBATMEASEN"), Ok(0));
+}
+
+#[test]
+fn charger_current_steps() {
+    let (sim, mut npm1300) = simulated();
+
+    block_on(npm1300.set_charger_current(Milliamps(202))).unwrap();
+    assert_eq!(block_on(npm1300.get_charger_config_current()), Ok(202));
+    block_on(npm1300.set_charger_current(ChargeCurrent::MAX)).unwrap();
+    assert_eq!(block_on(npm1300.get_charger_config_current()), Ok(800));
+
+    for (current, error) in [
+        (201, UnitError::OutOfStep),
+        (10, UnitError::OutOfRange),
+        (802, UnitError::OutOfRange),
+    ] {
+        assert_eq!(
+            block_on(npm1300.set_charger_current(Milliamps(current))),
+            Err(NPM1300Error::UnsupportedValue(error))
+        );
+    }
+    assert_eq!(sim.register("CHARGER.BCHGISETMSB"), Ok(200));
+}
+
+#[test]
+fn adc_snapshot_reads_all_results() {
+    let (sim, mut npm1300) = simulated();
+    sim.set_vbat(Millivolts(4000));
+    sim.set_vsys(Millivolts(4200));
+
+    block_on(npm1300.measure_vbat_millivolts()).unwrap();
+    block_on(npm1300.measure_vsys_millivolts()).unwrap();
+    let snapshot = block_on(npm1300.get_adc_snapshot()).unwrap();
+    assert!(snapshot.vbat.0.abs_diff(4000) <= 3, "{snapshot:?}");
+    assert!(snapshot.vsys.0.abs_diff(4200) <= 3, "{snapshot:?}");
+}
+
+#[test]
+fn software_reset_restores_reset_values() {
+    let (sim, mut npm1300) = simulated();
+    block_on(npm1300.set_buck1_normal_voltage(BuckVoltage::V3_0)).unwrap();
+    block_on(npm1300.enable_buck1()).unwrap();
+
+    block_on(npm1300.software_reset()).unwrap();
+    assert_eq!(sim.register("BUCK.BUCK1NORMVOUT"), Ok(2));
+    assert_eq!(sim.register("BUCK.BUCKSTATUS"), Ok(0));
+}
+
+#[test]
+fn hibernate_periods_round_up() {
+    let (sim, mut npm1300) = simulated();
+    block_on(npm1300.enter_hibernate_for(Duration::from_micros(500))).unwrap();
+    assert_eq!(sim.register("TIMER.TIMERLOBYTE"), Ok(1));
+
+    block_on(npm1300.enter_hibernate_for(Duration::from_micros(4001))).unwrap();
+    assert_eq!(sim.register("TIMER.TIMERLOBYTE"), Ok(3));
+
+    assert_eq!(
+        block_on(npm1300.enter_hibernate_for(Duration::ZERO)),
+        Err(NPM1300Error::InvalidHibernatePeriod)
+    );
+}
+
+/// Register writes of a transcript, after the last read if `after_last_read`
+fn writes(transcript: &Transcript, after_last_read: bool) -> Vec<(&'static str, u8)> {
+    let start = match transcript
+        .0
+        .iter()
+        .rposition(|access| matches!(access, Access::Read(..)))
+    {
+        Some(index) if after_last_read => index + 1,
+        _ => 0,
+    };
+    transcript.0[start..]
+        .iter()
+        .filter_map(|access| match *access {
+            Access::Write(register, value) => Some((register.name.as_str(), value)),
+            Access::Read(..) => None,
+        })
+        .collect()
+}
+
+#[test]
+fn sequence_timeout_undoes_completed_steps() {
+    let (sim, mut npm1300) = simulated();
+    let steps = [
+        Step::EnableLdsw(Ldsw::Ldsw1),
+        Step::SetGpio {
+            pin: GpioPin::Gpio2,
+            mode: GpioMode::GpoLogic1,
+        },
+        Step::SetGpio {
+            pin: GpioPin::Gpio3,
+            mode: GpioMode::GpiInput,
+        },
+        Step::SetBuckVoltage(Buck::Buck2, BuckVoltage::V1_8),
+        Step::EnableBuck(Buck::Buck2),
+        // BUCK2 never reaches 3.3 V
+        Step::WaitBuckVoltage {
+            buck: Buck::Buck2,
+            voltage: BuckVoltage::V3_3,
+            timeout_us: 1000,
+        },
+        Step::EnableLdsw(Ldsw::Ldsw2),
+    ];
+
+    sim.start_recording();
+    let result = block_on(npm1300.run_sequence(&steps));
+    assert_eq!(result, Err(NPM1300Error::Timeout));
+    assert_eq!(
+        writes(&sim.take_transcript(), true),
+        [
+            ("BUCK.BUCKENACLR[1]", 1),
+            ("GPIOS.GPIOMODE[2]", GpioMode::GpoLogic0 as u8),
+            ("LDSW.LDSWCLR[0]", 1),
+        ]
+    );
+    assert_eq!(sim.register("BUCK.BUCKSTATUS"), Ok(0));
+    assert_eq!(sim.register("LDSW.LDSWSTATUS"), Ok(0));
+}
+
+#[test]
+fn power_down_sequence_reverses_steps() {
+    let (sim, mut npm1300) = simulated();
+    let steps = [
+        Step::EnableBuck(Buck::Buck2),
+        Step::Delay { us: 500 },
+        Step::EnableLdsw(Ldsw::Ldsw1),
+        Step::SetGpio {
+            pin: GpioPin::Gpio2,
+            mode: GpioMode::GpoLogic1,
+        },
+        Step::SetGpio {
+            pin: GpioPin::Gpio3,
+            mode: GpioMode::GpiInput,
+        },
+        Step::WaitLdswPowered {
+            ldsw: Ldsw::Ldsw1,
+            timeout_us: 1000,
+        },
+    ];
+    block_on(npm1300.run_sequence(&steps)).unwrap();
+
+    sim.start_recording();
+    let start_us = sim.elapsed_us();
+    block_on(npm1300.run_power_down_sequence(&steps)).unwrap();
+    assert_eq!(sim.elapsed_us() - start_us, 500);
+    assert_eq!(
+        writes(&sim.take_transcript(), false),
+        [
+            ("GPIOS.GPIOMODE[2]", GpioMode::GpoLogic0 as u8),
+            ("LDSW.LDSWCLR[0]", 1),
+            ("BUCK.BUCKENACLR[1]", 1),
+        ]
+    );
+    assert_eq!(sim.register("BUCK.BUCKSTATUS"), Ok(0));
+    assert_eq!(sim.register("LDSW.LDSWSTATUS"), Ok(0));
+}
@@ -0,0 +1,214 @@
+#![cfg(all(feature = "sim", feature = "split", not(feature = "blocking")))]
+
+mod common;
+
+use std::{
+    convert::Infallible,
+    future::{poll_fn, Future},
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use common::{block_on, simulated};
+use embassy_sync::{blocking_mutex::raw::NoopRawMutex, mutex::Mutex};
+use embedded_hal_async::{delay::DelayNs, digital::Wait};
+use npm1300_rs::{
+    buck::BuckVoltage,
+    events::{AdcEvents, Events},
+    gpios::{GpioConfigBuilder, GpioMode, GpioPolarity, GpioStatus},
+    ldsw::{LdswMode, LdswVoltage},
+    leds::LedMode,
+    sim::Sim,
+    split::SharedNPM1300,
+    units::{Milliamps, Millivolts},
+    Buck1Pwrgood, Ldsw1Pwrupldo, NPM1300,
+};
+
+#[test]
+fn handles_drive_their_block() {
+    let (sim, npm1300) = simulated();
+    let mut shared: SharedNPM1300<NoopRawMutex, Sim, Sim> = Mutex::new(npm1300);
+    let parts = NPM1300::split(&mut shared);
+
+    block_on(parts.buck1.set_normal_voltage(BuckVoltage::V3_3)).unwrap();
+    block_on(parts.buck1.enable()).unwrap();
+    let status = block_on(parts.buck2.get_status()).unwrap();
+    assert_eq!(status.buck_1_pwrgood(), Buck1Pwrgood::Buckpowered);
+    assert_eq!(sim.register("BUCK.BUCKENASET[1]"), Ok(0));
+
+    block_on(parts.ldsw1.set_mode(LdswMode::Ldo)).unwrap();
+    block_on(parts.ldsw1.set_ldo_voltage(LdswVoltage::V1_8)).unwrap();
+    block_on(parts.ldsw1.enable()).unwrap();
+    let status = block_on(parts.ldsw1.get_status()).unwrap();
+    assert_eq!(status.ldsw_1_pwrupldo(), Ldsw1Pwrupldo::Ldopowered);
+
+    block_on(parts.charger.set_charger_current(Milliamps(200))).unwrap();
+    assert_eq!(
+        block_on(parts.charger.get_charger_config_current()),
+        Ok(200)
+    );
+
+    sim.set_vbat(Millivolts(3900));
+    let vbat = block_on(parts.adc.measure_vbat_millivolts()).unwrap();
+    assert!(vbat.0.abs_diff(3900) <= 3, "{vbat:?}");
+
+    let config = GpioConfigBuilder::new().mode(GpioMode::GpoLogic1).build();
+    block_on(parts.gpio2.configure(config)).unwrap();
+    assert_eq!(
+        sim.register("GPIOS.GPIOMODE[2]"),
+        Ok(GpioMode::GpoLogic1 as u8)
+    );
+    assert_eq!(
+        sim.register("GPIOS.GPIOMODE[3]"),
+        Ok(GpioMode::GpiInput as u8)
+    );
+    sim.set_register("GPIOS.GPIOSTATUS", 1 << 4).unwrap();
+    assert!(matches!(
+        block_on(parts.gpio4.get_status()),
+        Ok(GpioStatus::High)
+    ));
+    assert!(matches!(
+        block_on(parts.gpio0.get_status()),
+        Ok(GpioStatus::Low)
+    ));
+
+    block_on(parts.leds.configure_led1_mode(LedMode::Host)).unwrap();
+    assert_eq!(
+        sim.register("LEDDRV.LEDDRVMODESEL[1]"),
+        Ok(LedMode::Host as u8)
+    );
+
+    block_on(parts.pof.enable_power_failure_detection(true)).unwrap();
+    assert_eq!(
+        block_on(parts.pof.is_power_failure_detection_enabled()),
+        Ok(true)
+    );
+}
+
+/// Delay that yields once, standing in for a call that is still running
+struct YieldingDelay;
+
+impl DelayNs for YieldingDelay {
+    async fn delay_ns(&mut self, _ns: u32) {
+        let mut yielded = false;
+        poll_fn(|_| {
+            if yielded {
+                Poll::Ready(())
+            } else {
+                yielded = true;
+                Poll::Pending
+            }
+        })
+        .await
+    }
+}
+
+#[test]
+fn handles_wait_for_the_driver() {
+    let sim = Sim::new();
+    let mut shared: SharedNPM1300<NoopRawMutex, Sim, YieldingDelay> =
+        Mutex::new(NPM1300::new(sim.clone(), YieldingDelay));
+    let parts = NPM1300::split(&mut shared);
+    let mut context = Context::from_waker(Waker::noop());
+
+    // The ADC task holds the driver while waiting for the measurement, the call waits for it
+    let mut measure = pin!(parts.adc.measure_vbat_millivolts());
+    assert!(measure.as_mut().poll(&mut context).is_pending());
+    let mut enable = pin!(parts.buck2.enable());
+    assert!(enable.as_mut().poll(&mut context).is_pending());
+    assert_eq!(sim.register("BUCK.BUCKSTATUS"), Ok(0));
+
+    assert!(measure.as_mut().poll(&mut context).is_ready());
+    assert_eq!(enable.as_mut().poll(&mut context), Poll::Ready(Ok(())));
+    assert_eq!(sim.register("BUCK.BUCKSTATUS"), Ok(0x40));
+}
+
+#[test]
+fn regulator_gpio_control_takes_the_gpio_handle() {
+    let (sim, npm1300) = simulated();
+    let mut shared: SharedNPM1300<NoopRawMutex, Sim, Sim> = Mutex::new(npm1300);
+    let parts = NPM1300::split(&mut shared);
+
+    let config = GpioConfigBuilder::new().mode(GpioMode::GpoLogic1).build();
+    block_on(parts.gpio1.configure(config)).unwrap();
+    block_on(
+        parts
+            .buck2
+            .set_gpio_control(&parts.gpio1, GpioPolarity::Inverted),
+    )
+    .unwrap();
+    assert_eq!(
+        sim.register("GPIOS.GPIOMODE[1]"),
+        Ok(GpioMode::GpiInput as u8)
+    );
+    assert_eq!(sim.register("BUCK.BUCKENCTRL"), Ok(0x90));
+
+    block_on(
+        parts
+            .ldsw1
+            .set_gpio_control(&parts.gpio3, GpioPolarity::NotInverted),
+    )
+    .unwrap();
+    assert_eq!(sim.register("LDSW.LDSW1GPISEL"), Ok(4));
+    block_on(parts.ldsw1.disable_gpio_control()).unwrap();
+    assert_eq!(sim.register("LDSW.LDSW1GPISEL"), Ok(0));
+}
+
+/// Interrupt line that is asserted after being polled once
+struct Irq {
+    polled: bool,
+}
+
+impl embedded_hal::digital::ErrorType for Irq {
+    type Error = Infallible;
+}
+
+impl Wait for Irq {
+    async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+        poll_fn(|_| {
+            if self.polled {
+                Poll::Ready(Ok(()))
+            } else {
+                self.polled = true;
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
+    async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+
+    async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+
+    async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+
+    async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+        unimplemented!()
+    }
+}
+
+#[test]
+fn waiting_for_events_leaves_the_driver_unlocked() {
+    let (_, npm1300) = simulated();
+    let mut shared: SharedNPM1300<NoopRawMutex, Sim, Sim> = Mutex::new(npm1300);
+    let parts = NPM1300::split(&mut shared);
+    let mut context = Context::from_waker(Waker::noop());
+
+    let mut irq = Irq { polled: false };
+    let mut wait = pin!(parts.events.wait_for_events(&mut irq));
+    assert!(wait.as_mut().poll(&mut context).is_pending());
+    // The measurement raises the VBAT ready event
+    block_on(parts.adc.measure_vbat_millivolts()).unwrap();
+
+    let Poll::Ready(events) = wait.as_mut().poll(&mut context) else {
+        panic!("the interrupt line is asserted");
+    };
+    assert!(events.unwrap().adc.contains(AdcEvents::VBAT_READY));
+    assert_eq!(block_on(parts.events.get_events()), Ok(Events::empty()));
+}
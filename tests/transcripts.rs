@@ -0,0 +1,159 @@
+//! Golden transcripts of the register accesses of the high-level API
+//!
+//! Every test records the accesses of one call on the simulator and compares them to
+//! `tests/transcripts/<name>.txt`. After an intended change, regenerate the transcripts with
+//! `UPDATE_TRANSCRIPTS=1 cargo test --features sim` and review their diff.
+
+#![cfg(feature = "sim")]
+
+mod common;
+
+use std::{fs, path::PathBuf};
+
+use common::{block_on, simulated};
+use npm1300_rs::{
+    buck::{Buck, BuckVoltage},
+    events::Events,
+    ldsw::{Ldsw, LdswVoltage},
+    sequence::Step,
+    sim::Transcript,
+    units::Milliamps,
+};
+
+/// Record the register accesses of a call
+///
+/// A macro rather than a function, so the call of the blocking driver runs after the recording
+/// starts.
+macro_rules! record {
+    ($sim:expr, $call:expr) => {{
+        $sim.start_recording();
+        block_on($call).unwrap();
+        $sim.take_transcript()
+    }};
+}
+
+/// Compare a transcript to its golden transcript, or update the golden transcript
+fn assert_transcript(name: &str, transcript: &Transcript) {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "transcripts"]
+        .iter()
+        .collect::<PathBuf>()
+        .join(format!("{name}.txt"));
+    let actual = transcript.to_string();
+
+    if std::env::var_os("UPDATE_TRANSCRIPTS").is_some() {
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("missing {}, run with UPDATE_TRANSCRIPTS=1", path.display()));
+    assert!(
+        actual == expected,
+        "transcript of {name} changed, run with UPDATE_TRANSCRIPTS=1 to update it\n\
+         --- expected\n{expected}--- actual\n{actual}"
+    );
+}
+
+#[test]
+fn enable_buck1() {
+    let (sim, mut npm1300) = simulated();
+    let transcript = record!(sim, npm1300.enable_buck1());
+    assert_transcript("enable_buck1", &transcript);
+}
+
+#[test]
+fn set_buck1_normal_voltage() {
+    let (sim, mut npm1300) = simulated();
+    let transcript = record!(sim, npm1300.set_buck1_normal_voltage(BuckVoltage::V3_3));
+    assert_transcript("set_buck1_normal_voltage", &transcript);
+}
+
+#[test]
+fn set_ldo1_voltage() {
+    let (sim, mut npm1300) = simulated();
+    let transcript = record!(sim, npm1300.set_ldo1_voltage(LdswVoltage::V1_8));
+    assert_transcript("set_ldo1_voltage", &transcript);
+}
+
+#[test]
+fn set_charger_current() {
+    let (sim, mut npm1300) = simulated();
+    let transcript = record!(sim, npm1300.set_charger_current(Milliamps(400)));
+    assert_transcript("set_charger_current", &transcript);
+}
+
+#[test]
+fn set_charger_current_while_charging() {
+    let (sim, mut npm1300) = simulated();
+    block_on(npm1300.enable_battery_charging()).unwrap();
+    let transcript = record!(sim, npm1300.set_charger_current(Milliamps(400)));
+    assert_transcript("set_charger_current_while_charging", &transcript);
+}
+
+#[test]
+fn set_charger_current_while_charging_full_in_cool() {
+    let (sim, mut npm1300) = simulated();
+    block_on(npm1300.enable_battery_charger_full_charge_in_cool_temp()).unwrap();
+    block_on(npm1300.enable_battery_charging()).unwrap();
+    let transcript = record!(sim, npm1300.set_charger_current(Milliamps(400)));
+    assert_transcript(
+        "set_charger_current_while_charging_full_in_cool",
+        &transcript,
+    );
+    assert_eq!(sim.register("CHARGER.BCHGENABLESET"), Ok(0x03));
+}
+
+#[test]
+fn measure_vbat_millivolts() {
+    let (sim, mut npm1300) = simulated();
+    let transcript = record!(sim, npm1300.measure_vbat_millivolts());
+    assert_transcript("measure_vbat_millivolts", &transcript);
+}
+
+#[test]
+fn measure_vsys_millivolts() {
+    let (sim, mut npm1300) = simulated();
+    let transcript = record!(sim, npm1300.measure_vsys_millivolts());
+    assert_transcript("measure_vsys_millivolts", &transcript);
+}
+
+#[test]
+fn measure_vbus_millivolts() {
+    let (sim, mut npm1300) = simulated();
+    let transcript = record!(sim, npm1300.measure_vbus_millivolts());
+    assert_transcript("measure_vbus_millivolts", &transcript);
+}
+
+#[test]
+fn get_adc_snapshot() {
+    let (sim, mut npm1300) = simulated();
+    let transcript = record!(sim, npm1300.get_adc_snapshot());
+    assert_transcript("get_adc_snapshot", &transcript);
+}
+
+#[test]
+fn clear_events() {
+    let (sim, mut npm1300) = simulated();
+    let transcript = record!(sim, npm1300.clear_events(Events::all()));
+    assert_transcript("clear_events", &transcript);
+}
+
+#[test]
+fn run_sequence() {
+    let (sim, mut npm1300) = simulated();
+    let steps = [
+        Step::SetBuckVoltage(Buck::Buck2, BuckVoltage::V1_8),
+        Step::EnableBuck(Buck::Buck2),
+        Step::WaitBuckVoltage {
+            buck: Buck::Buck2,
+            voltage: BuckVoltage::V1_8,
+            timeout_us: 1000,
+        },
+        Step::EnableLdsw(Ldsw::Ldsw1),
+        Step::WaitLdswPowered {
+            ldsw: Ldsw::Ldsw1,
+            timeout_us: 1000,
+        },
+    ];
+    let transcript = record!(sim, npm1300.run_sequence(&steps));
+    assert_transcript("run_sequence", &transcript);
+}